@@ -15,7 +15,7 @@ impl MusicAccountProviderRepository for DummyMusicAccountProviderRepository {
         _id: Uuid,
     ) -> MusicAccountProviderRepositoryResult<Option<MusicAccountProvider>> {
         Ok(Some(MusicAccountProvider::new(
-            ProviderId::new(String::from("spotify")),
+            ProviderId::SPOTIFY,
             String::from("spotify"),
             String::from("#2cdc6a"),
             "https://accounts.spotify.com/authorize".parse().unwrap(),
@@ -26,7 +26,7 @@ impl MusicAccountProviderRepository for DummyMusicAccountProviderRepository {
 
     async fn get_all(&self) -> MusicAccountProviderRepositoryResult<Vec<MusicAccountProvider>> {
         Ok(vec![MusicAccountProvider::new(
-            ProviderId::new(String::from("spotify")),
+            ProviderId::SPOTIFY,
             String::from("spotify"),
             String::from("#2cdc6a"),
             "https://accounts.spotify.com/authorize".parse().unwrap(),
@@ -3,7 +3,12 @@ use std::collections::{HashMap, HashSet};
 use chrono::DateTime;
 use snk_core::{
     contracts::repositories::playlist_repository::{PlaylistRepository, PlaylistRepositoryResult},
-    entities::{album::Album, artist::Artist, playlist::Playlist, track::TrackWithAlbumAndArtists},
+    entities::{
+        album::Album,
+        artist::Artist,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
     value_objects::{
         image_cover::ImageCover, playlist_id::PlaylistId, product_id::ProductId,
         provider::provider_id::ProviderId,
@@ -40,10 +45,10 @@ impl PlaylistRepository for DummyPlaylistRepository {
         )])
     }
 
-    async fn create(&self, name: &str) -> PlaylistRepositoryResult<Playlist> {
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
         Ok(Playlist::new(
             PlaylistId::Owned(String::from("deezer_playlist_id")),
-            name.to_string(),
+            spec.name.clone(),
             HashSet::new(),
             String::from("me"),
             5,
@@ -53,6 +58,14 @@ impl PlaylistRepository for DummyPlaylistRepository {
         ))
     }
 
+    async fn update_metadata(
+        &self,
+        _id: &PlaylistId,
+        _spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        Ok(())
+    }
+
     async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
         Ok(Some(Playlist::new(
             id.clone(),
@@ -89,15 +102,15 @@ impl PlaylistRepository for DummyPlaylistRepository {
         _playlist_id: &PlaylistId,
     ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
         Ok(vec![TrackWithAlbumAndArtists::new(
-            HashSet::from_iter([ProductId::Provider((ProviderId::new(String::from("deezer")), String::from("deezer_track_id")))]),
+            HashSet::from_iter([ProductId::Provider((ProviderId::DEEZER, String::from("deezer_track_id")))]),
             String::from("Night Like This"),
             120000,
             HashMap::from_iter([(
-                ProviderId::new(String::from("deezer")),
+                ProviderId::DEEZER,
                 Url::parse("https://deezer.page.link/uivRrAmu77R4k27v5").unwrap(),
             )]),
             Album::new(
-              HashSet::from_iter([(ProductId::Provider((ProviderId::new(String::from("deezer")), String::from("deezer_album_id"))))]),
+              HashSet::from_iter([(ProductId::Provider((ProviderId::DEEZER, String::from("deezer_album_id"))))]),
               String::from("Nights Like This (feat. Ty Dolla $ign)"),
               DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
               .unwrap()
@@ -106,17 +119,17 @@ impl PlaylistRepository for DummyPlaylistRepository {
                   ImageCover::Sm(Url::parse("https://cdn-images.dzcdn.net/images/cover/38f53c7ad2ef060d90f500a597e0f2f5/500x500-000000-80-0-0.jpg").unwrap())
               ]),
               HashMap::from_iter([
-                  (ProviderId::new(String::from("deezer")), Url::parse("https://deezer.page.link/vF5Jvr4NnHQPhgJR6").unwrap())
+                  (ProviderId::DEEZER, Url::parse("https://deezer.page.link/vF5Jvr4NnHQPhgJR6").unwrap())
               ])
             ),
             vec![
                 Artist::new(
                     HashMap::from_iter([
-                        (ProviderId::new(String::from("deezer")), String::from("deezer_artist_id"))
+                        (ProviderId::DEEZER, String::from("deezer_artist_id"))
                     ]),
                     String::from("Khelani"),
                     HashMap::from_iter([
-                        (ProviderId::new(String::from("deezer")), Url::parse("https://www.deezer.com/us/artist/5603027").unwrap())
+                        (ProviderId::DEEZER, Url::parse("https://www.deezer.com/us/artist/5603027").unwrap())
                     ])
                 )
             ])
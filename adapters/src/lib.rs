@@ -1,2 +1,3 @@
 pub mod dummy;
+pub mod memory;
 pub mod postgres;
@@ -0,0 +1,2 @@
+pub mod smart_playlist_repository;
+pub mod transfer_job_repository;
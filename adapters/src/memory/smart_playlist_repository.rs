@@ -0,0 +1,51 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use snk_core::{
+    contracts::repositories::smart_playlist_repository::{
+        SmartPlaylistRepository, SmartPlaylistRepositoryError, SmartPlaylistRepositoryResult,
+    },
+    entities::smart_playlist::SmartPlaylist,
+};
+use uuid::Uuid;
+
+/// Smart playlists kept for the lifetime of the process
+#[derive(Default)]
+pub struct InMemorySmartPlaylistRepository {
+    smart_playlists: Mutex<HashMap<Uuid, SmartPlaylist>>,
+}
+
+impl InMemorySmartPlaylistRepository {
+    fn smart_playlists(
+        &self,
+    ) -> SmartPlaylistRepositoryResult<MutexGuard<'_, HashMap<Uuid, SmartPlaylist>>> {
+        self.smart_playlists
+            .lock()
+            .map_err(|err| SmartPlaylistRepositoryError::ServiceError(err.to_string()))
+    }
+}
+
+impl SmartPlaylistRepository for InMemorySmartPlaylistRepository {
+    async fn get(&self, id: Uuid) -> SmartPlaylistRepositoryResult<Option<SmartPlaylist>> {
+        Ok(self.smart_playlists()?.get(&id).cloned())
+    }
+
+    async fn get_all(&self) -> SmartPlaylistRepositoryResult<Vec<SmartPlaylist>> {
+        Ok(self.smart_playlists()?.values().cloned().collect())
+    }
+
+    async fn save(&self, smart_playlist: &SmartPlaylist) -> SmartPlaylistRepositoryResult<()> {
+        self.smart_playlists()?
+            .insert(smart_playlist.id(), smart_playlist.clone());
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> SmartPlaylistRepositoryResult<()> {
+        self.smart_playlists()?.remove(&id);
+
+        Ok(())
+    }
+}
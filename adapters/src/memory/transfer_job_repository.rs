@@ -0,0 +1,44 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use snk_core::{
+    contracts::repositories::transfer_job_repository::{
+        TransferJobRepository, TransferJobRepositoryError, TransferJobRepositoryResult,
+    },
+    entities::transfer_job::TransferJob,
+};
+use uuid::Uuid;
+
+/// Jobs kept for the lifetime of the process
+#[derive(Default)]
+pub struct InMemoryTransferJobRepository {
+    jobs: Mutex<HashMap<Uuid, TransferJob>>,
+}
+
+impl InMemoryTransferJobRepository {
+    fn jobs(&self) -> TransferJobRepositoryResult<MutexGuard<'_, HashMap<Uuid, TransferJob>>> {
+        self.jobs
+            .lock()
+            .map_err(|err| TransferJobRepositoryError::ServiceError(err.to_string()))
+    }
+}
+
+impl TransferJobRepository for InMemoryTransferJobRepository {
+    async fn get(&self, id: Uuid) -> TransferJobRepositoryResult<Option<TransferJob>> {
+        Ok(self.jobs()?.get(&id).cloned())
+    }
+
+    async fn save(&self, job: &TransferJob) -> TransferJobRepositoryResult<()> {
+        self.jobs()?.insert(job.id(), job.clone());
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> TransferJobRepositoryResult<()> {
+        self.jobs()?.remove(&id);
+
+        Ok(())
+    }
+}
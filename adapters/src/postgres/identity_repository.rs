@@ -0,0 +1,210 @@
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+use snk_core::{
+    contracts::repositories::identity_repository::{
+        IdentityRepository, IdentityRepositoryError, IdentityRepositoryResult,
+    },
+    entities::identity::{Identity, IdentityKind},
+    value_objects::product_id::ProductId,
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use super::{product_columns, product_id_from_columns};
+
+/// Identities of a kind, stored in the `Identity` & `IdentityProductId` tables
+pub struct PostgresIdentityRepository<K: IdentityKind> {
+    pool: PgPool,
+    kind: PhantomData<K>,
+}
+
+impl<K: IdentityKind> PostgresIdentityRepository<K> {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            kind: PhantomData,
+        }
+    }
+
+    /// Identities with all their ids, from rows (id, name, product type, product value)
+    fn identities(rows: Vec<(String, String, String, String)>) -> Vec<Identity<K>> {
+        let mut identities: HashMap<String, (String, HashSet<ProductId>)> = HashMap::new();
+
+        for (id, name, product_type, product_value) in rows {
+            let (_, ids) = identities
+                .entry(id)
+                .or_insert_with(|| (name, HashSet::new()));
+
+            if let Some(product_id) = product_id_from_columns(&product_type, product_value) {
+                ids.insert(product_id);
+            }
+        }
+
+        identities
+            .into_iter()
+            .filter_map(|(id, (name, ids))| {
+                Uuid::parse_str(&id)
+                    .ok()
+                    .map(|id| Identity::new(id, name, ids))
+            })
+            .collect()
+    }
+
+    /// Insert or replace the identity along with its ids, in a transaction
+    async fn save_in(
+        transaction: &mut Transaction<'_, Postgres>,
+        identity: &Identity<K>,
+    ) -> IdentityRepositoryResult<()> {
+        let id = identity.id().to_string();
+        let (product_types, product_values): (Vec<String>, Vec<String>) = identity
+            .ids()
+            .iter()
+            .map(|id| {
+                let (product_type, product_value) = product_columns(id);
+
+                (product_type, product_value.clone())
+            })
+            .unzip();
+
+        sqlx::query(
+            r#"INSERT INTO "Identity" ("id", "kind", "name") VALUES ($1, $2, $3)
+            ON CONFLICT ("id") DO UPDATE SET "name" = EXCLUDED."name""#,
+        )
+        .bind(&id)
+        .bind(K::NAME)
+        .bind(identity.name())
+        .execute(&mut **transaction)
+        .await
+        .map_err(service_error)?;
+
+        sqlx::query(r#"DELETE FROM "IdentityProductId" WHERE "identity_id" = $1"#)
+            .bind(&id)
+            .execute(&mut **transaction)
+            .await
+            .map_err(service_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO "IdentityProductId" ("kind", "product_type", "product_value", "identity_id")
+            SELECT $1, product_type, product_value, $4
+            FROM UNNEST($2::TEXT[], $3::TEXT[]) AS ids (product_type, product_value)"#,
+        )
+        .bind(K::NAME)
+        .bind(product_types)
+        .bind(product_values)
+        .bind(&id)
+        .execute(&mut **transaction)
+        .await
+        .map_err(service_error)?;
+
+        Ok(())
+    }
+}
+
+fn service_error(err: sqlx::Error) -> IdentityRepositoryError {
+    IdentityRepositoryError::ServiceError(format!(
+        "IdentityRepository - Failed to query - {:?}",
+        err
+    ))
+}
+
+impl<K: IdentityKind> IdentityRepository<K> for PostgresIdentityRepository<K> {
+    async fn get(&self, id: Uuid) -> IdentityRepositoryResult<Option<Identity<K>>> {
+        let rows = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"SELECT i."id", i."name", p."product_type", p."product_value"
+            FROM "Identity" i JOIN "IdentityProductId" p ON p."identity_id" = i."id"
+            WHERE i."kind" = $1 AND i."id" = $2"#,
+        )
+        .bind(K::NAME)
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(service_error)?;
+
+        Ok(Self::identities(rows).into_iter().next())
+    }
+
+    async fn find_by_ids(
+        &self,
+        ids: &HashSet<ProductId>,
+    ) -> IdentityRepositoryResult<Vec<Identity<K>>> {
+        let (product_types, product_values): (Vec<String>, Vec<String>) = ids
+            .iter()
+            .map(|id| {
+                let (product_type, product_value) = product_columns(id);
+
+                (product_type, product_value.clone())
+            })
+            .unzip();
+
+        let rows = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"SELECT i."id", i."name", p."product_type", p."product_value"
+            FROM "Identity" i JOIN "IdentityProductId" p ON p."identity_id" = i."id"
+            WHERE i."kind" = $1 AND i."id" IN (
+                SELECT "identity_id" FROM "IdentityProductId"
+                WHERE "kind" = $1 AND ("product_type", "product_value") IN (
+                    SELECT * FROM UNNEST($2::TEXT[], $3::TEXT[])
+                )
+            )"#,
+        )
+        .bind(K::NAME)
+        .bind(product_types)
+        .bind(product_values)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(service_error)?;
+
+        Ok(Self::identities(rows))
+    }
+
+    async fn save(&self, identity: &Identity<K>) -> IdentityRepositoryResult<()> {
+        let mut transaction = self.pool.begin().await.map_err(service_error)?;
+
+        Self::save_in(&mut transaction, identity).await?;
+
+        transaction.commit().await.map_err(service_error)
+    }
+
+    async fn merge(&self, identity: &Identity<K>, merged: &[Uuid]) -> IdentityRepositoryResult<()> {
+        let mut transaction = self.pool.begin().await.map_err(service_error)?;
+
+        // Ids are moved, the merged identities (& their ids) must be gone first
+        sqlx::query(r#"DELETE FROM "Identity" WHERE "kind" = $1 AND "id" = ANY($2)"#)
+            .bind(K::NAME)
+            .bind(merged.iter().map(Uuid::to_string).collect::<Vec<_>>())
+            .execute(&mut *transaction)
+            .await
+            .map_err(service_error)?;
+
+        Self::save_in(&mut transaction, identity).await?;
+
+        transaction.commit().await.map_err(service_error)
+    }
+
+    async fn split(
+        &self,
+        identity: &Identity<K>,
+        split_identity: &Identity<K>,
+    ) -> IdentityRepositoryResult<()> {
+        let mut transaction = self.pool.begin().await.map_err(service_error)?;
+
+        // Ids are moved, the identity must release them first
+        Self::save_in(&mut transaction, identity).await?;
+        Self::save_in(&mut transaction, split_identity).await?;
+
+        transaction.commit().await.map_err(service_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> IdentityRepositoryResult<()> {
+        sqlx::query(r#"DELETE FROM "Identity" WHERE "kind" = $1 AND "id" = $2"#)
+            .bind(K::NAME)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(service_error)?;
+
+        Ok(())
+    }
+}
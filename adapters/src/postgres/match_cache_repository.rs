@@ -0,0 +1,108 @@
+use chrono::{Duration, Utc};
+use snk_core::{
+    contracts::repositories::match_cache_repository::{
+        CachedMatch, MatchCacheRepository, MatchCacheRepositoryError, MatchCacheRepositoryResult,
+    },
+    value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+};
+use sqlx::PgPool;
+
+use super::product_columns;
+
+/// Match cache stored in the `MatchCache` table
+///
+/// Missing tracks are cached for a shorter time, catalogs growing over time.
+/// Lookups without market are stored under an empty market.
+pub struct PostgresMatchCacheRepository {
+    pool: PgPool,
+    ttl: Duration,
+    not_found_ttl: Duration,
+}
+
+impl PostgresMatchCacheRepository {
+    pub fn new(pool: PgPool, ttl: Duration, not_found_ttl: Duration) -> Self {
+        Self {
+            pool,
+            ttl,
+            not_found_ttl,
+        }
+    }
+}
+
+impl MatchCacheRepository for PostgresMatchCacheRepository {
+    async fn get(
+        &self,
+        product_id: &ProductId,
+        provider_id: &ProviderId,
+        market: Option<&str>,
+    ) -> MatchCacheRepositoryResult<Option<CachedMatch>> {
+        let (product_type, product_value) = product_columns(product_id);
+
+        let row = sqlx::query_as::<_, (Option<String>, Option<bool>)>(
+            r#"SELECT "provider_track_id", "playable" FROM "MatchCache"
+            WHERE "product_type" = $1 AND "product_value" = $2 AND "provider_id" = $3
+            AND "market" = $4 AND "expires_at" > $5"#,
+        )
+        .bind(product_type)
+        .bind(product_value)
+        .bind(provider_id.as_str())
+        .bind(market.unwrap_or_default())
+        .bind(Utc::now().naive_utc())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| {
+            MatchCacheRepositoryError::ServiceError(format!(
+                "MatchCacheRepository - Failed to query - {:?}",
+                err
+            ))
+        })?;
+
+        Ok(
+            row.map(|(provider_track_id, playable)| match provider_track_id {
+                Some(id) => CachedMatch::Found { id, playable },
+                None => CachedMatch::NotFound,
+            }),
+        )
+    }
+
+    async fn set(
+        &self,
+        product_id: &ProductId,
+        provider_id: &ProviderId,
+        market: Option<&str>,
+        cached_match: &CachedMatch,
+    ) -> MatchCacheRepositoryResult<()> {
+        let (product_type, product_value) = product_columns(product_id);
+        let (provider_track_id, playable, ttl) = match cached_match {
+            CachedMatch::Found { id, playable } => (Some(id), *playable, self.ttl),
+            CachedMatch::NotFound => (None, None, self.not_found_ttl),
+        };
+
+        sqlx::query(
+            r#"INSERT INTO "MatchCache"
+            ("product_type", "product_value", "provider_id", "market", "provider_track_id", "playable", "expires_at")
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT ("product_type", "product_value", "provider_id", "market") DO UPDATE
+            SET "provider_track_id" = EXCLUDED."provider_track_id",
+            "playable" = EXCLUDED."playable",
+            "expires_at" = EXCLUDED."expires_at""#,
+        )
+        .bind(product_type)
+        .bind(product_value)
+        .bind(provider_id.as_str())
+        .bind(market.unwrap_or_default())
+        .bind(provider_track_id)
+        .bind(playable)
+        .bind((Utc::now() + ttl).naive_utc())
+        .execute(&self.pool)
+        .await
+        .map_err(|err| {
+            MatchCacheRepositoryError::ServiceError(format!(
+                "MatchCacheRepository - Failed to query - {:?}",
+                err
+            ))
+        })?;
+
+        Ok(())
+    }
+}
@@ -1 +1,37 @@
+pub mod identity_repository;
+pub mod match_cache_repository;
+pub mod transfer_job_repository;
 
+use snk_core::value_objects::{
+    barcode::{Ean, Upc},
+    isrc::Isrc,
+    product_id::ProductId,
+    provider::provider_id::ProviderId,
+};
+
+/// Type & value columns of a product id
+pub(crate) fn product_columns(product_id: &ProductId) -> (String, &String) {
+    let product_type = match product_id {
+        ProductId::ISRC(_) => "isrc".to_string(),
+        ProductId::UPC(_) => "upc".to_string(),
+        ProductId::EAN(_) => "ean".to_string(),
+        ProductId::Provider((provider_id, _)) => format!("provider:{}", provider_id.as_str()),
+    };
+
+    (product_type, product_id.id())
+}
+
+/// Product id stored as type & value columns, if the type is known & the value valid
+pub(crate) fn product_id_from_columns(
+    product_type: &str,
+    product_value: String,
+) -> Option<ProductId> {
+    match product_type {
+        "isrc" => Isrc::parse(&product_value).ok().map(ProductId::ISRC),
+        "upc" => Upc::parse(&product_value).ok().map(ProductId::UPC),
+        "ean" => Ean::parse(&product_value).ok().map(ProductId::EAN),
+        _ => product_type.strip_prefix("provider:").map(|provider_id| {
+            ProductId::Provider((ProviderId::new(provider_id.to_string()), product_value))
+        }),
+    }
+}
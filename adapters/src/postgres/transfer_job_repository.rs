@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use snk_core::{
+    contracts::repositories::transfer_job_repository::{
+        TransferJobRepository, TransferJobRepositoryError, TransferJobRepositoryResult,
+    },
+    entities::{playlist::PlaylistSpec, transfer_job::TransferJob},
+    value_objects::{cover_image::CoverImage, playlist_id::PlaylistId},
+};
+use sqlx::PgPool;
+use url::Url;
+use uuid::Uuid;
+
+/// Row of the `TransferJob` table, without its id
+type TransferJobRow = (
+    Option<String>,
+    Option<String>,
+    Option<bool>,
+    Option<bool>,
+    Option<String>,
+    Option<Vec<u8>>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Jobs stored in the `TransferJob` & `TransferJobCheckpoint` tables
+pub struct PostgresTransferJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresTransferJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn service_error(err: sqlx::Error) -> TransferJobRepositoryError {
+    TransferJobRepositoryError::ServiceError(format!(
+        "TransferJobRepository - Failed to query - {:?}",
+        err
+    ))
+}
+
+/// Type & value columns of a playlist id
+fn playlist_columns(playlist_id: &PlaylistId) -> (&str, Option<&String>) {
+    match playlist_id {
+        PlaylistId::LikedSongs => ("likedSongs", None),
+        PlaylistId::Owned(id) => ("owned", Some(id)),
+    }
+}
+
+/// Playlist id stored as type & value columns, if the type is known
+fn playlist_id_from_columns(
+    playlist_type: Option<String>,
+    playlist_value: Option<String>,
+) -> Option<PlaylistId> {
+    match (playlist_type.as_deref(), playlist_value) {
+        (Some("likedSongs"), _) => Some(PlaylistId::LikedSongs),
+        (Some("owned"), Some(id)) => Some(PlaylistId::Owned(id)),
+        _ => None,
+    }
+}
+
+impl TransferJobRepository for PostgresTransferJobRepository {
+    async fn get(&self, id: Uuid) -> TransferJobRepositoryResult<Option<TransferJob>> {
+        let row = sqlx::query_as::<_, TransferJobRow>(
+            r#"SELECT "playlist_name", "playlist_description", "playlist_public",
+            "playlist_collaborative", "playlist_cover_url", "playlist_cover_jpeg",
+            "destination_playlist_type", "destination_playlist_id"
+            FROM "TransferJob" WHERE "id" = $1"#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(service_error)?;
+
+        let Some((
+            name,
+            description,
+            public,
+            collaborative,
+            cover_url,
+            cover_jpeg,
+            playlist_type,
+            playlist_value,
+        )) = row
+        else {
+            return Ok(None);
+        };
+
+        let checkpoints = sqlx::query_as::<_, (String, Option<String>)>(
+            r#"SELECT "key", "destination_id" FROM "TransferJobCheckpoint" WHERE "job_id" = $1"#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(service_error)?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        let cover = match (cover_jpeg, cover_url) {
+            (Some(jpeg), _) => Some(CoverImage::Jpeg(jpeg)),
+            (None, url) => url
+                .and_then(|url| Url::parse(&url).ok())
+                .map(CoverImage::Url),
+        };
+        let playlist_spec = name.map(|name| {
+            PlaylistSpec::new(name)
+                .with_description(description)
+                .with_public(public)
+                .with_collaborative(collaborative)
+                .with_cover(cover)
+        });
+
+        Ok(Some(TransferJob::new(
+            id,
+            playlist_spec,
+            playlist_id_from_columns(playlist_type, playlist_value),
+            checkpoints,
+        )))
+    }
+
+    async fn save(&self, job: &TransferJob) -> TransferJobRepositoryResult<()> {
+        let id = job.id().to_string();
+        let spec = job.playlist_spec();
+        let (cover_url, cover_jpeg) = match spec.and_then(|spec| spec.cover.as_ref()) {
+            Some(CoverImage::Url(url)) => (Some(url.to_string()), None),
+            Some(CoverImage::Jpeg(jpeg)) => (None, Some(jpeg)),
+            None => (None, None),
+        };
+        let (playlist_type, playlist_value) =
+            job.destination_playlist_id().map(playlist_columns).unzip();
+        let (keys, destination_ids): (Vec<&String>, Vec<Option<&String>>) = job
+            .checkpoints()
+            .iter()
+            .map(|(key, destination_id)| (key, destination_id.as_ref()))
+            .unzip();
+
+        let mut transaction = self.pool.begin().await.map_err(service_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO "TransferJob" ("id", "playlist_name", "playlist_description",
+            "playlist_public", "playlist_collaborative", "playlist_cover_url", "playlist_cover_jpeg",
+            "destination_playlist_type", "destination_playlist_id", "updated_at")
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT ("id") DO UPDATE SET
+            "playlist_name" = EXCLUDED."playlist_name",
+            "playlist_description" = EXCLUDED."playlist_description",
+            "playlist_public" = EXCLUDED."playlist_public",
+            "playlist_collaborative" = EXCLUDED."playlist_collaborative",
+            "playlist_cover_url" = EXCLUDED."playlist_cover_url",
+            "playlist_cover_jpeg" = EXCLUDED."playlist_cover_jpeg",
+            "destination_playlist_type" = EXCLUDED."destination_playlist_type",
+            "destination_playlist_id" = EXCLUDED."destination_playlist_id",
+            "updated_at" = EXCLUDED."updated_at""#,
+        )
+        .bind(&id)
+        .bind(spec.map(|spec| &spec.name))
+        .bind(spec.and_then(|spec| spec.description.as_ref()))
+        .bind(spec.and_then(|spec| spec.public))
+        .bind(spec.and_then(|spec| spec.collaborative))
+        .bind(cover_url)
+        .bind(cover_jpeg)
+        .bind(playlist_type)
+        .bind(playlist_value.flatten())
+        .bind(Utc::now().naive_utc())
+        .execute(&mut *transaction)
+        .await
+        .map_err(service_error)?;
+
+        // Checkpoints missing from the job are removed, the others upserted
+        sqlx::query(
+            r#"DELETE FROM "TransferJobCheckpoint" WHERE "job_id" = $1 AND NOT ("key" = ANY($2))"#,
+        )
+        .bind(&id)
+        .bind(&keys)
+        .execute(&mut *transaction)
+        .await
+        .map_err(service_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO "TransferJobCheckpoint" ("job_id", "key", "destination_id")
+            SELECT $1, key, destination_id
+            FROM UNNEST($2::TEXT[], $3::TEXT[]) AS checkpoints (key, destination_id)
+            ON CONFLICT ("job_id", "key") DO UPDATE SET "destination_id" = EXCLUDED."destination_id""#,
+        )
+        .bind(&id)
+        .bind(&keys)
+        .bind(&destination_ids)
+        .execute(&mut *transaction)
+        .await
+        .map_err(service_error)?;
+
+        transaction.commit().await.map_err(service_error)
+    }
+
+    async fn delete(&self, id: Uuid) -> TransferJobRepositoryResult<()> {
+        sqlx::query(r#"DELETE FROM "TransferJob" WHERE "id" = $1"#)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(service_error)?;
+
+        Ok(())
+    }
+}
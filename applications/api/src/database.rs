@@ -0,0 +1,40 @@
+use std::{env, sync::LazyLock};
+
+use adapters::postgres::{
+    identity_repository::PostgresIdentityRepository,
+    match_cache_repository::PostgresMatchCacheRepository,
+    transfer_job_repository::PostgresTransferJobRepository,
+};
+use chrono::Duration;
+use snk_core::entities::identity::RecordingKind;
+use sqlx::PgPool;
+
+/// Days before a match is looked up again
+static MATCH_TTL_DAYS: i64 = 30;
+
+/// Days before a track missing from a catalog is looked up again
+static NOT_FOUND_TTL_DAYS: i64 = 7;
+
+/// Connections to the database of `DATABASE_URL`, opened on first use
+pub static POOL: LazyLock<PgPool> = LazyLock::new(|| {
+    let url = env::var("DATABASE_URL").expect("DATABASE_URL: missing environment variable");
+
+    PgPool::connect_lazy(&url).expect("DATABASE_URL: invalid database URL")
+});
+
+/// Matches of the tracks in the catalog of each provider, shared by all users
+pub static MATCH_CACHE: LazyLock<PostgresMatchCacheRepository> = LazyLock::new(|| {
+    PostgresMatchCacheRepository::new(
+        POOL.clone(),
+        Duration::days(MATCH_TTL_DAYS),
+        Duration::days(NOT_FOUND_TTL_DAYS),
+    )
+});
+
+/// Canonical recordings, linking the ids of a track across providers
+pub static RECORDINGS: LazyLock<PostgresIdentityRepository<RecordingKind>> =
+    LazyLock::new(|| PostgresIdentityRepository::new(POOL.clone()));
+
+/// Progress of the transfers, so that a retry continues where it stopped
+pub static TRANSFER_JOBS: LazyLock<PostgresTransferJobRepository> =
+    LazyLock::new(|| PostgresTransferJobRepository::new(POOL.clone()));
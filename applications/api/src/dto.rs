@@ -0,0 +1,238 @@
+use serde::Serialize;
+use snk_core::{
+    entities::account_profile::AccountProfile,
+    services::deduplicator::{DuplicateGroup, DuplicateReason, PlaylistTrack},
+    use_cases::{
+        merge_playlists::MergeReport,
+        split_playlist::SplitReport,
+        transfer_tracks::{CreatedPlaylist, TransferReport, TransferStatus},
+    },
+};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum TransferStatusDto {
+    Transferred { id: String },
+    NotFound,
+    Failed { error: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferReportItemDto {
+    pub name: String,
+    pub artists: Vec<String>,
+    #[serde(flatten)]
+    pub status: TransferStatusDto,
+    /// Transferred, yet not playable in the country of the account
+    pub unavailable: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedPlaylistDto {
+    pub name: String,
+    /// Missing in dry-run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl From<CreatedPlaylist> for CreatedPlaylistDto {
+    fn from(created_playlist: CreatedPlaylist) -> Self {
+        Self {
+            name: created_playlist.name,
+            id: created_playlist.id.map(|id| id.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferReportDto {
+    pub dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_playlist: Option<CreatedPlaylistDto>,
+    pub transferred: usize,
+    pub not_found: usize,
+    pub failed: usize,
+    pub unavailable: usize,
+    pub items: Vec<TransferReportItemDto>,
+}
+
+impl From<TransferReport> for TransferReportDto {
+    fn from(report: TransferReport) -> Self {
+        Self {
+            dry_run: report.dry_run,
+            created_playlist: report.created_playlist.clone().map(Into::into),
+            transferred: report.transferred(),
+            not_found: report.not_found(),
+            failed: report.failed(),
+            unavailable: report.unavailable(),
+            items: report
+                .items
+                .into_iter()
+                .map(|item| TransferReportItemDto {
+                    name: item.name,
+                    artists: item.artists,
+                    status: match item.status {
+                        TransferStatus::Transferred(id) => TransferStatusDto::Transferred { id },
+                        TransferStatus::NotFound => TransferStatusDto::NotFound,
+                        TransferStatus::Failed(error) => TransferStatusDto::Failed { error },
+                    },
+                    unavailable: item.unavailable,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Report of a transfer, with the job to send again to resume it
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferJobReportDto {
+    pub job_id: String,
+    #[serde(flatten)]
+    pub transfer: TransferReportDto,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateTrackDto {
+    pub position: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub artists: Vec<String>,
+}
+
+impl From<PlaylistTrack> for DuplicateTrackDto {
+    fn from(track: PlaylistTrack) -> Self {
+        Self {
+            position: track.position,
+            id: track.id,
+            name: track.name,
+            artists: track.artists,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateReasonDto {
+    ProviderId,
+    Isrc,
+    Identity,
+    Metadata,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateDto {
+    #[serde(flatten)]
+    pub track: DuplicateTrackDto,
+    pub reason: DuplicateReasonDto,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroupDto {
+    pub kept: DuplicateTrackDto,
+    pub duplicates: Vec<DuplicateDto>,
+}
+
+impl From<DuplicateGroup> for DuplicateGroupDto {
+    fn from(group: DuplicateGroup) -> Self {
+        Self {
+            kept: group.kept.into(),
+            duplicates: group
+                .duplicates
+                .into_iter()
+                .map(|duplicate| DuplicateDto {
+                    track: duplicate.track.into(),
+                    reason: match duplicate.reason {
+                        DuplicateReason::ProviderId => DuplicateReasonDto::ProviderId,
+                        DuplicateReason::Isrc => DuplicateReasonDto::Isrc,
+                        DuplicateReason::Identity => DuplicateReasonDto::Identity,
+                        DuplicateReason::Metadata => DuplicateReasonDto::Metadata,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReportDto {
+    pub source_tracks: usize,
+    /// Positions are the ones in the sources put end to end
+    pub duplicates: Vec<DuplicateGroupDto>,
+    #[serde(flatten)]
+    pub transfer: TransferReportDto,
+}
+
+impl From<MergeReport> for MergeReportDto {
+    fn from(report: MergeReport) -> Self {
+        Self {
+            source_tracks: report.source_tracks,
+            duplicates: report.duplicates.into_iter().map(Into::into).collect(),
+            transfer: report.transfer.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitPartDto {
+    pub name: String,
+    /// Missing in dry-run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub track_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitReportDto {
+    pub dry_run: bool,
+    pub parts: Vec<SplitPartDto>,
+    pub skipped: usize,
+}
+
+impl From<SplitReport> for SplitReportDto {
+    fn from(report: SplitReport) -> Self {
+        Self {
+            dry_run: report.dry_run,
+            parts: report
+                .parts
+                .into_iter()
+                .map(|part| SplitPartDto {
+                    name: part.name,
+                    id: part.id.map(|id| id.to_string()),
+                    track_ids: part.track_ids,
+                })
+                .collect(),
+            skipped: report.skipped,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProfileDto {
+    pub id: String,
+    pub name: String,
+    /// ISO 3166-1 alpha-2, to send as `x-provider-country`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+impl From<AccountProfile> for AccountProfileDto {
+    fn from(profile: AccountProfile) -> Self {
+        Self {
+            id: profile.id().clone(),
+            name: profile.name().clone(),
+            country: profile.country_code().cloned(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use snk_core::services::provider_registry::ProviderRegistryError;
+
+/// Errors returned by the routes, rendered as `{"error": "..."}`
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    NotFound(String),
+    Internal(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::Internal(message) => {
+                tracing::error!("{}", message);
+
+                (StatusCode::INTERNAL_SERVER_ERROR, message)
+            }
+        };
+
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+impl From<ProviderRegistryError> for ApiError {
+    fn from(err: ProviderRegistryError) -> Self {
+        match err {
+            ProviderRegistryError::UnknownProvider(provider_id) => {
+                ApiError::BadRequest(format!("{} is not supported", provider_id))
+            }
+            ProviderRegistryError::MissingCredentials(message) => ApiError::Unauthorized(message),
+            ProviderRegistryError::ServiceError(message) => ApiError::Internal(message),
+        }
+    }
+}
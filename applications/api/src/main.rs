@@ -1,3 +1,11 @@
+mod database;
+mod dto;
+mod error;
+mod providers;
+mod routes;
+
+use std::sync::LazyLock;
+
 use axum::{routing::get, Router};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -25,16 +33,29 @@ async fn main() {
     // - GET  /providers/grant: OAuth2 callback for permissions granting
     // - GET  /providers/status: Get connection status of user's providers
     // - DELETE /providers/revoke: Revoke provider access
+    // - GET  /providers/{providerType}/profile: Profile of the account, with its country
     //
     // - GET    /providers/{providerType}/playlists : Get playlists
     // - POST   /providers/{providerType}/playlists : Create playlist
     // - DELETE /providers/{providerType}/playlists/{playlistId} : Delete playlist
     // - GET    /providers/{providerType}/playlists/{playlistId} : Fetch playlist info
+    // - PATCH  /providers/{providerType}/playlists/{playlistId} : Update name, description & visibility
     // - GET    /providers/{providerType}/playlists/{playlistId}/tracks : Fetch tracks of the playlist
     // - POST   /providers/{providerType}/playlist/{playlistId}/tracks : Add tracks to playlist
     // - DELETE /providers/{providerType}/playlist/{playlistId}/tracks : Delete tracks from playlist
+    //
+    // - POST   /providers/{providerType}/playlists/{playlistId}/import : Import tracks from a CSV file
+    // - GET    /providers/{providerType}/playlists/{playlistId}/export : Export tracks as a CSV file
+    // - POST   /providers/{providerType}/playlists/transfer : Transfer a playlist of any provider, resumable
+    // - POST   /providers/{providerType}/playlists/merge : Merge playlists of any provider into a new one
+    // - POST   /providers/{providerType}/playlists/{playlistId}/split : Split a playlist into new ones
+
+    // Fail on start rather than on the first request
+    LazyLock::force(&database::POOL);
 
-    let app = Router::new().route("/", get(health));
+    let app = Router::new()
+        .route("/", get(health))
+        .merge(routes::router());
 
     // TODO import from environment (PORT, HOST)
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
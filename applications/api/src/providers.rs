@@ -0,0 +1,73 @@
+use std::sync::LazyLock;
+
+use integrations::registry::provider_registry;
+use serde::Deserialize;
+use snk_core::{
+    entities::music_account_provider::MusicAccountProvider,
+    services::provider_registry::ProviderRegistry,
+    value_objects::provider::provider_id::ProviderId,
+};
+use url::Url;
+
+/// Repositories of the providers, built from the credentials of each request
+pub static PROVIDER_REGISTRY: LazyLock<ProviderRegistry> = LazyLock::new(provider_registry);
+
+/// Providers definitions, shared with the database seed
+static SEED_PROVIDERS: [&str; 6] = [
+    include_str!("../../seed/providers/deezer.json"),
+    include_str!("../../seed/providers/lastfm.json"),
+    include_str!("../../seed/providers/soundcloud.json"),
+    include_str!("../../seed/providers/spotify.json"),
+    include_str!("../../seed/providers/tidal.json"),
+    include_str!("../../seed/providers/youtube.json"),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SeedOAuth2 {
+    base_url: Url,
+    token_url: Url,
+    scope: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeedProvider {
+    id: String,
+    name: String,
+    color: String,
+    oauth2: SeedOAuth2,
+}
+
+/// Provider by its id (ex: "deezer")
+pub fn music_account_provider(id: &str) -> Option<MusicAccountProvider> {
+    SEED_PROVIDERS
+        .iter()
+        .filter_map(|content| serde_json::from_str::<SeedProvider>(content).ok())
+        .find(|provider| provider.id == id)
+        .map(|provider| {
+            MusicAccountProvider::new(
+                ProviderId::new(provider.id),
+                provider.name,
+                provider.color,
+                provider.oauth2.base_url,
+                provider.oauth2.token_url,
+                provider.oauth2.scope,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{music_account_provider, SeedProvider, PROVIDER_REGISTRY, SEED_PROVIDERS};
+
+    #[test]
+    fn test_seed_providers() {
+        for content in SEED_PROVIDERS {
+            assert!(serde_json::from_str::<SeedProvider>(content).is_ok());
+        }
+
+        assert!(music_account_provider("deezer").is_some());
+        assert!(PROVIDER_REGISTRY.is_registered(music_account_provider("lastfm").unwrap().id()));
+        assert!(music_account_provider("unknown").is_none());
+    }
+}
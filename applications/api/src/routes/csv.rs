@@ -0,0 +1,192 @@
+use axum::{
+    extract::{Path, Query},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        HeaderMap,
+    },
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use integrations::csv::{
+    mapping::{CsvColumnMapping, DurationUnit},
+    read_tracks, write_tracks,
+};
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::playlist_repository::PlaylistRepository,
+    services::identity_graph::IdentityGraph, use_cases::transfer_tracks::TransferTracks,
+};
+
+use crate::{
+    database::{MATCH_CACHE, RECORDINGS},
+    dto::TransferReportDto,
+    error::ApiError,
+    providers::PROVIDER_REGISTRY,
+};
+
+use super::{market, playlist_id, provider, provider_credentials};
+
+pub fn router() -> Router {
+    Router::new()
+        .route(
+            "/providers/:provider_id/playlists/:playlist_id/import",
+            post(import_csv),
+        )
+        .route(
+            "/providers/:provider_id/playlists/:playlist_id/export",
+            get(export_csv),
+        )
+}
+
+/// Column mapping, a preset ("default", "exportify") optionally overridden column by column
+#[derive(Debug, Default, Deserialize)]
+pub struct CsvMappingQuery {
+    preset: Option<String>,
+    title: Option<String>,
+    artists: Option<String>,
+    album: Option<String>,
+    isrc: Option<String>,
+    duration: Option<String>,
+    provider_url: Option<String>,
+    artists_separator: Option<String>,
+    /// "ms" or "s"
+    duration_unit: Option<String>,
+    delimiter: Option<char>,
+}
+
+impl TryFrom<CsvMappingQuery> for CsvColumnMapping {
+    type Error = ApiError;
+
+    fn try_from(query: CsvMappingQuery) -> Result<Self, Self::Error> {
+        let mut mapping = match query.preset.as_deref() {
+            Some(preset) => CsvColumnMapping::preset(preset)
+                .ok_or(ApiError::BadRequest(format!("unknown preset {}", preset)))?,
+            None => CsvColumnMapping::default(),
+        };
+
+        if let Some(title) = query.title {
+            mapping.title = title;
+        }
+
+        // Empty values disable a column
+        let columns = [
+            (&mut mapping.artists, query.artists),
+            (&mut mapping.album, query.album),
+            (&mut mapping.isrc, query.isrc),
+            (&mut mapping.duration, query.duration),
+            (&mut mapping.provider_url, query.provider_url),
+        ];
+
+        for (column, value) in columns {
+            if let Some(value) = value {
+                *column = Some(value).filter(|value| !value.is_empty());
+            }
+        }
+
+        if let Some(artists_separator) = query.artists_separator {
+            mapping.artists_separator = artists_separator;
+        }
+
+        mapping.duration_unit = match query.duration_unit.as_deref() {
+            Some("ms") => DurationUnit::Milliseconds,
+            Some("s") => DurationUnit::Seconds,
+            Some(other) => {
+                return Err(ApiError::BadRequest(format!(
+                    "invalid duration unit {}",
+                    other
+                )))
+            }
+            None => mapping.duration_unit,
+        };
+
+        if let Some(delimiter) = query.delimiter {
+            mapping.delimiter = u8::try_from(delimiter)
+                .map_err(|_| ApiError::BadRequest("delimiter must be ASCII".to_string()))?;
+        }
+
+        Ok(mapping)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportQuery {
+    /// Match the tracks without adding them to the playlist
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Import the tracks of a CSV file (request body) into a playlist,
+/// each track being matched in the catalog of the provider
+async fn import_csv(
+    Path((provider_id, id)): Path<(String, String)>,
+    Query(query): Query<CsvMappingQuery>,
+    Query(import_query): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<TransferReportDto>, ApiError> {
+    let mapping = CsvColumnMapping::try_from(query)?;
+    let tracks = read_tracks(body.as_bytes(), &mapping).map_err(ApiError::BadRequest)?;
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let track_search = repositories
+        .track_search
+        .clone()
+        .ok_or(ApiError::BadRequest(format!(
+            "import is not supported for {}",
+            provider_id
+        )))?;
+
+    let report = TransferTracks::new(
+        provider.id().clone(),
+        &repositories.playlists,
+        &track_search,
+    )
+    .with_match_cache(&*MATCH_CACHE)
+    .with_identity_graph(IdentityGraph::new(&*RECORDINGS))
+    .with_dry_run(import_query.dry_run)
+    .with_market(market(&credentials, &repositories).await)
+    .execute(&tracks, &playlist_id(id))
+    .await
+    .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(Json(report.into()))
+}
+
+/// Export the tracks of a playlist as a CSV file
+async fn export_csv(
+    Path((provider_id, id)): Path<(String, String)>,
+    Query(query): Query<CsvMappingQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let mapping = CsvColumnMapping::try_from(query)?;
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let playlist_id = playlist_id(id);
+
+    let tracks = repositories
+        .playlists
+        .get_tracks(&playlist_id)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    let mut content = vec![];
+
+    write_tracks(&mut content, &tracks, &mapping).map_err(ApiError::Internal)?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}-{}.csv\"",
+                    provider_id, playlist_id
+                ),
+            ),
+        ],
+        content,
+    ))
+}
@@ -0,0 +1,105 @@
+pub mod csv;
+pub mod playlists;
+pub mod profile;
+
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap},
+    Router,
+};
+use snk_core::{
+    contracts::repositories::account_profile_repository::AccountProfileRepository,
+    entities::{
+        music_account_provider::MusicAccountProvider, provider_credentials::ProviderCredentials,
+    },
+    services::provider_registry::ProviderRepositories,
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+
+use crate::{error::ApiError, providers::music_account_provider};
+
+/// Id or name of the user on the provider, when needed (Spotify, Tidal, Last.fm)
+static ACCOUNT_HEADER: &str = "x-provider-account";
+
+/// Country of the user on the provider (Tidal), used as the market of Spotify requests
+///
+/// Optional for the providers sharing the profile of the account, its country being used instead.
+static COUNTRY_HEADER: &str = "x-provider-country";
+
+pub fn router() -> Router {
+    Router::new()
+        .merge(csv::router())
+        .merge(playlists::router())
+        .merge(profile::router())
+}
+
+/// Provider from the path
+pub fn provider(provider_id: &str) -> Result<MusicAccountProvider, ApiError> {
+    let provider_id =
+        ProviderId::parse(provider_id).map_err(|err| ApiError::BadRequest(err.to_string()))?;
+
+    music_account_provider(provider_id.as_str()).ok_or(ApiError::NotFound(format!(
+        "unknown provider {}",
+        provider_id
+    )))
+}
+
+/// Access token of the user on the provider, sent as `Authorization: Bearer <token>`
+pub fn provider_token(headers: &HeaderMap) -> Result<String, ApiError> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or(ApiError::Unauthorized(
+            "missing provider access token".to_string(),
+        ))
+}
+
+/// Credentials of the user on the provider, from the request headers
+pub fn provider_credentials(
+    provider: &MusicAccountProvider,
+    headers: &HeaderMap,
+) -> Result<ProviderCredentials, ApiError> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    Ok(ProviderCredentials::new(
+        provider.id().clone(),
+        provider_token(headers)?,
+        header(ACCOUNT_HEADER),
+        header(COUNTRY_HEADER),
+    ))
+}
+
+/// Market of the account: the country sent by the client, else the one of its profile, if shared
+pub async fn market(
+    credentials: &ProviderCredentials,
+    repositories: &ProviderRepositories<'_>,
+) -> Option<String> {
+    if let Some(country_code) = credentials.country_code() {
+        return Some(country_code.clone());
+    }
+
+    repositories
+        .profile
+        .as_ref()?
+        .get_profile()
+        .await
+        .ok()?
+        .country_code()
+        .cloned()
+}
+
+/// Playlist id from the path, "favourites" being the liked songs
+pub fn playlist_id(id: String) -> PlaylistId {
+    match id == PlaylistId::LikedSongs.to_string() {
+        true => PlaylistId::LikedSongs,
+        false => PlaylistId::Owned(id),
+    }
+}
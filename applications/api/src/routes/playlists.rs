@@ -0,0 +1,415 @@
+use axum::{
+    extract::{Path, Query},
+    http::{HeaderMap, StatusCode},
+    routing::{patch, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::playlist_repository::PlaylistRepository,
+    contracts::repositories::transfer_job_repository::TransferJobRepository,
+    entities::{
+        music_account_provider::MusicAccountProvider, playlist::PlaylistSpec,
+        provider_credentials::ProviderCredentials, transfer_job::TransferJob,
+    },
+    services::{deduplicator::Equivalence, identity_graph::IdentityGraph},
+    use_cases::{
+        merge_playlists::{MergePlaylists, MergeSource},
+        split_playlist::{SplitBy, SplitPlaylist},
+        transfer_tracks::TransferTracks,
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    database::{MATCH_CACHE, RECORDINGS, TRANSFER_JOBS},
+    dto::{MergeReportDto, SplitReportDto, TransferJobReportDto},
+    error::ApiError,
+    providers::PROVIDER_REGISTRY,
+};
+
+use super::{market, playlist_id, provider, provider_credentials};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/providers/:provider_id/playlists/merge", post(merge))
+        .route("/providers/:provider_id/playlists/transfer", post(transfer))
+        .route(
+            "/providers/:provider_id/playlists/:playlist_id",
+            patch(update_metadata),
+        )
+        .route(
+            "/providers/:provider_id/playlists/:playlist_id/split",
+            post(split),
+        )
+}
+
+/// Playlist to merge or transfer, with the credentials of its provider
+/// (the ones of the request for the destination provider)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeSourceRequest {
+    provider: String,
+    playlist_id: String,
+    token: Option<String>,
+    account: Option<String>,
+    country: Option<String>,
+}
+
+impl MergeSourceRequest {
+    /// Credentials of the source, the ones of the request when on the destination provider
+    fn credentials(
+        &self,
+        source_provider: &MusicAccountProvider,
+        provider_id: &str,
+        headers: &HeaderMap,
+    ) -> Result<ProviderCredentials, ApiError> {
+        match (&self.token, self.provider == provider_id) {
+            (Some(token), _) => Ok(ProviderCredentials::new(
+                source_provider.id().clone(),
+                token.clone(),
+                self.account.clone(),
+                self.country.clone(),
+            )),
+            (None, true) => provider_credentials(source_provider, headers),
+            (None, false) => Err(ApiError::Unauthorized(format!(
+                "missing access token for {}",
+                self.provider
+            ))),
+        }
+    }
+}
+
+/// Which tracks are considered the same, exact duplicates by default
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EquivalenceRequest {
+    provider_id: bool,
+    isrc: bool,
+    metadata: bool,
+}
+
+impl Default for EquivalenceRequest {
+    fn default() -> Self {
+        let equivalence = Equivalence::default();
+
+        Self {
+            provider_id: equivalence.provider_id,
+            isrc: equivalence.isrc,
+            metadata: equivalence.metadata,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    name: String,
+    description: Option<String>,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+    sources: Vec<MergeSourceRequest>,
+    #[serde(default)]
+    equivalence: EquivalenceRequest,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+impl MergeRequest {
+    fn spec(&self) -> PlaylistSpec {
+        PlaylistSpec {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            public: self.public,
+            collaborative: self.collaborative,
+            cover: None,
+        }
+    }
+}
+
+/// Merge playlists of any provider into a new deduplicated playlist of the provider
+async fn merge(
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<MergeRequest>,
+) -> Result<Json<MergeReportDto>, ApiError> {
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let track_search = repositories
+        .track_search
+        .clone()
+        .ok_or(ApiError::BadRequest(format!(
+            "merge is not supported into {}",
+            provider_id
+        )))?;
+
+    let mut source_providers = vec![];
+
+    for source in &request.sources {
+        let source_provider = super::provider(&source.provider)?;
+        let source_credentials = source.credentials(&source_provider, &provider_id, &headers)?;
+
+        source_providers.push((source_provider, source_credentials));
+    }
+
+    let mut source_repositories = vec![];
+
+    for (source_provider, source_credentials) in &source_providers {
+        source_repositories.push(
+            PROVIDER_REGISTRY
+                .repositories(source_provider, source_credentials)?
+                .playlists,
+        );
+    }
+
+    let sources = request
+        .sources
+        .iter()
+        .zip(source_repositories.iter())
+        .map(|(source, playlist_repository)| MergeSource {
+            playlist_repository,
+            playlist_id: playlist_id(source.playlist_id.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    let report = MergePlaylists::new(
+        provider.id().clone(),
+        &repositories.playlists,
+        &track_search,
+    )
+    .with_match_cache(&*MATCH_CACHE)
+    .with_identity_graph(IdentityGraph::new(&*RECORDINGS))
+    .with_equivalence(Equivalence {
+        provider_id: request.equivalence.provider_id,
+        isrc: request.equivalence.isrc,
+        metadata: request.equivalence.metadata,
+    })
+    .with_dry_run(request.dry_run)
+    .with_market(market(&credentials, &repositories).await)
+    .execute(&sources, &request.spec())
+    .await
+    .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(Json(report.into()))
+}
+
+/// Playlist to transfer, into an existing playlist or a copy of it (with the metadata sent, if any)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRequest {
+    source: MergeSourceRequest,
+    /// Existing playlist to add the tracks to
+    playlist_id: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+    /// Job of a previous attempt to resume, or the id of the new job
+    job_id: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Transfer a playlist of any provider into a playlist of the provider
+///
+/// The progress is saved in a job, sending its id again resumes the transfer where it stopped.
+async fn transfer(
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<TransferRequest>,
+) -> Result<Json<TransferJobReportDto>, ApiError> {
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let track_search = repositories
+        .track_search
+        .clone()
+        .ok_or(ApiError::BadRequest(format!(
+            "transfer is not supported into {}",
+            provider_id
+        )))?;
+
+    let source = &request.source;
+    let source_provider = super::provider(&source.provider)?;
+    let source_credentials = source.credentials(&source_provider, &provider_id, &headers)?;
+    let source_repositories =
+        PROVIDER_REGISTRY.repositories(&source_provider, &source_credentials)?;
+    let source_playlist_id = playlist_id(source.playlist_id.clone());
+
+    let job_id = match &request.job_id {
+        Some(job_id) => Uuid::parse_str(job_id)
+            .map_err(|_| ApiError::BadRequest(format!("invalid job id {}", job_id)))?,
+        None => Uuid::new_v4(),
+    };
+
+    let saved_job = TRANSFER_JOBS
+        .get(job_id)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    let mut job = match (saved_job, &request.playlist_id) {
+        (Some(job), _) => job,
+        (None, Some(id)) => TransferJob::into_playlist(job_id, playlist_id(id.clone())),
+        (None, None) => {
+            let source_playlist = source_repositories
+                .playlists
+                .get(&source_playlist_id)
+                .await
+                .map_err(|err| ApiError::Internal(err.to_string()))?
+                .ok_or(ApiError::NotFound(format!(
+                    "unknown playlist {}",
+                    source_playlist_id
+                )))?;
+            let copy = PlaylistSpec::from(&source_playlist);
+            let spec = PlaylistSpec {
+                name: request.name.clone().unwrap_or(copy.name),
+                description: request.description.clone().or(copy.description),
+                public: request.public.or(copy.public),
+                collaborative: request.collaborative.or(copy.collaborative),
+                cover: copy.cover,
+            };
+
+            TransferJob::into_new_playlist(job_id, spec)
+        }
+    };
+
+    let tracks = source_repositories
+        .playlists
+        .get_tracks(&source_playlist_id)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    let report = TransferTracks::new(
+        provider.id().clone(),
+        &repositories.playlists,
+        &track_search,
+    )
+    .with_match_cache(&*MATCH_CACHE)
+    .with_identity_graph(IdentityGraph::new(&*RECORDINGS))
+    .with_dry_run(request.dry_run)
+    .with_market(market(&credentials, &repositories).await)
+    .execute_job(&tracks, &mut job, &*TRANSFER_JOBS)
+    .await
+    .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(Json(TransferJobReportDto {
+        job_id: job.id().to_string(),
+        transfer: report.into(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SplitQuery {
+    /// "size", "decade" or "artist"
+    by: String,
+    /// Tracks per part, when split by size
+    size: Option<usize>,
+    /// Minimum tracks of an artist to get its own part, when split by artist
+    min_tracks: Option<usize>,
+    /// Prefix of the names of the parts, the name of the playlist by default
+    name: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+impl TryFrom<&SplitQuery> for SplitBy {
+    type Error = ApiError;
+
+    fn try_from(query: &SplitQuery) -> Result<Self, Self::Error> {
+        match query.by.as_str() {
+            "size" => query
+                .size
+                .filter(|size| *size > 0)
+                .map(SplitBy::Size)
+                .ok_or(ApiError::BadRequest("size must be positive".to_string())),
+            "decade" => Ok(SplitBy::Decade),
+            "artist" => Ok(SplitBy::Artist {
+                min_tracks: query.min_tracks.unwrap_or(1),
+            }),
+            other => Err(ApiError::BadRequest(format!("invalid split {}", other))),
+        }
+    }
+}
+
+/// Split a playlist into new playlists of the same provider
+async fn split(
+    Path((provider_id, id)): Path<(String, String)>,
+    Query(query): Query<SplitQuery>,
+    headers: HeaderMap,
+) -> Result<Json<SplitReportDto>, ApiError> {
+    let split_by = SplitBy::try_from(&query)?;
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let playlist_id = playlist_id(id);
+
+    let name = match query.name {
+        Some(name) => name,
+        None => repositories
+            .playlists
+            .get(&playlist_id)
+            .await
+            .map_err(|err| ApiError::Internal(err.to_string()))?
+            .map(|playlist| playlist.name().clone())
+            .ok_or(ApiError::NotFound(format!(
+                "unknown playlist {}",
+                playlist_id
+            )))?,
+    };
+
+    let report = SplitPlaylist::new(provider.id().clone(), &repositories.playlists)
+        .with_dry_run(query.dry_run)
+        .execute(&playlist_id, &name, &split_by)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(Json(report.into()))
+}
+
+/// Metadata to change, the others are left untouched
+#[derive(Debug, Deserialize)]
+pub struct MetadataRequest {
+    name: Option<String>,
+    description: Option<String>,
+    public: Option<bool>,
+    collaborative: Option<bool>,
+}
+
+/// Update the name, description & visibility of a playlist
+async fn update_metadata(
+    Path((provider_id, id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(request): Json<MetadataRequest>,
+) -> Result<StatusCode, ApiError> {
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let playlist_id = playlist_id(id);
+
+    let playlist = repositories
+        .playlists
+        .get(&playlist_id)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?
+        .ok_or(ApiError::NotFound(format!(
+            "unknown playlist {}",
+            playlist_id
+        )))?;
+
+    let spec = PlaylistSpec {
+        name: request.name.unwrap_or(playlist.name().clone()),
+        description: request.description,
+        public: request.public,
+        collaborative: request.collaborative,
+        cover: None,
+    };
+
+    repositories
+        .playlists
+        .update_metadata(&playlist_id, &spec)
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,31 @@
+use axum::{extract::Path, http::HeaderMap, routing::get, Json, Router};
+use snk_core::contracts::repositories::account_profile_repository::AccountProfileRepository;
+
+use crate::{dto::AccountProfileDto, error::ApiError, providers::PROVIDER_REGISTRY};
+
+use super::{provider, provider_credentials};
+
+pub fn router() -> Router {
+    Router::new().route("/providers/:provider_id/profile", get(get_profile))
+}
+
+/// Profile of the account, its country being the market of the account when none is sent
+async fn get_profile(
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<AccountProfileDto>, ApiError> {
+    let provider = provider(&provider_id)?;
+    let credentials = provider_credentials(&provider, &headers)?;
+    let repositories = PROVIDER_REGISTRY.repositories(&provider, &credentials)?;
+    let profile = repositories.profile.ok_or(ApiError::BadRequest(format!(
+        "profile is not supported for {}",
+        provider_id
+    )))?;
+
+    let profile = profile
+        .get_profile()
+        .await
+        .map_err(|err| ApiError::Internal(err.to_string()))?;
+
+    Ok(Json(profile.into()))
+}
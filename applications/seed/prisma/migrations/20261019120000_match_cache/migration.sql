@@ -0,0 +1,13 @@
+-- CreateTable
+CREATE TABLE "MatchCache" (
+    "product_type" TEXT NOT NULL,
+    "product_value" TEXT NOT NULL,
+    "provider_id" TEXT NOT NULL,
+    "provider_track_id" TEXT,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "MatchCache_pkey" PRIMARY KEY ("product_type","product_value","provider_id")
+);
+
+-- CreateIndex
+CREATE INDEX "MatchCache_expires_at_idx" ON "MatchCache"("expires_at");
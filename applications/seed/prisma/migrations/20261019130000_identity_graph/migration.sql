@@ -0,0 +1,24 @@
+-- CreateTable
+CREATE TABLE "Identity" (
+    "id" TEXT NOT NULL,
+    "kind" TEXT NOT NULL,
+    "name" TEXT NOT NULL,
+
+    CONSTRAINT "Identity_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "IdentityProductId" (
+    "kind" TEXT NOT NULL,
+    "product_type" TEXT NOT NULL,
+    "product_value" TEXT NOT NULL,
+    "identity_id" TEXT NOT NULL,
+
+    CONSTRAINT "IdentityProductId_pkey" PRIMARY KEY ("kind","product_type","product_value")
+);
+
+-- CreateIndex
+CREATE INDEX "IdentityProductId_identity_id_idx" ON "IdentityProductId"("identity_id");
+
+-- AddForeignKey
+ALTER TABLE "IdentityProductId" ADD CONSTRAINT "IdentityProductId_identity_id_fkey" FOREIGN KEY ("identity_id") REFERENCES "Identity"("id") ON DELETE CASCADE ON UPDATE CASCADE;
@@ -0,0 +1,5 @@
+-- AlterTable
+ALTER TABLE "MatchCache" DROP CONSTRAINT "MatchCache_pkey",
+ADD COLUMN     "market" TEXT NOT NULL DEFAULT '',
+ADD COLUMN     "playable" BOOLEAN,
+ADD CONSTRAINT "MatchCache_pkey" PRIMARY KEY ("product_type", "product_value", "provider_id", "market");
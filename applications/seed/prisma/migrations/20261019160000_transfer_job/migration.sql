@@ -0,0 +1,27 @@
+-- CreateTable
+CREATE TABLE "TransferJob" (
+    "id" TEXT NOT NULL,
+    "playlist_name" TEXT,
+    "playlist_description" TEXT,
+    "playlist_public" BOOLEAN,
+    "playlist_collaborative" BOOLEAN,
+    "playlist_cover_url" TEXT,
+    "playlist_cover_jpeg" BYTEA,
+    "destination_playlist_type" TEXT,
+    "destination_playlist_id" TEXT,
+    "updated_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "TransferJob_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "TransferJobCheckpoint" (
+    "job_id" TEXT NOT NULL,
+    "key" TEXT NOT NULL,
+    "destination_id" TEXT,
+
+    CONSTRAINT "TransferJobCheckpoint_pkey" PRIMARY KEY ("job_id","key")
+);
+
+-- AddForeignKey
+ALTER TABLE "TransferJobCheckpoint" ADD CONSTRAINT "TransferJobCheckpoint_job_id_fkey" FOREIGN KEY ("job_id") REFERENCES "TransferJob"("id") ON DELETE CASCADE ON UPDATE CASCADE;
@@ -0,0 +1,234 @@
+use std::future::Future;
+
+use snk_core::contracts::repositories::{
+    album_library_repository::AlbumLibraryRepositoryError,
+    artist_library_repository::ArtistLibraryRepositoryError,
+    playlist_repository::{ChunkResult, PlaylistRepositoryError, PlaylistRepositoryResult},
+};
+use url::form_urlencoded;
+
+/// Failure of a write to the library (saved albums, followed artists)
+#[derive(Debug)]
+pub(crate) enum LibraryActionError {
+    /// Rejected before any request
+    Invalid(String),
+    /// Every chunk was attempted, some of them failing
+    PartialFailure(Vec<ChunkResult>),
+}
+
+impl From<LibraryActionError> for AlbumLibraryRepositoryError {
+    fn from(err: LibraryActionError) -> Self {
+        match err {
+            LibraryActionError::Invalid(err) => AlbumLibraryRepositoryError::ServiceError(format!(
+                "AlbumLibraryRepository - {}",
+                err
+            )),
+            LibraryActionError::PartialFailure(chunks) => {
+                AlbumLibraryRepositoryError::PartialFailure(chunks)
+            }
+        }
+    }
+}
+
+impl From<LibraryActionError> for ArtistLibraryRepositoryError {
+    fn from(err: LibraryActionError) -> Self {
+        match err {
+            LibraryActionError::Invalid(err) => ArtistLibraryRepositoryError::ServiceError(
+                format!("ArtistLibraryRepository - {}", err),
+            ),
+            LibraryActionError::PartialFailure(chunks) => {
+                ArtistLibraryRepositoryError::PartialFailure(chunks)
+            }
+        }
+    }
+}
+
+/// Send a library write in several requests, every chunk being attempted even after a failure
+pub(crate) async fn send_library_chunks<'i, F, Fut>(
+    chunks: Vec<&'i [String]>,
+    mut send: F,
+) -> Result<(), LibraryActionError>
+where
+    F: FnMut(&'i [String]) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let mut results = Vec::with_capacity(chunks.len());
+
+    for chunk in chunks {
+        results.push(ChunkResult {
+            ids: chunk.to_vec(),
+            error: send(chunk).await.err(),
+        });
+    }
+
+    match results.iter().any(|chunk| chunk.error.is_some()) {
+        true => Err(LibraryActionError::PartialFailure(results)),
+        false => Ok(()),
+    }
+}
+
+/// Send a write in several requests, every chunk being attempted even after a failure
+///
+/// Failures are always reported as `PartialFailure`, even for a write sent in a single chunk,
+/// so that callers know which items were not written.
+pub(crate) async fn send_chunks<'i, F, Fut>(
+    chunks: Vec<&'i [String]>,
+    mut send: F,
+) -> PlaylistRepositoryResult<()>
+where
+    F: FnMut(&'i [String]) -> Fut,
+    Fut: Future<Output = PlaylistRepositoryResult<()>>,
+{
+    let mut results = Vec::with_capacity(chunks.len());
+
+    for chunk in chunks {
+        results.push(ChunkResult {
+            ids: chunk.to_vec(),
+            error: send(chunk).await.err().map(|err| err.to_string()),
+        });
+    }
+
+    match results.iter().any(|chunk| chunk.error.is_some()) {
+        true => Err(PlaylistRepositoryError::PartialFailure(results)),
+        false => Ok(()),
+    }
+}
+
+/// Length of the `,` separator once URL-encoded (`%2C`)
+const ENCODED_SEPARATOR_LENGTH: usize = 3;
+
+/// Split ids so that each chunk, joined with commas and URL-encoded as a query value,
+/// fits in `max_length`
+pub(crate) fn chunks_by_length(ids: &[String], max_length: usize) -> Vec<&[String]> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    let mut length = 0;
+
+    for (index, id) in ids.iter().enumerate() {
+        let id_length = encoded_length(id);
+        let added_length = if index == start {
+            id_length
+        } else {
+            id_length + ENCODED_SEPARATOR_LENGTH
+        };
+
+        if index > start && length + added_length > max_length {
+            chunks.push(&ids[start..index]);
+            start = index;
+            length = id_length;
+        } else {
+            length += added_length;
+        }
+    }
+
+    if start < ids.len() {
+        chunks.push(&ids[start..]);
+    }
+
+    chunks
+}
+
+/// Length of a query value once URL-encoded
+fn encoded_length(value: &str) -> usize {
+    form_urlencoded::byte_serialize(value.as_bytes())
+        .map(str::len)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::contracts::repositories::playlist_repository::PlaylistRepositoryError;
+
+    use super::{chunks_by_length, send_chunks, send_library_chunks, LibraryActionError};
+
+    fn ids(count: usize) -> Vec<String> {
+        (0..count).map(|index| format!("{:04}", index)).collect()
+    }
+
+    #[test]
+    fn test_chunks_by_length() {
+        let ids = ids(10);
+        let chunks = chunks_by_length(&ids, 18);
+
+        // "0000%2C0001%2C0002" is 18 characters long
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0], &ids[0..3]);
+        assert_eq!(chunks[3], &ids[9..10]);
+        assert!(chunks_by_length(&[], 18).is_empty());
+
+        // Not 14 characters long once encoded
+        assert_eq!(chunks_by_length(&ids[0..3], 14).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_chunks() {
+        let ids = ids(250);
+
+        let result = send_chunks(ids.chunks(100).collect(), |chunk| async move {
+            match chunk[0].as_str() {
+                "0100" => Err(PlaylistRepositoryError::ServiceError(
+                    "rate limited".to_string(),
+                )),
+                _ => Ok(()),
+            }
+        })
+        .await;
+
+        let Err(PlaylistRepositoryError::PartialFailure(chunks)) = result else {
+            panic!("partial failure expected");
+        };
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].error.is_none());
+        assert_eq!(chunks[1].ids.len(), 100);
+        assert_eq!(
+            chunks[1].error.as_deref(),
+            Some("ServiceError: rate limited")
+        );
+        assert_eq!(chunks[2].ids.len(), 50);
+    }
+
+    #[tokio::test]
+    async fn test_send_single_chunk() {
+        let ids = ids(10);
+
+        let result = send_chunks(vec![&ids[..]], |_| async move {
+            Err(PlaylistRepositoryError::ServiceError(
+                "rate limited".to_string(),
+            ))
+        })
+        .await;
+
+        // Reported like any other failed chunk, with its items
+        let Err(PlaylistRepositoryError::PartialFailure(chunks)) = result else {
+            panic!("partial failure expected");
+        };
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].ids.len(), 10);
+        assert!(chunks[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_send_library_chunks() {
+        let ids = ids(5);
+
+        let result = send_library_chunks(ids.chunks(2).collect(), |chunk| async move {
+            match chunk[0].as_str() {
+                "0002" => Err("rejected".to_string()),
+                _ => Ok(()),
+            }
+        })
+        .await;
+
+        let Err(LibraryActionError::PartialFailure(chunks)) = result else {
+            panic!("partial failure expected");
+        };
+
+        // The chunks after the failed one are still sent
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].error.is_none());
+        assert_eq!(chunks[1].error.as_deref(), Some("rejected"));
+        assert!(chunks[2].error.is_none());
+    }
+}
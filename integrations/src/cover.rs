@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use snk_core::value_objects::cover_image::CoverImage;
+
+/// Magic number starting JPEG files
+static JPEG_SIGNATURE: [u8; 3] = [0xFF, 0xD8, 0xFF];
+
+/// JPEG content of a cover, downloaded when given as an URL, of at most `max_encoded_length`
+/// bytes once base64 encoded
+///
+/// Downloads use a client without the credentials of the provider, the image being hosted anywhere.
+pub(crate) async fn jpeg_bytes(
+    cover: &CoverImage,
+    max_encoded_length: usize,
+) -> Result<Vec<u8>, String> {
+    let bytes = match cover {
+        CoverImage::Jpeg(bytes) => bytes.clone(),
+        CoverImage::Url(url) => Client::builder()
+            .connect_timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|err| format!("Could not init HTTP client - {:?}", err))?
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|err| format!("Failed to send request - {:?}", err))?
+            .error_for_status()
+            .map_err(|err| format!("Error during request - {:?}", err))?
+            .bytes()
+            .await
+            .map_err(|err| format!("Failed to read response - {:?}", err))?
+            .to_vec(),
+    };
+
+    if !bytes.starts_with(&JPEG_SIGNATURE) {
+        return Err("cover is not a JPEG image".to_string());
+    }
+
+    match base64_length(bytes.len()) <= max_encoded_length {
+        true => Ok(bytes),
+        false => Err(format!(
+            "cover is larger than {} bytes once encoded",
+            max_encoded_length
+        )),
+    }
+}
+
+/// Length of bytes once base64 encoded, with padding
+fn base64_length(length: usize) -> usize {
+    length.div_ceil(3) * 4
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::value_objects::cover_image::CoverImage;
+
+    use super::jpeg_bytes;
+
+    #[tokio::test]
+    async fn test_jpeg_bytes() {
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+
+        assert_eq!(
+            jpeg_bytes(&CoverImage::Jpeg(jpeg.clone()), 8).await,
+            Ok(jpeg.clone())
+        );
+        assert!(jpeg_bytes(&CoverImage::Jpeg(jpeg), 7).await.is_err());
+        assert!(jpeg_bytes(&CoverImage::Jpeg(b"\x89PNG".to_vec()), 1024)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,68 @@
+/// Unit of the values of the duration column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationUnit {
+    Milliseconds,
+    Seconds,
+}
+
+/// Names of the CSV columns holding each field of a track.
+///
+/// Only the title is required, columns set to `None` (or missing from the file)
+/// are ignored when reading and not written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumnMapping {
+    pub title: String,
+    pub artists: Option<String>,
+    pub album: Option<String>,
+    pub isrc: Option<String>,
+    pub duration: Option<String>,
+    pub provider_url: Option<String>,
+    /// Separator between the names of the artists column
+    pub artists_separator: String,
+    /// Unit of plain numbers in the duration column, `m:ss` values are always supported
+    pub duration_unit: DurationUnit,
+    pub delimiter: u8,
+}
+
+impl Default for CsvColumnMapping {
+    /// Columns of the files exported by SonikSwap
+    fn default() -> Self {
+        Self {
+            title: "Title".to_string(),
+            artists: Some("Artists".to_string()),
+            album: Some("Album".to_string()),
+            isrc: Some("ISRC".to_string()),
+            duration: Some("Duration (ms)".to_string()),
+            provider_url: Some("URL".to_string()),
+            artists_separator: ", ".to_string(),
+            duration_unit: DurationUnit::Milliseconds,
+            delimiter: b',',
+        }
+    }
+}
+
+impl CsvColumnMapping {
+    /// Columns of the files exported by Exportify (https://exportify.net)
+    pub fn exportify() -> Self {
+        Self {
+            title: "Track Name".to_string(),
+            artists: Some("Artist Name(s)".to_string()),
+            album: Some("Album Name".to_string()),
+            isrc: Some("ISRC".to_string()),
+            duration: Some("Track Duration (ms)".to_string()),
+            provider_url: Some("Track URI".to_string()),
+            artists_separator: ",".to_string(),
+            duration_unit: DurationUnit::Milliseconds,
+            delimiter: b',',
+        }
+    }
+
+    /// Mapping by name, used to let users pick a preset
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "exportify" => Some(Self::exportify()),
+            _ => None,
+        }
+    }
+}
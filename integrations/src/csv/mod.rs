@@ -0,0 +1,359 @@
+pub mod mapping;
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+};
+
+use chrono::{DateTime, Utc};
+use mapping::{CsvColumnMapping, DurationUnit};
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{
+        isrc::Isrc,
+        product_id::ProductId,
+        provider::{
+            provider_id::ProviderId,
+            spotify_id::{SpotifyResource, SpotifyUri},
+        },
+    },
+};
+use url::Url;
+
+use crate::local::provider_id;
+
+/// Positions of the mapped columns in the header of a file
+struct ColumnIndexes {
+    title: usize,
+    artists: Option<usize>,
+    album: Option<usize>,
+    isrc: Option<usize>,
+    duration: Option<usize>,
+    provider_url: Option<usize>,
+}
+
+impl ColumnIndexes {
+    fn new(headers: &::csv::StringRecord, mapping: &CsvColumnMapping) -> Result<Self, String> {
+        let find = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(column.trim()))
+        };
+        let find_optional = |column: &Option<String>| column.as_deref().and_then(find);
+
+        Ok(Self {
+            title: find(&mapping.title)
+                .ok_or(format!("CSV - Missing column \"{}\"", mapping.title))?,
+            artists: find_optional(&mapping.artists),
+            album: find_optional(&mapping.album),
+            isrc: find_optional(&mapping.isrc),
+            duration: find_optional(&mapping.duration),
+            provider_url: find_optional(&mapping.provider_url),
+        })
+    }
+}
+
+/// Read tracks from a CSV file having a header row
+pub fn read_tracks<R: Read>(
+    reader: R,
+    mapping: &CsvColumnMapping,
+) -> Result<Vec<TrackWithAlbumAndArtists>, String> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter)
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = reader
+        .headers()
+        .map_err(|err| format!("CSV - Invalid header - {}", err))?;
+    let indexes = ColumnIndexes::new(headers, mapping)?;
+
+    let mut tracks = vec![];
+
+    for (row, record) in reader.records().enumerate() {
+        let record = record.map_err(|err| format!("CSV - Invalid row {} - {}", row + 1, err))?;
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(title) = field(Some(indexes.title)) else {
+            // Blank lines
+            continue;
+        };
+
+        let duration_ms = match field(indexes.duration) {
+            Some(duration) => parse_duration(duration, mapping.duration_unit)
+                .ok_or(format!("CSV - Invalid duration at row {}", row + 1))?,
+            None => 0,
+        };
+
+        tracks.push(to_track(
+            title,
+            field(indexes.artists),
+            field(indexes.album),
+            field(indexes.isrc),
+            duration_ms,
+            field(indexes.provider_url),
+            mapping,
+        ));
+    }
+
+    Ok(tracks)
+}
+
+/// Write tracks as a CSV file, with a header row made of the mapped columns
+pub fn write_tracks<W: Write>(
+    writer: W,
+    tracks: &[TrackWithAlbumAndArtists],
+    mapping: &CsvColumnMapping,
+) -> Result<(), String> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(mapping.delimiter)
+        .from_writer(writer);
+
+    let columns = [
+        &mapping.artists,
+        &mapping.album,
+        &mapping.isrc,
+        &mapping.duration,
+        &mapping.provider_url,
+    ];
+    let mut headers = vec![mapping.title.as_str()];
+
+    headers.extend(columns.iter().filter_map(|column| column.as_deref()));
+
+    writer
+        .write_record(headers)
+        .map_err(|err| format!("CSV - Could not write header - {}", err))?;
+
+    for track in tracks {
+        let artists = track
+            .artists()
+            .iter()
+            .map(|artist| artist.name().as_str())
+            .collect::<Vec<_>>()
+            .join(&mapping.artists_separator);
+        let isrc = track
+            .ids()
+            .iter()
+            .find_map(|id| match id {
+                ProductId::ISRC(isrc) => Some(isrc.to_string()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let duration = match (track.duration_ms(), mapping.duration_unit) {
+            (0, _) => String::new(),
+            (duration, DurationUnit::Milliseconds) => duration.to_string(),
+            (duration, DurationUnit::Seconds) => ((duration + 500) / 1000).to_string(),
+        };
+        let mut provider_urls = track
+            .provider_urls()
+            .values()
+            .map(Url::to_string)
+            .collect::<Vec<_>>();
+
+        provider_urls.sort();
+
+        let values = [
+            artists,
+            track.album().name().clone(),
+            isrc,
+            duration,
+            provider_urls.into_iter().next().unwrap_or_default(),
+        ];
+        let mut record = vec![track.name().clone()];
+
+        record.extend(
+            columns
+                .iter()
+                .zip(values)
+                .filter(|(column, _)| column.is_some())
+                .map(|(_, value)| value),
+        );
+
+        writer
+            .write_record(record)
+            .map_err(|err| format!("CSV - Could not write row - {}", err))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|err| format!("CSV - Could not write file - {}", err))
+}
+
+/// Parse a duration, either a number in `unit` or `[h:]m:ss`
+fn parse_duration(value: &str, unit: DurationUnit) -> Option<u32> {
+    if value.contains(':') {
+        let seconds = value.split(':').try_fold(0u32, |total, part| {
+            total
+                .checked_mul(60)?
+                .checked_add(part.parse::<u32>().ok()?)
+        })?;
+
+        return seconds.checked_mul(1000);
+    }
+
+    let value = value.parse::<f64>().ok().filter(|value| *value >= 0.0)?;
+
+    Some(match unit {
+        DurationUnit::Milliseconds => value.round() as u32,
+        DurationUnit::Seconds => (value * 1000.0).round() as u32,
+    })
+}
+
+fn to_track(
+    title: &str,
+    artists: Option<&str>,
+    album: Option<&str>,
+    isrc: Option<&str>,
+    duration_ms: u32,
+    provider_url: Option<&str>,
+    mapping: &CsvColumnMapping,
+) -> TrackWithAlbumAndArtists {
+    let provider_id = provider_id();
+    let mut ids = HashSet::new();
+    let mut urls = HashMap::new();
+
+    if let Some(isrc) = isrc.and_then(|isrc| Isrc::parse(isrc).ok()) {
+        ids.insert(ProductId::ISRC(isrc));
+    }
+
+    if let Some(provider_url) = provider_url {
+        match SpotifyUri::parse(provider_url) {
+            // Exportify "Track URI", or share URL of a Spotify track
+            Ok(uri) if uri.resource() == SpotifyResource::Track => {
+                ids.insert(ProductId::Provider((
+                    ProviderId::SPOTIFY,
+                    uri.id().to_string(),
+                )));
+                urls.insert(ProviderId::SPOTIFY, uri.url());
+            }
+            // Albums, artists & playlists are not the track
+            Ok(_) => {}
+            Err(_) => {
+                if let Ok(url) = provider_url.parse::<Url>() {
+                    ids.insert(ProductId::Provider((provider_id.clone(), url.to_string())));
+                    urls.insert(provider_id, url);
+                }
+            }
+        }
+    }
+
+    let artists = artists
+        .unwrap_or_default()
+        .split(mapping.artists_separator.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Artist::new(HashMap::new(), name.to_string(), HashMap::new()))
+        .collect();
+
+    // Release date is unknown from playlist files
+    let album = Album::new(
+        HashSet::new(),
+        album.unwrap_or_default().to_string(),
+        DateTime::<Utc>::default(),
+        HashSet::new(),
+        HashMap::new(),
+    );
+
+    TrackWithAlbumAndArtists::new(ids, title.to_string(), duration_ms, urls, album, artists)
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::value_objects::{
+        isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId,
+    };
+
+    use super::{
+        mapping::{CsvColumnMapping, DurationUnit},
+        parse_duration, read_tracks, write_tracks,
+    };
+
+    #[test]
+    fn test_read_exportify() {
+        let content = include_str!("../../tests/csv/exportify.csv");
+        let tracks =
+            read_tracks(content.as_bytes(), &CsvColumnMapping::exportify()).expect("valid csv");
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].name(), "How Sweet");
+        assert!(tracks[0]
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert!(tracks[0].ids().contains(&ProductId::Provider((
+            ProviderId::SPOTIFY,
+            "38tXZcL1gZRfbqfOG0VMTH".to_string()
+        ))));
+        assert_eq!(
+            tracks[0]
+                .provider_urls()
+                .get(&ProviderId::SPOTIFY)
+                .map(|url| url.as_str()),
+            Some("https://open.spotify.com/track/38tXZcL1gZRfbqfOG0VMTH")
+        );
+        assert_eq!(tracks[1].artists().len(), 2);
+        assert_eq!(tracks[1].artists()[1].name(), "Ty Dolla $ign");
+        assert_eq!(tracks[1].duration_ms(), 201_000);
+    }
+
+    #[test]
+    fn test_read_missing_title_column() {
+        let mapping = CsvColumnMapping::default();
+
+        assert!(read_tracks("Name,Artist\nHow Sweet,NewJeans\n".as_bytes(), &mapping).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = include_str!("../../tests/csv/exportify.csv");
+        let tracks =
+            read_tracks(content.as_bytes(), &CsvColumnMapping::exportify()).expect("valid csv");
+
+        let mapping = CsvColumnMapping {
+            delimiter: b';',
+            ..Default::default()
+        };
+        let mut output = vec![];
+
+        write_tracks(&mut output, &tracks, &mapping).expect("csv written");
+
+        let read = read_tracks(output.as_slice(), &mapping).expect("valid csv");
+
+        assert_eq!(read.len(), tracks.len());
+
+        for (track, read_track) in tracks.iter().zip(read.iter()) {
+            assert_eq!(track.name(), read_track.name());
+            assert!(track.ids() == read_track.ids());
+            assert_eq!(track.duration_ms(), read_track.duration_ms());
+            assert!(track.artists() == read_track.artists());
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("219000", DurationUnit::Milliseconds),
+            Some(219_000)
+        );
+        assert_eq!(parse_duration("219", DurationUnit::Seconds), Some(219_000));
+        assert_eq!(
+            parse_duration("3:39", DurationUnit::Milliseconds),
+            Some(219_000)
+        );
+        assert_eq!(parse_duration("abc", DurationUnit::Seconds), None);
+        assert_eq!(
+            parse_duration("99999999:59", DurationUnit::Milliseconds),
+            None
+        );
+        assert_eq!(parse_duration("71583:00", DurationUnit::Seconds), None);
+        assert!(read_tracks(
+            "Title,Duration (ms)\nx,99999999:59\n".as_bytes(),
+            &CsvColumnMapping::default()
+        )
+        .is_err());
+    }
+}
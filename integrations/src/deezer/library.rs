@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize};
+use snk_core::{
+    contracts::repositories::{
+        album_library_repository::{
+            AlbumLibraryRepository, AlbumLibraryRepositoryError, AlbumLibraryRepositoryResult,
+        },
+        artist_library_repository::{
+            ArtistLibraryRepository, ArtistLibraryRepositoryError, ArtistLibraryRepositoryResult,
+        },
+    },
+    entities::{
+        album::{Album, AlbumWithArtists},
+        artist::Artist,
+    },
+    value_objects::{
+        image_cover::ImageCover,
+        product_id::ProductId,
+        provider::{deezer_id::DeezerId, provider_id::ProviderId},
+    },
+};
+use url::Url;
+
+use crate::chunks::{send_library_chunks, LibraryActionError};
+
+use super::{
+    artist::{DeezerIdType, ReducedArtist},
+    error::DeezerErrorPayload,
+    DeezerList, DeezerPlaylistRepository, API_URL,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeezerLibraryResponse<T> {
+    Error(DeezerErrorPayload),
+    List(DeezerList<T>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(super) enum DeezerActionResponse {
+    Error(DeezerErrorPayload),
+    Result(bool),
+}
+
+/// Album of the library of the user, without UPC nor contributors
+#[derive(Debug, Deserialize)]
+pub struct DeezerLibraryAlbum {
+    // The Deezer album id
+    pub id: DeezerIdType,
+    // The album title
+    pub title: String,
+    // The url of the album on Deezer
+    pub link: Url,
+    // The url of the album's cover in size small.
+    pub cover_small: Option<Url>,
+    // The url of the album's cover in size medium.
+    pub cover_medium: Option<Url>,
+    // The url of the album's cover in size big.
+    pub cover_big: Option<Url>,
+    // The album's release date
+    pub release_date: Option<String>,
+    // artist object containing : id, name, link, picture...
+    pub artist: ReducedArtist,
+}
+
+impl TryFrom<DeezerLibraryAlbum> for AlbumWithArtists {
+    type Error = &'static str;
+
+    fn try_from(library_album: DeezerLibraryAlbum) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::DEEZER;
+        let mut covers = HashSet::new();
+
+        if let Some(cover) = library_album.cover_small {
+            covers.insert(ImageCover::Sm(cover));
+        }
+        if let Some(cover) = library_album.cover_medium {
+            covers.insert(ImageCover::Md(cover));
+        }
+        if let Some(cover) = library_album.cover_big {
+            covers.insert(ImageCover::Lg(cover));
+        }
+
+        let release_date = library_album
+            .release_date
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+            .unwrap_or_default();
+
+        let album = Album::new(
+            HashSet::from_iter([ProductId::Provider((
+                provider_id.clone(),
+                library_album.id.to_string(),
+            ))]),
+            library_album.title,
+            release_date,
+            covers,
+            HashMap::from_iter([(provider_id, library_album.link)]),
+        );
+
+        Ok(AlbumWithArtists::new(
+            album,
+            vec![library_album.artist.try_into()?],
+        ))
+    }
+}
+
+impl TryFrom<ReducedArtist> for Artist {
+    type Error = &'static str;
+
+    fn try_from(reduced_artist: ReducedArtist) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::DEEZER;
+
+        let Some(artist_id) = reduced_artist.id else {
+            return Err("artist.id is missing");
+        };
+
+        let Some(artist_name) = reduced_artist.name else {
+            return Err("artist.name is missing");
+        };
+
+        Ok(Artist::new(
+            HashMap::from_iter([(provider_id.clone(), artist_id.to_string())]),
+            artist_name,
+            reduced_artist
+                .link
+                .map(|link| HashMap::from_iter([(provider_id, link)]))
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+impl DeezerPlaylistRepository<'_> {
+    /// Fetch every page of a list of the library
+    async fn library_list<T: DeserializeOwned>(&self, endpoint: &str) -> Result<Vec<T>, String> {
+        let mut items = vec![];
+        let mut url = Some(format!("{}/{}", API_URL, endpoint));
+
+        while let Some(page_url) = url {
+            let response = self
+                .http_client
+                .get(page_url)
+                .send()
+                .await
+                .map_err(|err| format!("Failed to send request - {:?}", err))?
+                .error_for_status()
+                .map_err(|err| format!("Error during request - {:?}", err))?
+                .json::<DeezerLibraryResponse<T>>()
+                .await
+                .map_err(|err| format!("Failed to parse response - {:?}", err))?;
+
+            match response {
+                DeezerLibraryResponse::Error(deezer_error_payload) => {
+                    return Err(format!(
+                        "Error during request - {}",
+                        deezer_error_payload.error.message
+                    ))
+                }
+                DeezerLibraryResponse::List(deezer_list) => {
+                    items.extend(deezer_list.data);
+                    url = deezer_list.next;
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Add or remove items of the library, one request per item
+    async fn library_action(
+        &self,
+        method: Method,
+        endpoint: &str,
+        id_param: &str,
+        ids: &[String],
+    ) -> Result<(), LibraryActionError> {
+        // Checked before the first request, not to apply the action partially
+        for id in ids {
+            DeezerId::parse(id).map_err(|err| LibraryActionError::Invalid(err.to_string()))?;
+        }
+
+        send_library_chunks(ids.chunks(1).collect(), |chunk| {
+            self.library_request(&method, endpoint, id_param, &chunk[0])
+        })
+        .await
+    }
+
+    async fn library_request(
+        &self,
+        method: &Method,
+        endpoint: &str,
+        id_param: &str,
+        id: &str,
+    ) -> Result<(), String> {
+        let url = Url::parse_with_params(&format!("{}/{}", API_URL, endpoint), [(id_param, id)])
+            .map_err(|err| err.to_string())?;
+
+        let response = self
+            .http_client
+            .request(method.clone(), url)
+            .send()
+            .await
+            .map_err(|err| format!("Failed to send request - {:?}", err))?
+            .error_for_status()
+            .map_err(|err| format!("Error during request - {:?}", err))?
+            .json::<DeezerActionResponse>()
+            .await
+            .map_err(|err| format!("Failed to parse response - {:?}", err))?;
+
+        match response {
+            DeezerActionResponse::Error(deezer_error_payload) => Err(format!(
+                "Error during request - {} ({})",
+                deezer_error_payload.error.message, id
+            )),
+            DeezerActionResponse::Result(false) => {
+                Err(format!("Error during request - rejected ({})", id))
+            }
+            DeezerActionResponse::Result(true) => Ok(()),
+        }
+    }
+}
+
+impl AlbumLibraryRepository for DeezerPlaylistRepository<'_> {
+    async fn get_all(&self) -> AlbumLibraryRepositoryResult<Vec<AlbumWithArtists>> {
+        self.library_list::<DeezerLibraryAlbum>("user/me/albums")
+            .await
+            .map_err(|err| {
+                AlbumLibraryRepositoryError::ServiceError(format!(
+                    "AlbumLibraryRepository - {}",
+                    err
+                ))
+            })?
+            .into_iter()
+            .map(AlbumWithArtists::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| AlbumLibraryRepositoryError::ServiceError(err.to_string()))
+    }
+
+    async fn save(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::POST, "user/me/albums", "album_id", ids)
+            .await?)
+    }
+
+    async fn remove(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::DELETE, "user/me/albums", "album_id", ids)
+            .await?)
+    }
+}
+
+impl ArtistLibraryRepository for DeezerPlaylistRepository<'_> {
+    async fn get_all(&self) -> ArtistLibraryRepositoryResult<Vec<Artist>> {
+        self.library_list::<ReducedArtist>("user/me/artists")
+            .await
+            .map_err(|err| {
+                ArtistLibraryRepositoryError::ServiceError(format!(
+                    "ArtistLibraryRepository - {}",
+                    err
+                ))
+            })?
+            .into_iter()
+            .map(Artist::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ArtistLibraryRepositoryError::ServiceError(err.to_string()))
+    }
+
+    async fn save(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::POST, "user/me/artists", "artist_id", ids)
+            .await?)
+    }
+
+    async fn remove(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::DELETE, "user/me/artists", "artist_id", ids)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::{album::AlbumWithArtists, artist::Artist},
+        value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
+    use crate::deezer::{artist::ReducedArtist, DeezerList};
+
+    use super::DeezerLibraryAlbum;
+
+    #[test]
+    pub fn test_deserialize_library_albums() {
+        let json_str = include_str!("../../tests/deezer/payload_library_albums.json");
+        let json =
+            serde_json::from_str::<DeezerList<DeezerLibraryAlbum>>(json_str).expect("valid json");
+
+        assert_eq!(json.total, 1);
+
+        let album =
+            AlbumWithArtists::try_from(json.data.into_iter().next().unwrap()).expect("valid album");
+
+        assert_eq!(album.album().name(), "How Sweet");
+        assert!(album.album().ids().contains(&ProductId::Provider((
+            ProviderId::DEEZER,
+            "591284702".to_string()
+        ))));
+        assert_eq!(album.artists()[0].name(), "NewJeans");
+    }
+
+    #[test]
+    pub fn test_deserialize_library_artists() {
+        let json_str = include_str!("../../tests/deezer/payload_library_artists.json");
+        let json = serde_json::from_str::<DeezerList<ReducedArtist>>(json_str).expect("valid json");
+
+        assert!(json.next.is_some());
+
+        let artist = Artist::try_from(json.data.into_iter().next().unwrap()).expect("valid artist");
+
+        assert_eq!(artist.name(), "Kehlani");
+    }
+}
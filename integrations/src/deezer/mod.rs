@@ -1,8 +1,11 @@
 pub mod album;
 pub mod artist;
 pub mod error;
+pub mod library;
 pub mod playlist;
+pub mod search;
 pub mod track;
+pub mod user;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -12,10 +15,11 @@ use std::{
 };
 
 use error::{DeezerErrorPayload, DeezerErrorType};
+use library::DeezerActionResponse;
 use playlist::DeezerPlaylist;
 use reqwest::{
     header::{HeaderMap, HeaderValue},
-    Client, StatusCode,
+    Client, Method, StatusCode,
 };
 use serde::Deserialize;
 use snk_core::{
@@ -23,16 +27,25 @@ use snk_core::{
         PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
     },
     entities::{
-        music_account_provider::MusicAccountProvider, playlist::Playlist,
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
         track::TrackWithAlbumAndArtists,
     },
-    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+    value_objects::{
+        image_cover::ImageCover, playlist_id::PlaylistId, provider::deezer_id::DeezerId,
+    },
 };
 use track::DeezerTrack;
 use url::Url;
 
+use crate::chunks::{chunks_by_length, send_chunks};
+
 static API_URL: &str = "https://api.deezer.com";
 
+/// Maximum length of the comma separated ids of the `songs` parameter once URL-encoded,
+/// keeping urls under 2KB
+static SONGS_MAX_LENGTH: usize = 1500;
+
 #[derive(Debug, Deserialize)]
 pub struct DeezerList<T> {
     pub data: Vec<T>,
@@ -86,13 +99,72 @@ impl<'a> DeezerPlaylistRepository<'a> {
     }
 }
 
+/// Id of a playlist or track, a malformed one being rejected before any request
+fn deezer_id(id: &str) -> PlaylistRepositoryResult<DeezerId> {
+    DeezerId::parse(id).map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))
+}
+
+/// Reject the tracks to write before any request if one of their ids is malformed
+fn check_track_ids(ids: &[String]) -> PlaylistRepositoryResult<()> {
+    ids.iter().try_for_each(|id| deezer_id(id).map(|_| ()))
+}
+
+impl DeezerPlaylistRepository<'_> {
+    /// Add (POST) or remove (DELETE) tracks of a playlist, ids being sent in the query
+    async fn tracks_request(
+        &self,
+        method: Method,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+    ) -> PlaylistRepositoryResult<()> {
+        let url = reqwest::Url::parse_with_params(
+            match playlist_id {
+                PlaylistId::LikedSongs => format!("{}/user/me/tracks", API_URL),
+                PlaylistId::Owned(id) => format!("{}/playlist/{}/tracks", API_URL, deezer_id(id)?),
+            }
+            .as_str(),
+            [("songs", ids.join(","))],
+        )
+        .map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!("{}: invalid url ({})", method, err))
+        })?;
+
+        let response = self
+            .http_client
+            .request(method, url)
+            .send()
+            .await
+            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let response_body = response
+                    .json::<DeezerResponse>()
+                    .await
+                    .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
+
+                match response_body {
+                    DeezerResponse::Error(deezer_error_payload) => Err(
+                        PlaylistRepositoryError::ServiceError(deezer_error_payload.error.message),
+                    ),
+                    _ => Ok(()),
+                }
+            }
+            other => Err(PlaylistRepositoryError::ServiceError(format!(
+                "Failed request: {}",
+                other
+            ))),
+        }
+    }
+}
+
 impl PlaylistRepository for DeezerPlaylistRepository<'_> {
     async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
         let response = self
             .http_client
             .get(match id {
                 PlaylistId::LikedSongs => format!("{}/user/me/tracks", API_URL),
-                PlaylistId::Owned(deezer_id) => format!("{}/playlist/{}", API_URL, deezer_id),
+                PlaylistId::Owned(id) => format!("{}/playlist/{}", API_URL, deezer_id(id)?),
             })
             .send()
             .await
@@ -192,10 +264,11 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
         }
     }
 
-    async fn create(&self, name: &str) -> PlaylistRepositoryResult<Playlist> {
+    /// Deezer has no API to upload a custom cover, the cover of the spec is ignored
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
         let mut payload = HashMap::new();
 
-        payload.insert("title", name);
+        payload.insert("title", spec.name.as_str());
         let response = self
             .http_client
             .post(format!("{}/user/me/playlists", API_URL))
@@ -215,7 +288,18 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
                     DeezerResponse::Error(deezer_error_payload) => Err(
                         PlaylistRepositoryError::ServiceError(deezer_error_payload.error.message),
                     ),
-                    DeezerResponse::Playlist(deezer_playlist) => Ok((*deezer_playlist).into()),
+                    DeezerResponse::Playlist(deezer_playlist) => {
+                        let playlist: Playlist = (*deezer_playlist).into();
+                        let has_details = spec.description.is_some()
+                            || spec.public.is_some()
+                            || spec.collaborative.is_some();
+
+                        if has_details {
+                            self.update_metadata(playlist.id(), spec).await?;
+                        }
+
+                        Ok(playlist)
+                    }
                     _ => Err(PlaylistRepositoryError::ServiceError(
                         "bad response format".to_string(),
                     )),
@@ -228,15 +312,64 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
         }
     }
 
+    /// Deezer has no API to upload a custom cover, the cover of the spec is ignored
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let PlaylistId::Owned(id) = id else {
+            return Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            ));
+        };
+        let deezer_id = deezer_id(id)?;
+
+        let mut params = vec![("title", spec.name.clone())];
+
+        if let Some(description) = &spec.description {
+            params.push(("description", description.clone()));
+        }
+        if let Some(public) = spec.public {
+            params.push(("public", public.to_string()));
+        }
+        if let Some(collaborative) = spec.collaborative {
+            params.push(("collaborative", collaborative.to_string()));
+        }
+
+        let url =
+            reqwest::Url::parse_with_params(&format!("{}/playlist/{}", API_URL, deezer_id), params)
+                .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
+
+        let response = self
+            .http_client
+            .post(url)
+            .send()
+            .await
+            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?
+            .error_for_status()
+            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?
+            .json::<DeezerActionResponse>()
+            .await
+            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
+
+        match response {
+            DeezerActionResponse::Error(deezer_error_payload) => Err(
+                PlaylistRepositoryError::ServiceError(deezer_error_payload.error.message),
+            ),
+            DeezerActionResponse::Result(_) => Ok(()),
+        }
+    }
+
     async fn delete(&self, playlist_id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
         match playlist_id {
             PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
                 "operation not permitted with favourite tracks list".to_string(),
             )),
-            PlaylistId::Owned(deezer_id) => {
+            PlaylistId::Owned(id) => {
                 let response = self
                     .http_client
-                    .delete(format!("{}/playlist/{}", API_URL, deezer_id))
+                    .delete(format!("{}/playlist/{}", API_URL, deezer_id(id)?))
                     .send()
                     .await
                     .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
@@ -276,50 +409,12 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
         ids: &[String],
         _snapshot_id: Option<String>,
     ) -> PlaylistRepositoryResult<()> {
-        let mut data = HashMap::new();
-
-        data.insert("songs", ids.join(","));
+        check_track_ids(ids)?;
 
-        let url = reqwest::Url::parse_with_params(
-            match playlist_id {
-                PlaylistId::LikedSongs => format!("{}/user/me/tracks", API_URL),
-                PlaylistId::Owned(deezer_id) => {
-                    format!("{}/playlist/{}/tracks", API_URL, deezer_id)
-                }
-            }
-            .as_str(),
-            data,
-        )
-        .map_err(|err| {
-            PlaylistRepositoryError::ServiceError(format!("add_tracks: invalid url ({})", err))
-        })?;
-
-        let response = self
-            .http_client
-            .post(url)
-            .send()
-            .await
-            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
-
-        match response.status() {
-            StatusCode::OK => {
-                let response_body = response
-                    .json::<DeezerResponse>()
-                    .await
-                    .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
-
-                match response_body {
-                    DeezerResponse::Error(deezer_error_payload) => Err(
-                        PlaylistRepositoryError::ServiceError(deezer_error_payload.error.message),
-                    ),
-                    _ => Ok(()),
-                }
-            }
-            other => Err(PlaylistRepositoryError::ServiceError(format!(
-                "Failed request: {}",
-                other
-            ))),
-        }
+        send_chunks(chunks_by_length(ids, SONGS_MAX_LENGTH), |chunk| {
+            self.tracks_request(Method::POST, playlist_id, chunk)
+        })
+        .await
     }
 
     async fn delete_tracks(
@@ -328,49 +423,12 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
         ids: &[String],
         _snapshot_id: Option<String>,
     ) -> PlaylistRepositoryResult<()> {
-        let mut data = HashMap::new();
-        data.insert("songs", ids.join(","));
+        check_track_ids(ids)?;
 
-        let url = reqwest::Url::parse_with_params(
-            match playlist_id {
-                PlaylistId::LikedSongs => format!("{}/user/me/tracks", API_URL),
-                PlaylistId::Owned(deezer_id) => {
-                    format!("{}/playlist/{}/tracks", API_URL, deezer_id)
-                }
-            }
-            .as_str(),
-            data,
-        )
-        .map_err(|err| {
-            PlaylistRepositoryError::ServiceError(format!("add_tracks: invalid url ({})", err))
-        })?;
-
-        let response = self
-            .http_client
-            .delete(url)
-            .send()
-            .await
-            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
-
-        match response.status() {
-            StatusCode::OK => {
-                let response_body = response
-                    .json::<DeezerResponse>()
-                    .await
-                    .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?;
-
-                match response_body {
-                    DeezerResponse::Error(deezer_error_payload) => Err(
-                        PlaylistRepositoryError::ServiceError(deezer_error_payload.error.message),
-                    ),
-                    _ => Ok(()),
-                }
-            }
-            other => Err(PlaylistRepositoryError::ServiceError(format!(
-                "Failed request: {}",
-                other
-            ))),
-        }
+        send_chunks(chunks_by_length(ids, SONGS_MAX_LENGTH), |chunk| {
+            self.tracks_request(Method::DELETE, playlist_id, chunk)
+        })
+        .await
     }
 
     async fn get_tracks(
@@ -379,7 +437,7 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
     ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
         let endpoint = match playlist_id {
             PlaylistId::LikedSongs => format!("{}/user/me/tracks", API_URL),
-            PlaylistId::Owned(deezer_id) => format!("{}/playlist/{}/tracks", API_URL, deezer_id),
+            PlaylistId::Owned(id) => format!("{}/playlist/{}/tracks", API_URL, deezer_id(id)?),
         };
 
         let response = self
@@ -427,7 +485,7 @@ impl PlaylistRepository for DeezerPlaylistRepository<'_> {
 
 #[cfg(test)]
 mod tests {
-    use crate::deezer::API_URL;
+    use crate::deezer::{check_track_ids, deezer_id, API_URL};
     use std::collections::HashMap;
 
     #[test]
@@ -446,4 +504,16 @@ mod tests {
             format!("{}/playlist/{}/tracks?songs=1%2C2", API_URL, "10000000").as_str()
         )
     }
+
+    #[test]
+    pub fn test_malformed_ids_rejected() {
+        assert_eq!(
+            deezer_id("908622995").map(|id| id.value()).ok(),
+            Some(908622995)
+        );
+        assert!(deezer_id("6rqhFgbbKwnb9MLmUQDhG6").is_err());
+
+        assert!(check_track_ids(&["3135556".to_string(), "1109731".to_string()]).is_ok());
+        assert!(check_track_ids(&["3135556".to_string(), "spotify:track:1".to_string()]).is_err());
+    }
 }
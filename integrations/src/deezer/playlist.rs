@@ -16,19 +16,16 @@ pub struct DeezerPlaylist {
     // The playlist's title
     pub title: String,
     // The playlist description
-    #[allow(dead_code)]
     pub description: String,
     // The playlist's duration (seconds)
     #[allow(dead_code)]
     pub duration: u32,
     // If the playlist is public or not
-    #[allow(dead_code)]
     pub public: bool,
     // If the playlist is the love tracks playlist
     #[allow(dead_code)]
     pub is_loved_track: bool,
     // If the playlist is collaborative or not
-    #[allow(dead_code)]
     pub collaborative: bool,
     // Nb tracks in the playlist
     pub nb_tracks: u32,
@@ -56,6 +53,7 @@ impl From<DeezerPlaylist> for Playlist {
     fn from(val: DeezerPlaylist) -> Playlist {
         let id = PlaylistId::Owned(val.id);
         let name = val.title;
+        let owner_id = val.creator.id.as_ref().map(ToString::to_string);
         let owner = val.creator.name.expect("missing creator name");
         let total_songs = val.nb_tracks;
         let provider_url = val.link;
@@ -77,12 +75,21 @@ impl From<DeezerPlaylist> for Playlist {
             covers.insert(ImageCover::Lg(picture_xl));
         }
 
-        Playlist::new(id, name, covers, owner, total_songs, provider_url)
+        let mut playlist = Playlist::new(id, name, covers, owner, total_songs, provider_url);
+
+        playlist.set_description(Some(val.description));
+        playlist.set_public(Some(val.public));
+        playlist.set_collaborative(Some(val.collaborative));
+        playlist.set_owner_id(owner_id);
+
+        playlist
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use snk_core::entities::playlist::Playlist;
+
     use crate::deezer::playlist::DeezerPlaylist;
 
     #[test]
@@ -92,5 +99,9 @@ mod tests {
 
         assert_eq!(json.title, "Women of Rap");
         assert_eq!(json.nb_tracks, 50);
+
+        let playlist = Playlist::from(json);
+
+        assert_eq!(playlist.owner_id(), Some(&"917475151".to_string()));
     }
 }
@@ -0,0 +1,425 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::{
+        catalog_search_repository::{
+            CatalogSearchRepository, CatalogSearchRepositoryError, CatalogSearchRepositoryResult,
+        },
+        track_search_repository::{
+            TrackSearchRepository, TrackSearchRepositoryError, TrackSearchRepositoryResult,
+        },
+    },
+    entities::{
+        album::{Album, AlbumWithArtists},
+        artist::Artist,
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{
+        image_cover::ImageCover, product_id::ProductId, provider::provider_id::ProviderId,
+    },
+};
+use url::Url;
+
+use super::{
+    artist::{DeezerIdType, ReducedArtist},
+    error::{DeezerErrorPayload, DeezerErrorType},
+    library::DeezerLibraryAlbum,
+    track::DeezerTrack,
+    DeezerList, DeezerPlaylistRepository, API_URL,
+};
+
+/// Maximum number of candidates fetched by search
+static SEARCH_LIMIT: &str = "10";
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeezerSearchResponse {
+    Error(DeezerErrorPayload),
+    Track(Box<DeezerTrack>),
+    ListTracks(DeezerList<DeezerSearchTrack>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeezerCatalogResponse<T> {
+    Error(DeezerErrorPayload),
+    List(DeezerList<T>),
+    Item(T),
+}
+
+/// Album looked up by UPC, the full album having the fields of a library album
+#[derive(Debug, Deserialize)]
+struct DeezerUpcAlbum {
+    upc: String,
+    #[serde(flatten)]
+    album: DeezerLibraryAlbum,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeezerSearchAlbum {
+    // The Deezer album id
+    pub id: DeezerIdType,
+    // The album title
+    pub title: String,
+    // The url of the album's cover in size small.
+    pub cover_small: Option<Url>,
+    // The url of the album's cover in size medium.
+    pub cover_medium: Option<Url>,
+    // The url of the album's cover in size big.
+    pub cover_big: Option<Url>,
+}
+
+/// Track as returned by the search endpoint, without ISRC nor contributors
+#[derive(Debug, Deserialize)]
+pub struct DeezerSearchTrack {
+    // The track's Deezer id
+    pub id: DeezerIdType,
+    // The track's fulltitle
+    pub title: String,
+    // The url of the track on Deezer
+    pub link: Url,
+    // true if the track is readable in the player for the current user
+    pub readable: Option<bool>,
+    // The track's duration in seconds
+    pub duration: u32,
+    // Whether the track contains explicit lyrics
+    pub explicit_lyrics: Option<bool>,
+    // The url of track's preview file. This file contains the first 30 seconds of the track
+    pub preview: Option<Url>,
+    // artist object containing : id, name, link, picture...
+    pub artist: ReducedArtist,
+    // album object containing : id, title, cover...
+    pub album: DeezerSearchAlbum,
+}
+
+impl TryFrom<DeezerSearchTrack> for TrackWithAlbumAndArtists {
+    type Error = &'static str;
+
+    fn try_from(search_track: DeezerSearchTrack) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::DEEZER;
+
+        let Some(artist_id) = search_track.artist.id else {
+            return Err("artist.id is missing");
+        };
+
+        let Some(artist_name) = search_track.artist.name else {
+            return Err("artist.name is missing");
+        };
+
+        let artist = Artist::new(
+            HashMap::from_iter([(provider_id.clone(), artist_id.to_string())]),
+            artist_name,
+            search_track
+                .artist
+                .link
+                .map(|link| HashMap::from_iter([(provider_id.clone(), link)]))
+                .unwrap_or_default(),
+        );
+
+        let mut covers = HashSet::new();
+
+        if let Some(cover) = search_track.album.cover_small {
+            covers.insert(ImageCover::Sm(cover));
+        }
+        if let Some(cover) = search_track.album.cover_medium {
+            covers.insert(ImageCover::Md(cover));
+        }
+        if let Some(cover) = search_track.album.cover_big {
+            covers.insert(ImageCover::Lg(cover));
+        }
+
+        // The release date is not part of search results
+        let album = Album::new(
+            HashSet::from_iter([ProductId::Provider((
+                provider_id.clone(),
+                search_track.album.id.to_string(),
+            ))]),
+            search_track.album.title,
+            DateTime::<Utc>::default(),
+            covers,
+            HashMap::new(),
+        );
+
+        let mut track = TrackWithAlbumAndArtists::new(
+            HashSet::from_iter([ProductId::Provider((
+                provider_id.clone(),
+                search_track.id.to_string(),
+            ))]),
+            search_track.title,
+            search_track.duration * 1000,
+            HashMap::from_iter([(provider_id, search_track.link)]),
+            album,
+            vec![artist],
+        );
+
+        track.explicit = search_track.explicit_lyrics;
+        track.preview_url = search_track.preview;
+        track.playable = search_track.readable;
+
+        Ok(track)
+    }
+}
+
+/// Advanced search query (https://developers.deezer.com/api/search)
+fn search_query(track: &TrackWithAlbumAndArtists) -> String {
+    let mut query = format!("track:\"{}\"", track.name().replace('"', ""));
+
+    if let Some(artist) = track.artists().first() {
+        query.push_str(&format!(" artist:\"{}\"", artist.name().replace('"', "")));
+    }
+
+    query
+}
+
+/// Advanced album search query
+fn album_search_query(album: &AlbumWithArtists) -> String {
+    let mut query = format!("album:\"{}\"", album.album().name().replace('"', ""));
+
+    if let Some(artist) = album.artists().first() {
+        query.push_str(&format!(" artist:\"{}\"", artist.name().replace('"', "")));
+    }
+
+    query
+}
+
+impl DeezerPlaylistRepository<'_> {
+    async fn catalog_request<T: DeserializeOwned>(
+        &self,
+        url: Url,
+    ) -> CatalogSearchRepositoryResult<DeezerCatalogResponse<T>> {
+        let response = self.http_client.get(url).send().await.map_err(|err| {
+            CatalogSearchRepositoryError::ServiceError(format!(
+                "CatalogSearchRepository - Failed to send request - {:?}",
+                err
+            ))
+        })?;
+
+        response
+            .error_for_status()
+            .map_err(|err| {
+                CatalogSearchRepositoryError::ServiceError(format!(
+                    "CatalogSearchRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<DeezerCatalogResponse<T>>()
+            .await
+            .map_err(|err| {
+                CatalogSearchRepositoryError::ServiceError(format!(
+                    "CatalogSearchRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })
+    }
+
+    async fn catalog_search<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        query: &str,
+    ) -> CatalogSearchRepositoryResult<Vec<T>> {
+        let url = Url::parse_with_params(
+            &format!("{}/{}", API_URL, endpoint),
+            [("q", query), ("limit", SEARCH_LIMIT)],
+        )
+        .map_err(|err| CatalogSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        match self.catalog_request::<T>(url).await? {
+            DeezerCatalogResponse::List(deezer_list) => Ok(deezer_list.data),
+            DeezerCatalogResponse::Error(deezer_error_payload) => Err(
+                CatalogSearchRepositoryError::ServiceError(deezer_error_payload.error.message),
+            ),
+            DeezerCatalogResponse::Item(_) => Err(CatalogSearchRepositoryError::ServiceError(
+                "bad response format".to_string(),
+            )),
+        }
+    }
+
+    async fn search_request(&self, url: Url) -> TrackSearchRepositoryResult<DeezerSearchResponse> {
+        let response = self.http_client.get(url).send().await.map_err(|err| {
+            TrackSearchRepositoryError::ServiceError(format!(
+                "TrackSearchRepository - Failed to send request - {:?}",
+                err
+            ))
+        })?;
+
+        response
+            .error_for_status()
+            .map_err(|err| {
+                TrackSearchRepositoryError::ServiceError(format!(
+                    "TrackSearchRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<DeezerSearchResponse>()
+            .await
+            .map_err(|err| {
+                TrackSearchRepositoryError::ServiceError(format!(
+                    "TrackSearchRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })
+    }
+}
+
+impl TrackSearchRepository for DeezerPlaylistRepository<'_> {
+    async fn find_by_isrc(
+        &self,
+        isrc: &str,
+    ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        let url = format!("{}/track/isrc:{}", API_URL, isrc)
+            .parse::<Url>()
+            .map_err(|err| TrackSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        match self.search_request(url).await? {
+            DeezerSearchResponse::Track(deezer_track) => (*deezer_track)
+                .try_into()
+                .map(Some)
+                .map_err(|err: &'static str| {
+                    TrackSearchRepositoryError::ServiceError(err.to_string())
+                }),
+            DeezerSearchResponse::Error(deezer_error) => {
+                match DeezerErrorType::try_from(deezer_error.error) {
+                    Ok(DeezerErrorType::DataNotFound) => Ok(None),
+                    Ok(other_error) => Err(TrackSearchRepositoryError::ServiceError(
+                        other_error.to_string(),
+                    )),
+                    Err(err) => Err(TrackSearchRepositoryError::ServiceError(err.to_string())),
+                }
+            }
+            DeezerSearchResponse::ListTracks(_) => Err(TrackSearchRepositoryError::ServiceError(
+                "bad response format".to_string(),
+            )),
+        }
+    }
+
+    async fn search(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let url = Url::parse_with_params(
+            &format!("{}/search/track", API_URL),
+            [("q", search_query(track).as_str()), ("limit", SEARCH_LIMIT)],
+        )
+        .map_err(|err| TrackSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        match self.search_request(url).await? {
+            DeezerSearchResponse::ListTracks(deezer_list) => deezer_list
+                .data
+                .into_iter()
+                .map(TrackWithAlbumAndArtists::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err: &'static str| {
+                    TrackSearchRepositoryError::ServiceError(err.to_string())
+                }),
+            DeezerSearchResponse::Error(deezer_error_payload) => Err(
+                TrackSearchRepositoryError::ServiceError(deezer_error_payload.error.message),
+            ),
+            DeezerSearchResponse::Track(_) => Err(TrackSearchRepositoryError::ServiceError(
+                "bad response format".to_string(),
+            )),
+        }
+    }
+}
+
+impl CatalogSearchRepository for DeezerPlaylistRepository<'_> {
+    async fn find_album_by_upc(
+        &self,
+        upc: &str,
+    ) -> CatalogSearchRepositoryResult<Option<AlbumWithArtists>> {
+        let url = format!("{}/album/upc:{}", API_URL, upc)
+            .parse::<Url>()
+            .map_err(|err| CatalogSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        match self.catalog_request::<DeezerUpcAlbum>(url).await? {
+            DeezerCatalogResponse::Item(deezer_album) => {
+                let mut album = AlbumWithArtists::try_from(deezer_album.album).map_err(
+                    |err: &'static str| CatalogSearchRepositoryError::ServiceError(err.to_string()),
+                )?;
+
+                album
+                    .album
+                    .extend_ids(ProductId::barcode(&deezer_album.upc).ok());
+
+                Ok(Some(album))
+            }
+            DeezerCatalogResponse::Error(deezer_error) => {
+                match DeezerErrorType::try_from(deezer_error.error) {
+                    Ok(DeezerErrorType::DataNotFound) => Ok(None),
+                    Ok(other_error) => Err(CatalogSearchRepositoryError::ServiceError(
+                        other_error.to_string(),
+                    )),
+                    Err(err) => Err(CatalogSearchRepositoryError::ServiceError(err.to_string())),
+                }
+            }
+            DeezerCatalogResponse::List(_) => Err(CatalogSearchRepositoryError::ServiceError(
+                "bad response format".to_string(),
+            )),
+        }
+    }
+
+    async fn search_albums(
+        &self,
+        album: &AlbumWithArtists,
+    ) -> CatalogSearchRepositoryResult<Vec<AlbumWithArtists>> {
+        self.catalog_search::<DeezerLibraryAlbum>("search/album", &album_search_query(album))
+            .await?
+            .into_iter()
+            .map(AlbumWithArtists::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| CatalogSearchRepositoryError::ServiceError(err.to_string()))
+    }
+
+    async fn search_artists(&self, artist: &Artist) -> CatalogSearchRepositoryResult<Vec<Artist>> {
+        self.catalog_search::<ReducedArtist>("search/artist", artist.name())
+            .await?
+            .into_iter()
+            .map(Artist::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| CatalogSearchRepositoryError::ServiceError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::{album::AlbumWithArtists, track::TrackWithAlbumAndArtists},
+        value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
+    use crate::deezer::DeezerList;
+
+    use super::{DeezerSearchTrack, DeezerUpcAlbum};
+
+    #[test]
+    pub fn test_deserialize_search() {
+        let json_str = include_str!("../../tests/deezer/payload_search.json");
+        let json =
+            serde_json::from_str::<DeezerList<DeezerSearchTrack>>(json_str).expect("valid json");
+
+        assert_eq!(json.data.len(), 1);
+
+        let track = TrackWithAlbumAndArtists::try_from(json.data.into_iter().next().unwrap())
+            .expect("valid track");
+
+        assert!(track.ids().contains(&ProductId::Provider((
+            ProviderId::DEEZER,
+            "630286212".to_string()
+        ))));
+        assert_eq!(track.artists()[0].name(), "Kehlani");
+        assert_eq!(track.duration_ms(), 201_000);
+        assert_eq!(track.explicit(), Some(true));
+        assert_eq!(track.playable(), Some(true));
+    }
+
+    #[test]
+    pub fn test_deserialize_upc_album() {
+        let json_str = include_str!("../../tests/deezer/payload_album.json");
+        let json = serde_json::from_str::<DeezerUpcAlbum>(json_str).expect("valid json");
+
+        assert_eq!(json.upc, "196922889738");
+        assert!(AlbumWithArtists::try_from(json.album).is_ok());
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
-use chrono::NaiveDateTime;
+use chrono::NaiveDate;
 use serde::Deserialize;
 use snk_core::{
     entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
     value_objects::{
-        image_cover::ImageCover, product_id::ProductId, provider::provider_id::ProviderId,
+        image_cover::ImageCover, isrc::Isrc, product_id::ProductId,
+        provider::provider_id::ProviderId,
     },
 };
 use url::Url;
@@ -71,11 +72,10 @@ impl TryInto<TrackWithAlbumAndArtists> for DeezerTrack {
     fn try_into(self) -> Result<TrackWithAlbumAndArtists, Self::Error> {
         let mut ids = HashSet::new();
 
-        ids.insert(ProductId::ISRC(self.isrc));
-        ids.insert(ProductId::Provider((
-            ProviderId::new("deezer".to_string()),
-            self.id,
-        )));
+        if let Ok(isrc) = Isrc::parse(&self.isrc) {
+            ids.insert(ProductId::ISRC(isrc));
+        }
+        ids.insert(ProductId::Provider((ProviderId::DEEZER, self.id)));
 
         let Ok(duration) = self.duration.parse::<u32>() else {
             return Err("duration is corrupted");
@@ -83,19 +83,22 @@ impl TryInto<TrackWithAlbumAndArtists> for DeezerTrack {
 
         let mut urls = HashMap::new();
 
-        urls.insert(ProviderId::new("deezer".to_string()), self.link);
+        urls.insert(ProviderId::DEEZER, self.link);
 
         let album = get_album(self.album)?;
         let artists = get_artists(self.contributors)?;
 
-        Ok(TrackWithAlbumAndArtists::new(
-            ids,
-            self.title,
-            duration * 1000,
-            urls,
-            album,
-            artists,
-        ))
+        let mut track =
+            TrackWithAlbumAndArtists::new(ids, self.title, duration * 1000, urls, album, artists);
+
+        track.explicit = Some(self.explicit_lyrics);
+        track.disc_number = Some(self.disk_number);
+        track.track_number = Some(self.track_position);
+        track.preview_url = Some(self.preview);
+        track.available_markets = Some(HashSet::from_iter(self.available_countries));
+        track.playable = Some(self.readable);
+
+        Ok(track)
     }
 }
 
@@ -110,11 +113,8 @@ fn get_album(reduced_album: ReducedAlbum) -> Result<Album, &'static str> {
 
     let mut album_ids = HashSet::new();
 
-    album_ids.insert(ProductId::UPC(album_upc));
-    album_ids.insert(ProductId::Provider((
-        ProviderId::new("deezer".to_string()),
-        album_id,
-    )));
+    album_ids.extend(ProductId::barcode(&album_upc).ok());
+    album_ids.insert(ProductId::Provider((ProviderId::DEEZER, album_id)));
 
     let Some(album_title) = reduced_album.title else {
         return Err("album.title is missing");
@@ -153,7 +153,9 @@ fn get_album(reduced_album: ReducedAlbum) -> Result<Album, &'static str> {
     let Some(album_release_date) = reduced_album.release_date else {
         return Err("album.release_date is missing");
     };
-    let Ok(album_release_date) = NaiveDateTime::parse_from_str(&album_release_date, "%Y-%m-%d")
+    let Some(album_release_date) = NaiveDate::parse_from_str(&album_release_date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
     else {
         return Err("album.release_date is corrupted");
     };
@@ -164,7 +166,7 @@ fn get_album(reduced_album: ReducedAlbum) -> Result<Album, &'static str> {
 
     let mut album_urls = HashMap::new();
 
-    album_urls.insert(ProviderId::new("deezer".to_string()), album_link);
+    album_urls.insert(ProviderId::DEEZER, album_link);
 
     let album = Album::new(
         album_ids,
@@ -187,7 +189,7 @@ fn get_artists(reduced_artists: Vec<ReducedArtist>) -> Result<Vec<Artist>, &'sta
                 return Err("artist.id is missing");
             };
 
-            ids.insert(ProviderId::new("deezer".to_string()), id.to_string());
+            ids.insert(ProviderId::DEEZER, id.to_string());
 
             let Some(name) = reduced.name else {
                 return Err("artist.name is missing");
@@ -199,7 +201,7 @@ fn get_artists(reduced_artists: Vec<ReducedArtist>) -> Result<Vec<Artist>, &'sta
                 return Err("artist.link is missing");
             };
 
-            urls.insert(ProviderId::new("deezer".to_string()), link);
+            urls.insert(ProviderId::DEEZER, link);
 
             Ok(Artist::new(ids, name, urls))
         })
@@ -208,15 +210,29 @@ fn get_artists(reduced_artists: Vec<ReducedArtist>) -> Result<Vec<Artist>, &'sta
 
 #[cfg(test)]
 mod tests {
+    use snk_core::entities::track::TrackWithAlbumAndArtists;
+
     use crate::deezer::track::DeezerTrack;
 
     #[test]
     pub fn test_deserialize_playlist() {
         let json_str = include_str!("../../tests/deezer/payload_track.json");
-        let json = serde_json::from_str::<DeezerTrack>(&json_str).expect("valid json");
+        let mut json = serde_json::from_str::<DeezerTrack>(&json_str).expect("valid json");
 
         assert_eq!(json.title, "How Sweet");
         assert_eq!(json.artist.name, Some("NewJeans".to_string()));
         assert_eq!(json.album.title, Some("How Sweet".to_string()));
+
+        // Only part of the album endpoint
+        json.album.upc = Some("196922880574".to_string());
+
+        let track: TrackWithAlbumAndArtists = json.try_into().expect("valid track");
+
+        assert_eq!(track.explicit(), Some(false));
+        assert_eq!(track.disc_number(), Some(1));
+        assert_eq!(track.track_number(), Some(1));
+        assert!(track.preview_url().is_some());
+        assert_eq!(track.is_available_in("fr"), Some(true));
+        assert_eq!(track.is_playable_in(None), Some(true));
     }
 }
@@ -0,0 +1,95 @@
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::account_profile_repository::{
+        AccountProfileRepository, AccountProfileRepositoryError, AccountProfileRepositoryResult,
+    },
+    entities::account_profile::AccountProfile,
+};
+
+use super::{error::DeezerErrorPayload, DeezerPlaylistRepository, API_URL};
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeezerUserResponse {
+    Error(DeezerErrorPayload),
+    User(DeezerUser),
+}
+
+/// Current user, as returned by `/user/me`
+#[derive(Debug, Deserialize)]
+pub struct DeezerUser {
+    // The user's Deezer ID
+    pub id: u64,
+    // The user's Deezer nickname
+    pub name: String,
+    // The user's country
+    pub country: Option<String>,
+}
+
+impl From<DeezerUser> for AccountProfile {
+    fn from(user: DeezerUser) -> Self {
+        AccountProfile::new(user.id.to_string(), user.name, user.country)
+    }
+}
+
+impl AccountProfileRepository for DeezerPlaylistRepository<'_> {
+    async fn get_profile(&self) -> AccountProfileRepositoryResult<AccountProfile> {
+        let url = format!("{}/user/me", API_URL);
+
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| {
+                AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?
+            .error_for_status()
+            .map_err(|err| {
+                AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<DeezerUserResponse>()
+            .await
+            .map_err(|err| {
+                AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })?;
+
+        match response {
+            DeezerUserResponse::Error(deezer_error_payload) => {
+                Err(AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Error during request - {}",
+                    deezer_error_payload.error.message
+                )))
+            }
+            DeezerUserResponse::User(user) => Ok(user.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::entities::account_profile::AccountProfile;
+
+    use super::DeezerUser;
+
+    #[test]
+    fn test_deserialize_user() {
+        let json_str = include_str!("../../tests/deezer/payload_user.json");
+        let json = serde_json::from_str::<DeezerUser>(json_str).expect("valid json");
+
+        let profile = AccountProfile::from(json);
+
+        assert_eq!(profile.id(), "2529");
+        assert_eq!(profile.name(), "dzuser");
+        assert_eq!(profile.country_code(), Some(&"FR".to_string()));
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Deserialize;
+
+/// Error payload (https://www.last.fm/api/errorcodes)
+#[derive(Debug, Deserialize)]
+pub struct LastfmError {
+    pub error: u32,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LastfmError;
+
+    #[test]
+    fn test_deserialize_error() {
+        let json_str = include_str!("../../tests/lastfm/payload_error.json");
+        let json = serde_json::from_str::<LastfmError>(json_str).expect("valid json");
+
+        assert_eq!(json.error, 6);
+        assert_eq!(json.message, "User not found");
+    }
+}
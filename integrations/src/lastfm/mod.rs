@@ -0,0 +1,450 @@
+pub mod error;
+pub mod track;
+
+use std::{collections::HashSet, time::Duration};
+
+use error::LastfmError;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client,
+};
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::playlist_id::PlaylistId,
+};
+use track::{LastfmPageAttributes, LastfmTrack};
+use url::Url;
+
+static API_URL: &str = "https://ws.audioscrobbler.com/2.0/";
+
+/// Maximum page size accepted by the Last.fm API
+static PAGE_LIMIT: u32 = 1000;
+
+/// Number of tracks of the top tracks playlists
+static TOP_TRACKS_LIMIT: u32 = 100;
+
+/// Time ranges of the top tracks, each one exposed as a playlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LastfmPeriod {
+    Week,
+    Month,
+    ThreeMonths,
+    SixMonths,
+    Year,
+    Overall,
+}
+
+impl LastfmPeriod {
+    pub const ALL: [LastfmPeriod; 6] = [
+        LastfmPeriod::Week,
+        LastfmPeriod::Month,
+        LastfmPeriod::ThreeMonths,
+        LastfmPeriod::SixMonths,
+        LastfmPeriod::Year,
+        LastfmPeriod::Overall,
+    ];
+
+    /// Value of the `period` parameter of `user.getTopTracks`
+    pub fn value(&self) -> &'static str {
+        match self {
+            LastfmPeriod::Week => "7day",
+            LastfmPeriod::Month => "1month",
+            LastfmPeriod::ThreeMonths => "3month",
+            LastfmPeriod::SixMonths => "6month",
+            LastfmPeriod::Year => "12month",
+            LastfmPeriod::Overall => "overall",
+        }
+    }
+
+    pub fn playlist_name(&self) -> &'static str {
+        match self {
+            LastfmPeriod::Week => "Top tracks - last 7 days",
+            LastfmPeriod::Month => "Top tracks - last month",
+            LastfmPeriod::ThreeMonths => "Top tracks - last 3 months",
+            LastfmPeriod::SixMonths => "Top tracks - last 6 months",
+            LastfmPeriod::Year => "Top tracks - last 12 months",
+            LastfmPeriod::Overall => "Top tracks - all time",
+        }
+    }
+
+    /// Id of the virtual playlist (ex: `top-3month`)
+    pub fn playlist_id(&self) -> PlaylistId {
+        PlaylistId::Owned(format!("top-{}", self.value()))
+    }
+
+    pub fn from_playlist_id(id: &str) -> Option<Self> {
+        let value = id.strip_prefix("top-")?;
+
+        Self::ALL.into_iter().find(|period| period.value() == value)
+    }
+}
+
+/// Tracks of a page, a single track is not sent as an array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LastfmTracks {
+    Many(Vec<LastfmTrack>),
+    One(Box<LastfmTrack>),
+}
+
+impl From<LastfmTracks> for Vec<LastfmTrack> {
+    fn from(tracks: LastfmTracks) -> Self {
+        match tracks {
+            LastfmTracks::Many(tracks) => tracks,
+            LastfmTracks::One(track) => vec![*track],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LastfmTrackList {
+    #[serde(default = "LastfmTrackList::no_tracks")]
+    track: LastfmTracks,
+    #[serde(rename = "@attr")]
+    pub attributes: LastfmPageAttributes,
+}
+
+impl LastfmTrackList {
+    fn no_tracks() -> LastfmTracks {
+        LastfmTracks::Many(vec![])
+    }
+
+    pub fn total(&self) -> u32 {
+        self.attributes.total.parse().unwrap_or_default()
+    }
+
+    pub fn has_next_page(&self) -> bool {
+        self.attributes.page.parse::<u32>().unwrap_or_default()
+            < self
+                .attributes
+                .total_pages
+                .parse::<u32>()
+                .unwrap_or_default()
+    }
+
+    pub fn into_tracks(self) -> Vec<LastfmTrack> {
+        self.track.into()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LastfmResponse {
+    Error(LastfmError),
+    LovedTracks { lovedtracks: LastfmTrackList },
+    TopTracks { toptracks: LastfmTrackList },
+}
+
+/// Read-only repository over the listening history of a Last.fm user:
+/// loved tracks are the favourites, top tracks of each [`LastfmPeriod`] are playlists
+pub struct LastfmPlaylistRepository<'a> {
+    http_client: Client,
+    #[allow(dead_code)]
+    music_account_provider: &'a MusicAccountProvider,
+    api_key: String,
+    username: String,
+}
+
+impl<'a> LastfmPlaylistRepository<'a> {
+    pub fn new(
+        music_account_provider: &'a MusicAccountProvider,
+        api_key: String,
+        username: String,
+    ) -> Result<Self, &'static str> {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+
+        Ok(Self {
+            http_client: Client::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .default_headers(default_headers)
+                .build()
+                .map_err(|err| {
+                    eprintln!("{:?}", err);
+                    "LastfmPlaylistRepository::new: Could not init HTTP client"
+                })?,
+            music_account_provider,
+            api_key,
+            username,
+        })
+    }
+
+    fn read_only() -> PlaylistRepositoryError {
+        PlaylistRepositoryError::ServiceError(
+            "operation not permitted with Last.fm, playlists are read-only".to_string(),
+        )
+    }
+
+    /// Fetch a page of the loved tracks, or of the top tracks of a period
+    async fn fetch_page(
+        &self,
+        period: Option<LastfmPeriod>,
+        page: u32,
+        limit: u32,
+    ) -> PlaylistRepositoryResult<LastfmTrackList> {
+        let mut query = vec![
+            ("user", self.username.clone()),
+            ("api_key", self.api_key.clone()),
+            ("format", "json".to_string()),
+            ("page", page.to_string()),
+            ("limit", limit.to_string()),
+        ];
+
+        match period {
+            Some(period) => {
+                query.push(("method", "user.gettoptracks".to_string()));
+                query.push(("period", period.value().to_string()));
+            }
+            None => query.push(("method", "user.getlovedtracks".to_string())),
+        }
+
+        let response = self
+            .http_client
+            .get(API_URL)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?;
+
+        // Errors are sent as payloads, with a 4xx or a 200 status
+        let status = response.status();
+        let response_body = response.json::<LastfmResponse>().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to parse response - {:?} ({})",
+                err, status
+            ))
+        })?;
+
+        match response_body {
+            LastfmResponse::Error(lastfm_error) => {
+                Err(PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Error during request - {} ({})",
+                    lastfm_error.message, lastfm_error.error
+                )))
+            }
+            LastfmResponse::LovedTracks { lovedtracks } => Ok(lovedtracks),
+            LastfmResponse::TopTracks { toptracks } => Ok(toptracks),
+        }
+    }
+
+    fn playlist(&self, id: PlaylistId, name: &str, total_songs: u32) -> Playlist {
+        let path = match id {
+            PlaylistId::LikedSongs => "loved",
+            PlaylistId::Owned(_) => "library/tracks",
+        };
+        let provider_url = Url::parse("https://www.last.fm/user/")
+            .and_then(|url| url.join(&format!("{}/{}", self.username, path)))
+            .unwrap_or_else(|_| Url::parse("https://www.last.fm").expect("valid url"));
+
+        Playlist::new(
+            id,
+            name.to_string(),
+            HashSet::new(),
+            self.username.clone(),
+            total_songs,
+            provider_url,
+        )
+    }
+}
+
+impl PlaylistRepository for LastfmPlaylistRepository<'_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => {
+                let page = self.fetch_page(None, 1, 1).await?;
+
+                Ok(Some(self.playlist(
+                    id.clone(),
+                    "Loved tracks",
+                    page.total(),
+                )))
+            }
+            PlaylistId::Owned(lastfm_id) => {
+                let Some(period) = LastfmPeriod::from_playlist_id(lastfm_id) else {
+                    return Ok(None);
+                };
+
+                let page = self.fetch_page(Some(period), 1, 1).await?;
+
+                Ok(Some(self.playlist(
+                    id.clone(),
+                    period.playlist_name(),
+                    page.total().min(TOP_TRACKS_LIMIT),
+                )))
+            }
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let mut playlists = vec![];
+
+        for period in LastfmPeriod::ALL {
+            if let Some(playlist) = self.get(&period.playlist_id()).await? {
+                playlists.push(playlist);
+            }
+        }
+
+        Ok(playlists)
+    }
+
+    async fn create(&self, _spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        Err(Self::read_only())
+    }
+
+    async fn update_metadata(
+        &self,
+        _id: &PlaylistId,
+        _spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        Err(Self::read_only())
+    }
+
+    async fn add_tracks(
+        &self,
+        _playlist_id: &PlaylistId,
+        _ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn delete_tracks(
+        &self,
+        _playlist_id: &PlaylistId,
+        _ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let tracks = match playlist_id {
+            PlaylistId::LikedSongs => {
+                let mut tracks = vec![];
+                let mut page_number = 1;
+
+                loop {
+                    let page = self.fetch_page(None, page_number, PAGE_LIMIT).await?;
+                    let has_next_page = page.has_next_page();
+
+                    tracks.extend(page.into_tracks());
+
+                    if !has_next_page {
+                        break tracks;
+                    }
+
+                    page_number += 1;
+                }
+            }
+            PlaylistId::Owned(lastfm_id) => {
+                let period = LastfmPeriod::from_playlist_id(lastfm_id).ok_or(
+                    PlaylistRepositoryError::ServiceError(format!(
+                        "PlaylistRepository - Unknown playlist {}",
+                        lastfm_id
+                    )),
+                )?;
+
+                self.fetch_page(Some(period), 1, TOP_TRACKS_LIMIT)
+                    .await?
+                    .into_tracks()
+            }
+        };
+
+        Ok(tracks.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::track::TrackWithAlbumAndArtists,
+        value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
+    use super::{LastfmPeriod, LastfmResponse};
+
+    #[test]
+    fn test_deserialize_loved_tracks() {
+        let json_str = include_str!("../../tests/lastfm/payload_loved_tracks.json");
+        let json = serde_json::from_str::<LastfmResponse>(json_str).expect("valid json");
+
+        let LastfmResponse::LovedTracks { lovedtracks } = json else {
+            panic!("loved tracks expected");
+        };
+
+        assert_eq!(lovedtracks.total(), 2);
+        assert!(!lovedtracks.has_next_page());
+
+        let tracks = lovedtracks
+            .into_tracks()
+            .into_iter()
+            .map(TrackWithAlbumAndArtists::from)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tracks[0].name(), "How Sweet");
+        assert!(tracks[0].ids().contains(&ProductId::Provider((
+            ProviderId::MUSICBRAINZ,
+            "4c1e0d37-5f7d-4b55-8d37-ea6b3d4f6c10".to_string()
+        ))));
+        assert_eq!(
+            tracks[0].artists()[0].ids().get(&ProviderId::MUSICBRAINZ),
+            Some(&"1ad2e2a6-8e34-4a4e-9b50-4c5ea6dbcb1b".to_string())
+        );
+        // Unknown MBID
+        assert_eq!(tracks[1].ids().len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_top_tracks() {
+        let json_str = include_str!("../../tests/lastfm/payload_top_tracks.json");
+        let json = serde_json::from_str::<LastfmResponse>(json_str).expect("valid json");
+
+        let LastfmResponse::TopTracks { toptracks } = json else {
+            panic!("top tracks expected");
+        };
+
+        assert!(toptracks.has_next_page());
+
+        let tracks = toptracks.into_tracks();
+
+        // Single track sent as an object
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(
+            TrackWithAlbumAndArtists::from(tracks.into_iter().next().unwrap()).duration_ms(),
+            201_000
+        );
+    }
+
+    #[test]
+    fn test_period_playlist_id() {
+        for period in LastfmPeriod::ALL {
+            assert_eq!(
+                LastfmPeriod::from_playlist_id(&period.playlist_id().to_string()),
+                Some(period)
+            );
+        }
+
+        assert_eq!(LastfmPeriod::from_playlist_id("top-2day"), None);
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{
+        image_cover::ImageCover, product_id::ProductId, provider::provider_id::ProviderId,
+    },
+};
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+pub struct LastfmImage {
+    /// small, medium, large, extralarge
+    pub size: String,
+    /// Url of the image, empty when there is none
+    #[serde(rename = "#text")]
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LastfmArtist {
+    pub name: String,
+    /// MusicBrainz id of the artist, empty when unknown
+    #[serde(default)]
+    pub mbid: String,
+    /// Url of the artist page on Last.fm
+    pub url: Option<Url>,
+}
+
+/// Page information, numbers are sent as strings
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastfmPageAttributes {
+    pub page: String,
+    pub total_pages: String,
+    pub total: String,
+}
+
+/// Track of the loved or top tracks lists
+#[derive(Debug, Deserialize)]
+pub struct LastfmTrack {
+    pub name: String,
+    /// MusicBrainz id of the recording, empty when unknown
+    #[serde(default)]
+    pub mbid: String,
+    /// Url of the track page on Last.fm
+    pub url: Url,
+    /// Duration in seconds, only known for top tracks ("0" when unknown)
+    pub duration: Option<String>,
+    pub artist: LastfmArtist,
+    #[serde(default)]
+    pub image: Vec<LastfmImage>,
+}
+
+impl From<LastfmTrack> for TrackWithAlbumAndArtists {
+    fn from(lastfm_track: LastfmTrack) -> Self {
+        let provider_id = ProviderId::LASTFM;
+        let musicbrainz_id = ProviderId::MUSICBRAINZ;
+
+        // Tracks have no id on Last.fm, their page url is used instead
+        let mut ids = HashSet::from_iter([ProductId::Provider((
+            provider_id.clone(),
+            lastfm_track.url.to_string(),
+        ))]);
+
+        if !lastfm_track.mbid.is_empty() {
+            ids.insert(ProductId::Provider((
+                musicbrainz_id.clone(),
+                lastfm_track.mbid,
+            )));
+        }
+
+        let mut artist_ids = HashMap::new();
+
+        if !lastfm_track.artist.mbid.is_empty() {
+            artist_ids.insert(musicbrainz_id, lastfm_track.artist.mbid);
+        }
+
+        let artist = Artist::new(
+            artist_ids,
+            lastfm_track.artist.name,
+            lastfm_track
+                .artist
+                .url
+                .map(|url| HashMap::from_iter([(provider_id.clone(), url)]))
+                .unwrap_or_default(),
+        );
+
+        let covers = lastfm_track
+            .image
+            .into_iter()
+            .filter_map(|image| {
+                let url = image.url.parse::<Url>().ok()?;
+
+                Some(match image.size.as_str() {
+                    "small" => ImageCover::Sm(url),
+                    "medium" => ImageCover::Md(url),
+                    "large" => ImageCover::Lg(url),
+                    _ => ImageCover::Other(url),
+                })
+            })
+            .collect();
+
+        // Lists only hold the track & artist, album name & release date are unknown
+        let album = Album::new(
+            HashSet::new(),
+            String::new(),
+            DateTime::<Utc>::default(),
+            covers,
+            HashMap::new(),
+        );
+
+        let duration_ms = lastfm_track
+            .duration
+            .and_then(|duration| duration.parse::<u32>().ok())
+            .unwrap_or_default()
+            * 1000;
+
+        TrackWithAlbumAndArtists::new(
+            ids,
+            lastfm_track.name,
+            duration_ms,
+            HashMap::from_iter([(provider_id, lastfm_track.url)]),
+            album,
+            vec![artist],
+        )
+    }
+}
@@ -1,2 +1,17 @@
+// Payload tests deserialize their `include_str!` fixtures through a borrow
+#![cfg_attr(test, allow(clippy::needless_borrow))]
+
+mod chunks;
+mod cover;
+pub mod csv;
 pub mod deezer;
+pub mod lastfm;
+mod local;
+pub mod m3u;
+pub mod musicbrainz;
+pub mod registry;
+pub mod soundcloud;
 pub mod spotify;
+pub mod tidal;
+pub mod xspf;
+pub mod youtube;
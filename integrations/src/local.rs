@@ -0,0 +1,100 @@
+//! Helpers shared by the repositories storing playlists as local files (M3U, XSPF...)
+
+use std::path::Path;
+
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+use tokio::fs;
+use url::Url;
+
+/// Provider of the tracks read from playlist files
+pub(crate) fn provider_id() -> ProviderId {
+    ProviderId::LOCAL
+}
+
+/// File name (without extension) of a playlist, rejecting ids escaping the playlists directory
+pub(crate) fn playlist_file_name(id: &PlaylistId) -> PlaylistRepositoryResult<String> {
+    let name = id.to_string();
+
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(PlaylistRepositoryError::ServiceError(format!(
+            "PlaylistRepository - Invalid playlist id: {}",
+            name
+        )));
+    }
+
+    Ok(name)
+}
+
+/// Playlist id of a new playlist, characters forbidden in file names are replaced
+pub(crate) fn new_playlist_id(name: &str) -> PlaylistId {
+    PlaylistId::Owned(
+        name.trim()
+            .trim_start_matches('.')
+            .replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_"),
+    )
+}
+
+/// Ids of the playlists of a directory, sorted by name
+pub(crate) async fn playlist_ids(
+    directory: &Path,
+    extensions: &[&str],
+) -> PlaylistRepositoryResult<Vec<PlaylistId>> {
+    let mut entries = fs::read_dir(directory)
+        .await
+        .map_err(|err| io_error(directory, err))?;
+    let mut ids = vec![];
+
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|err| io_error(directory, err))?
+    {
+        let path = entry.path();
+        let is_playlist = path.extension().is_some_and(|extension| {
+            extensions
+                .iter()
+                .any(|playlist_extension| extension == *playlist_extension)
+        });
+
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        if is_playlist {
+            ids.push(match stem == PlaylistId::LikedSongs.to_string() {
+                true => PlaylistId::LikedSongs,
+                false => PlaylistId::Owned(stem.to_string()),
+            });
+        }
+    }
+
+    ids.sort_by_key(|id| id.to_string());
+    // The same playlist may exist with several extensions
+    ids.dedup_by(|id, other| id.to_string() == other.to_string());
+
+    Ok(ids)
+}
+
+pub(crate) fn io_error(path: &Path, err: std::io::Error) -> PlaylistRepositoryError {
+    PlaylistRepositoryError::ServiceError(format!(
+        "PlaylistRepository - {} - {:?}",
+        path.display(),
+        err
+    ))
+}
+
+pub(crate) fn file_url(path: &Path) -> PlaylistRepositoryResult<Url> {
+    let path = std::path::absolute(path).map_err(|err| io_error(path, err))?;
+
+    Url::from_file_path(&path).map_err(|_| {
+        PlaylistRepositoryError::ServiceError(format!(
+            "PlaylistRepository - Invalid path: {}",
+            path.display()
+        ))
+    })
+}
@@ -0,0 +1,304 @@
+pub mod parser;
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Utc};
+use parser::{M3uEntry, M3uPlaylist};
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        album::Album,
+        artist::Artist,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{playlist_id::PlaylistId, product_id::ProductId},
+};
+use tokio::fs;
+use url::Url;
+
+use crate::local::{
+    file_url, io_error, new_playlist_id, playlist_file_name, playlist_ids, provider_id,
+};
+
+static EXTENSION: &str = "m3u8";
+static LEGACY_EXTENSION: &str = "m3u";
+
+/// Playlist repository backed by a directory of `.m3u8` files.
+///
+/// Each file is a playlist, its id being the file name without extension.
+/// Liked songs are stored in `favourites.m3u8`.
+/// Track ids are the locations (paths or URLs) of the entries.
+pub struct M3uPlaylistRepository {
+    directory: PathBuf,
+}
+
+impl M3uPlaylistRepository {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Path of an existing playlist (`.m3u8`, then `.m3u`), or of the playlist to create
+    async fn path(&self, id: &PlaylistId) -> PlaylistRepositoryResult<PathBuf> {
+        let name = playlist_file_name(id)?;
+        let path = self.directory.join(format!("{}.{}", name, EXTENSION));
+        let legacy_path = self
+            .directory
+            .join(format!("{}.{}", name, LEGACY_EXTENSION));
+
+        if !fs::try_exists(&path).await.unwrap_or(false)
+            && fs::try_exists(&legacy_path).await.unwrap_or(false)
+        {
+            return Ok(legacy_path);
+        }
+
+        Ok(path)
+    }
+
+    async fn read(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<M3uPlaylist>> {
+        let path = self.path(id).await?;
+
+        match fs::read(&path).await {
+            Ok(content) => Ok(Some(M3uPlaylist::parse(&String::from_utf8_lossy(&content)))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(io_error(&path, err)),
+        }
+    }
+
+    async fn write(&self, id: &PlaylistId, playlist: &M3uPlaylist) -> PlaylistRepositoryResult<()> {
+        let path = self.path(id).await?;
+
+        fs::write(&path, playlist.to_string())
+            .await
+            .map_err(|err| io_error(&path, err))
+    }
+
+    async fn to_playlist(
+        &self,
+        id: &PlaylistId,
+        m3u_playlist: &M3uPlaylist,
+    ) -> PlaylistRepositoryResult<Playlist> {
+        let path = self.path(id).await?;
+        let provider_url = file_url(&path)?;
+
+        Ok(Playlist::new(
+            id.clone(),
+            m3u_playlist.name.clone().unwrap_or(id.to_string()),
+            HashSet::new(),
+            "me".to_string(),
+            m3u_playlist.entries.len() as u32,
+            provider_url,
+        ))
+    }
+
+    /// Append tracks with their metadata (title, artists, album, duration),
+    /// using their first provider URL as location.
+    ///
+    /// Used to back up streaming playlists, [`PlaylistRepository::add_tracks`]
+    /// only knows the locations.
+    pub async fn add_track_entries(
+        &self,
+        playlist_id: &PlaylistId,
+        tracks: &[TrackWithAlbumAndArtists],
+    ) -> PlaylistRepositoryResult<()> {
+        let mut m3u_playlist = self.read(playlist_id).await?.unwrap_or_default();
+
+        m3u_playlist
+            .entries
+            .extend(tracks.iter().filter_map(|track| {
+                let location = track.provider_urls().values().next()?;
+
+                Some(M3uEntry {
+                    duration: Some(track.duration_ms() / 1000),
+                    artists: track
+                        .artists()
+                        .iter()
+                        .map(|artist| artist.name().clone())
+                        .collect(),
+                    title: track.name().clone(),
+                    album: Some(track.album().name().clone())
+                        .filter(|album_name| !album_name.is_empty()),
+                    location: location.to_string(),
+                })
+            }));
+
+        self.write(playlist_id, &m3u_playlist).await
+    }
+
+    fn to_track(&self, entry: M3uEntry) -> TrackWithAlbumAndArtists {
+        let provider_id = provider_id();
+        let mut urls = HashMap::new();
+
+        match Url::parse(&entry.location) {
+            Ok(url) => {
+                urls.insert(provider_id.clone(), url);
+            }
+            Err(_) => {
+                if let Ok(url) = file_url(&self.directory.join(&entry.location)) {
+                    urls.insert(provider_id.clone(), url);
+                }
+            }
+        }
+
+        let artists = entry
+            .artists
+            .into_iter()
+            .map(|name| Artist::new(HashMap::new(), name, HashMap::new()))
+            .collect();
+
+        // Release date is unknown from playlist files
+        let album = Album::new(
+            HashSet::new(),
+            entry.album.unwrap_or_default(),
+            DateTime::<Utc>::default(),
+            HashSet::new(),
+            HashMap::new(),
+        );
+
+        TrackWithAlbumAndArtists::new(
+            HashSet::from_iter([ProductId::Provider((provider_id, entry.location))]),
+            entry.title,
+            // Unknown when too long to be held in milliseconds
+            entry
+                .duration
+                .and_then(|duration| duration.checked_mul(1000))
+                .unwrap_or_default(),
+            urls,
+            album,
+            artists,
+        )
+    }
+}
+
+impl PlaylistRepository for M3uPlaylistRepository {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match self.read(id).await? {
+            Some(m3u_playlist) => Ok(Some(self.to_playlist(id, &m3u_playlist).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let ids = playlist_ids(&self.directory, &[EXTENSION, LEGACY_EXTENSION]).await?;
+        let mut playlists = vec![];
+
+        for id in ids {
+            if let Some(playlist) = self.get(&id).await? {
+                playlists.push(playlist);
+            }
+        }
+
+        Ok(playlists)
+    }
+
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let id = new_playlist_id(&spec.name);
+
+        if self.read(&id).await?.is_some() {
+            return Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist {} already exists",
+                id
+            )));
+        }
+
+        let m3u_playlist = M3uPlaylist {
+            name: Some(spec.name.clone()),
+            entries: vec![],
+        };
+
+        self.write(&id, &m3u_playlist).await?;
+        self.to_playlist(&id, &m3u_playlist).await
+    }
+
+    /// Only the name is stored by M3U files, in the `#PLAYLIST` directive
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let Some(mut m3u_playlist) = self.read(id).await? else {
+            return Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist {} not found",
+                id
+            )));
+        };
+
+        m3u_playlist.name = Some(spec.name.clone());
+
+        self.write(id, &m3u_playlist).await
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        let Some(playlist) = self.get(id).await? else {
+            return Ok(None);
+        };
+
+        let path = self.path(id).await?;
+
+        fs::remove_file(&path)
+            .await
+            .map_err(|err| io_error(&path, err))?;
+
+        Ok(Some(playlist))
+    }
+
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        let mut m3u_playlist = match (self.read(playlist_id).await?, playlist_id) {
+            (Some(m3u_playlist), _) => m3u_playlist,
+            (None, PlaylistId::LikedSongs) => M3uPlaylist::default(),
+            (None, PlaylistId::Owned(_)) => {
+                return Err(PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Playlist {} not found",
+                    playlist_id
+                )))
+            }
+        };
+
+        m3u_playlist
+            .entries
+            .extend(ids.iter().map(|id| M3uEntry::from_location(id)));
+
+        self.write(playlist_id, &m3u_playlist).await
+    }
+
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        let Some(mut m3u_playlist) = self.read(playlist_id).await? else {
+            return Ok(());
+        };
+
+        m3u_playlist
+            .entries
+            .retain(|entry| !ids.contains(&entry.location));
+
+        self.write(playlist_id, &m3u_playlist).await
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let m3u_playlist = self.read(playlist_id).await?.unwrap_or_default();
+
+        Ok(m3u_playlist
+            .entries
+            .into_iter()
+            .map(|entry| self.to_track(entry))
+            .collect())
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+static HEADER: &str = "#EXTM3U";
+static PLAYLIST_DIRECTIVE: &str = "#PLAYLIST:";
+static TRACK_DIRECTIVE: &str = "#EXTINF:";
+static ALBUM_DIRECTIVE: &str = "#EXTALB:";
+
+/// Entry of an extended M3U playlist
+///
+/// ```text
+/// #EXTINF:<duration in seconds>,<artists> - <title>
+/// #EXTALB:<album>
+/// <location>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct M3uEntry {
+    /// Duration of the track in seconds, `None` when unknown (-1)
+    pub duration: Option<u32>,
+    /// Artists of the track, when the display title follows the "artists - title" convention
+    pub artists: Vec<String>,
+    pub title: String,
+    pub album: Option<String>,
+    /// Path (absolute or relative to the playlist file) or URL of the track
+    pub location: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct M3uPlaylist {
+    pub name: Option<String>,
+    pub entries: Vec<M3uEntry>,
+}
+
+impl M3uEntry {
+    /// Entry without metadata, the title is deduced from the location
+    pub fn from_location(location: &str) -> Self {
+        let file_name = location
+            .trim_end_matches('/')
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(location);
+        let title = file_name
+            .rsplit_once('.')
+            .map(|(stem, _)| stem)
+            .filter(|stem| !stem.is_empty())
+            .unwrap_or(file_name);
+
+        Self {
+            duration: None,
+            artists: vec![],
+            title: title.to_string(),
+            album: None,
+            location: location.to_string(),
+        }
+    }
+
+    fn display_title(&self) -> String {
+        match self.artists.is_empty() {
+            true => self.title.clone(),
+            false => format!("{} - {}", self.artists.join(", "), self.title),
+        }
+    }
+}
+
+impl M3uPlaylist {
+    /// Parse M3U / M3U8 content, unknown directives & comments are ignored
+    pub fn parse(content: &str) -> Self {
+        let mut playlist = M3uPlaylist::default();
+        let mut pending: Option<M3uEntry> = None;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line == HEADER {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix(PLAYLIST_DIRECTIVE) {
+                playlist.name = Some(name.trim().to_string());
+            } else if let Some(info) = line.strip_prefix(TRACK_DIRECTIVE) {
+                pending = Some(parse_track_information(info));
+            } else if let Some(album) = line.strip_prefix(ALBUM_DIRECTIVE) {
+                if let Some(entry) = pending.as_mut() {
+                    entry.album = Some(album.trim().to_string());
+                }
+            } else if !line.starts_with('#') {
+                let entry = match pending.take() {
+                    Some(entry) => M3uEntry {
+                        location: line.to_string(),
+                        ..entry
+                    },
+                    None => M3uEntry::from_location(line),
+                };
+
+                playlist.entries.push(entry);
+            }
+        }
+
+        playlist
+    }
+}
+
+/// Parse the content of an `#EXTINF` directive: `<duration> [attributes],<display title>`
+fn parse_track_information(info: &str) -> M3uEntry {
+    let (header, display_title) = info.split_once(',').unwrap_or((info, ""));
+    let duration = header
+        .split_whitespace()
+        .next()
+        .and_then(|duration| duration.parse::<f64>().ok())
+        .filter(|duration| *duration >= 0.0)
+        .map(|duration| duration.round() as u32);
+
+    let (artists, title) = match display_title.split_once(" - ") {
+        Some((artists, title)) => (
+            artists
+                .split(',')
+                .map(str::trim)
+                .filter(|artist| !artist.is_empty())
+                .map(str::to_string)
+                .collect(),
+            title.trim().to_string(),
+        ),
+        None => (vec![], display_title.trim().to_string()),
+    };
+
+    M3uEntry {
+        duration,
+        artists,
+        title,
+        album: None,
+        location: String::new(),
+    }
+}
+
+impl Display for M3uPlaylist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+
+        if let Some(name) = &self.name {
+            writeln!(f, "{}{}", PLAYLIST_DIRECTIVE, name)?;
+        }
+
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{}{},{}",
+                TRACK_DIRECTIVE,
+                entry
+                    .duration
+                    .map(|duration| duration.to_string())
+                    .unwrap_or("-1".to_string()),
+                entry.display_title()
+            )?;
+
+            if let Some(album) = &entry.album {
+                writeln!(f, "{}{}", ALBUM_DIRECTIVE, album)?;
+            }
+
+            writeln!(f, "{}", entry.location)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{M3uEntry, M3uPlaylist};
+
+    #[test]
+    fn test_parse_playlist() {
+        let content = include_str!("../../tests/m3u/playlist.m3u8");
+        let playlist = M3uPlaylist::parse(content);
+
+        assert_eq!(playlist.name, Some("Women of Rap".to_string()));
+        assert_eq!(playlist.entries.len(), 3);
+        assert_eq!(
+            playlist.entries[0],
+            M3uEntry {
+                duration: Some(219),
+                artists: vec!["NewJeans".to_string()],
+                title: "How Sweet".to_string(),
+                album: Some("How Sweet".to_string()),
+                location: "../Music/NewJeans/How Sweet/01 How Sweet.flac".to_string(),
+            }
+        );
+        assert_eq!(
+            playlist.entries[1].artists,
+            vec!["Kehlani".to_string(), "Ty Dolla $ign".to_string()]
+        );
+        assert_eq!(playlist.entries[2].duration, None);
+    }
+
+    #[test]
+    fn test_parse_plain_playlist() {
+        let playlist = M3uPlaylist::parse("/music/a.mp3\nC:\\Music\\b.flac\n");
+
+        assert_eq!(playlist.entries[0].title, "a");
+        assert_eq!(playlist.entries[1].title, "b");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = include_str!("../../tests/m3u/playlist.m3u8");
+        let playlist = M3uPlaylist::parse(content);
+
+        assert_eq!(M3uPlaylist::parse(&playlist.to_string()), playlist);
+    }
+}
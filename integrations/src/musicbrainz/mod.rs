@@ -0,0 +1,190 @@
+pub mod recording;
+
+use std::time::Duration;
+
+use recording::{MusicBrainzRecording, MusicBrainzRecordingSearch};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use snk_core::{
+    contracts::repositories::track_metadata_repository::{
+        TrackMetadataRepository, TrackMetadataRepositoryError, TrackMetadataRepositoryResult,
+    },
+    entities::track::TrackWithAlbumAndArtists,
+    services::track_matcher::is_same_recording,
+};
+use tokio::{sync::Mutex, time::Instant};
+use url::Url;
+
+static API_URL: &str = "https://musicbrainz.org/ws/2";
+
+/// MusicBrainz allows a single request per second (https://musicbrainz.org/doc/MusicBrainz_API/Rate_Limiting)
+static REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of candidates fetched by search
+static SEARCH_LIMIT: &str = "5";
+
+/// Gap allowed between the duration of a track and the one of recordings, in milliseconds
+static DURATION_TOLERANCE_MS: u32 = 10_000;
+
+pub struct MusicBrainzRepository {
+    http_client: Client,
+    api_url: String,
+    /// Time of the last request, shared by all requests to respect the rate limit
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl MusicBrainzRepository {
+    /// Arguments:
+    /// - user_agent: identifies the application, as required by MusicBrainz
+    ///   (e.g. `SonikSwap/0.1.0 ( contact@example.com )`)
+    pub fn new(user_agent: &str) -> Result<Self, &'static str> {
+        Self::with_api_url(API_URL, user_agent)
+    }
+
+    /// Repository using another MusicBrainz server, such as a mirror
+    pub fn with_api_url(api_url: &str, user_agent: &str) -> Result<Self, &'static str> {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+        default_headers.insert(
+            "User-Agent",
+            user_agent.parse().map_err(|err| {
+                eprintln!("{:?}", err);
+                "MusicBrainzRepository::new: Could not parse header value"
+            })?,
+        );
+
+        Ok(Self {
+            http_client: Client::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .default_headers(default_headers)
+                .build()
+                .map_err(|err| {
+                    eprintln!("{:?}", err);
+                    "MusicBrainzRepository::new: Could not init HTTP client"
+                })?,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            last_request: Mutex::new(None),
+        })
+    }
+
+    /// Wait until a request can be sent without exceeding the rate limit
+    async fn wait_rate_limit(&self) {
+        let mut last_request = self.last_request.lock().await;
+
+        if let Some(last_request) = *last_request {
+            tokio::time::sleep_until(last_request + REQUEST_INTERVAL).await;
+        }
+
+        *last_request = Some(Instant::now());
+    }
+
+    /// Send a GET request, `None` if the entity does not exist
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> TrackMetadataRepositoryResult<Option<T>> {
+        let url = Url::parse_with_params(
+            &format!("{}{}", self.api_url, path),
+            params.iter().chain([("fmt", "json")].iter()),
+        )
+        .map_err(|err| TrackMetadataRepositoryError::ServiceError(err.to_string()))?;
+
+        self.wait_rate_limit().await;
+
+        let response = self.http_client.get(url).send().await.map_err(|err| {
+            TrackMetadataRepositoryError::ServiceError(format!(
+                "TrackMetadataRepository - Failed to send request - {:?}",
+                err
+            ))
+        })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        response
+            .error_for_status()
+            .map_err(|err| {
+                TrackMetadataRepositoryError::ServiceError(format!(
+                    "TrackMetadataRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<T>()
+            .await
+            .map(Some)
+            .map_err(|err| {
+                TrackMetadataRepositoryError::ServiceError(format!(
+                    "TrackMetadataRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })
+    }
+}
+
+/// Lucene search query (https://musicbrainz.org/doc/MusicBrainz_API/Search#Recording)
+fn search_query(track: &TrackWithAlbumAndArtists) -> String {
+    let escape = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut query = format!("recording:\"{}\"", escape(track.name()));
+
+    if let Some(artist) = track.artists().first() {
+        query.push_str(&format!(" AND artist:\"{}\"", escape(artist.name())));
+    }
+
+    if track.duration_ms() > 0 {
+        query.push_str(&format!(
+            " AND dur:[{} TO {}]",
+            track.duration_ms().saturating_sub(DURATION_TOLERANCE_MS),
+            track.duration_ms().saturating_add(DURATION_TOLERANCE_MS)
+        ));
+    }
+
+    query
+}
+
+impl TrackMetadataRepository for MusicBrainzRepository {
+    async fn find_recording(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        let Some(search) = self
+            .get::<MusicBrainzRecordingSearch>(
+                "/recording",
+                &[("query", &search_query(track)), ("limit", SEARCH_LIMIT)],
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Search results hold neither the barcodes of releases nor all the ISRCs
+        let Some(recording_id) = search
+            .recordings
+            .into_iter()
+            .map(|recording| {
+                (
+                    recording.id.clone(),
+                    recording.into_track(track.album().name()),
+                )
+            })
+            .find(|(_, candidate)| is_same_recording(track, candidate))
+            .map(|(id, _)| id)
+        else {
+            return Ok(None);
+        };
+
+        let recording = self
+            .get::<MusicBrainzRecording>(
+                &format!("/recording/{}", recording_id),
+                &[("inc", "isrcs+releases+artist-credits")],
+            )
+            .await?;
+
+        Ok(recording.map(|recording| recording.into_track(track.album().name())))
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    services::track_matcher::normalize,
+    value_objects::{isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId},
+};
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzArtist {
+    /// The artist MBID
+    pub id: String,
+    /// The artist name
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzArtistCredit {
+    /// The name credited on the recording, may differ from the artist name
+    pub name: String,
+    pub artist: MusicBrainzArtist,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzRelease {
+    /// The release MBID
+    pub id: String,
+    /// The release title
+    pub title: String,
+    /// Official, Promotion, Bootleg...
+    pub status: Option<String>,
+    /// Release date, as YYYY, YYYY-MM or YYYY-MM-DD
+    pub date: Option<String>,
+    /// UPC or EAN of the release, only returned by lookups
+    pub barcode: Option<String>,
+}
+
+impl MusicBrainzRelease {
+    /// UPC (12 digits) or EAN (13 digits) of the release, `None` when not a valid barcode
+    pub fn product_id(&self) -> Option<ProductId> {
+        ProductId::barcode(self.barcode.as_deref()?.trim()).ok()
+    }
+
+    fn release_date(&self) -> DateTime<Utc> {
+        let Some(date) = self.date.as_deref() else {
+            return DateTime::<Utc>::default();
+        };
+
+        // Missing month & day default to the first one
+        let date = match date.len() {
+            4 => format!("{}-01-01", date),
+            7 => format!("{}-01", date),
+            _ => date.to_string(),
+        };
+
+        NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MusicBrainzRecording {
+    /// The recording MBID
+    pub id: String,
+    /// The recording title
+    pub title: String,
+    /// Duration in milliseconds
+    pub length: Option<u32>,
+    /// Relevance of a search result, from 0 to 100
+    pub score: Option<u8>,
+    #[serde(default)]
+    pub isrcs: Vec<String>,
+    #[serde(default)]
+    pub artist_credit: Vec<MusicBrainzArtistCredit>,
+    #[serde(default)]
+    pub releases: Vec<MusicBrainzRelease>,
+}
+
+impl MusicBrainzRecording {
+    /// Release the track most likely comes from: same album title first,
+    /// then official releases having a barcode
+    fn release(&self, album_name: &str) -> Option<&MusicBrainzRelease> {
+        let album_name = normalize(album_name);
+
+        self.releases
+            .iter()
+            .filter(|release| release.product_id().is_some())
+            .max_by_key(|release| {
+                (
+                    !album_name.is_empty() && normalize(&release.title) == album_name,
+                    release.status.as_deref() == Some("Official"),
+                )
+            })
+            .or(self.releases.first())
+    }
+
+    /// Track holding the ids of the recording, and of the release matching `album_name`
+    pub fn into_track(self, album_name: &str) -> TrackWithAlbumAndArtists {
+        let provider_id = ProviderId::MUSICBRAINZ;
+
+        let album = match self.release(album_name) {
+            Some(release) => {
+                let mut ids = HashSet::from_iter([ProductId::Provider((
+                    provider_id.clone(),
+                    release.id.clone(),
+                ))]);
+
+                ids.extend(release.product_id());
+
+                Album::new(
+                    ids,
+                    release.title.clone(),
+                    release.release_date(),
+                    HashSet::new(),
+                    release_url(&release.id)
+                        .map(|url| HashMap::from_iter([(provider_id.clone(), url)]))
+                        .unwrap_or_default(),
+                )
+            }
+            None => Album::new(
+                HashSet::new(),
+                String::new(),
+                DateTime::<Utc>::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+        };
+
+        let artists = self
+            .artist_credit
+            .into_iter()
+            .map(|credit| {
+                Artist::new(
+                    HashMap::from_iter([(provider_id.clone(), credit.artist.id)]),
+                    credit.name,
+                    HashMap::new(),
+                )
+            })
+            .collect();
+
+        let mut ids =
+            HashSet::from_iter([ProductId::Provider((provider_id.clone(), self.id.clone()))]);
+
+        ids.extend(
+            self.isrcs
+                .into_iter()
+                .filter_map(|isrc| Isrc::parse(&isrc).ok())
+                .map(ProductId::ISRC),
+        );
+
+        TrackWithAlbumAndArtists::new(
+            ids,
+            self.title,
+            self.length.unwrap_or_default(),
+            format!("https://musicbrainz.org/recording/{}", self.id)
+                .parse::<Url>()
+                .map(|url| HashMap::from_iter([(provider_id, url)]))
+                .unwrap_or_default(),
+            album,
+            artists,
+        )
+    }
+}
+
+fn release_url(id: &str) -> Option<Url> {
+    format!("https://musicbrainz.org/release/{}", id)
+        .parse::<Url>()
+        .ok()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MusicBrainzRecordingSearch {
+    pub count: u32,
+    pub recordings: Vec<MusicBrainzRecording>,
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::value_objects::{barcode::Upc, isrc::Isrc, product_id::ProductId};
+
+    use super::{MusicBrainzRecording, MusicBrainzRecordingSearch};
+
+    #[test]
+    fn test_deserialize_search() {
+        let json_str = include_str!("../../tests/musicbrainz/payload_search.json");
+        let json =
+            serde_json::from_str::<MusicBrainzRecordingSearch>(json_str).expect("valid json");
+
+        assert_eq!(json.recordings.len(), 2);
+        assert_eq!(json.recordings[0].score, Some(100));
+        assert_eq!(json.recordings[0].artist_credit[0].name, "NewJeans");
+    }
+
+    #[test]
+    fn test_deserialize_recording() {
+        let json_str = include_str!("../../tests/musicbrainz/payload_recording.json");
+        let json = serde_json::from_str::<MusicBrainzRecording>(json_str).expect("valid json");
+
+        let track = json.into_track("How Sweet");
+
+        assert!(track
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert!(track
+            .album()
+            .ids()
+            .contains(&ProductId::UPC(Upc::parse("196922995354").unwrap())));
+        assert_eq!(track.duration_ms(), 219_533);
+    }
+}
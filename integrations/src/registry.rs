@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use snk_core::{
+    entities::{
+        music_account_provider::MusicAccountProvider, provider_credentials::ProviderCredentials,
+    },
+    impl_dyn_account_profile_repository, impl_dyn_playlist_repository,
+    impl_dyn_track_search_repository,
+    services::provider_registry::{
+        ProviderRegistry, ProviderRegistryError, ProviderRegistryResult, ProviderRepositories,
+    },
+    value_objects::provider::provider_id::ProviderId,
+};
+
+use crate::{
+    deezer::DeezerPlaylistRepository, lastfm::LastfmPlaylistRepository,
+    soundcloud::SoundcloudPlaylistRepository, spotify::SpotifyPlaylistRepository,
+    tidal::TidalPlaylistRepository, youtube::YoutubePlaylistRepository,
+};
+
+impl_dyn_playlist_repository!(DeezerPlaylistRepository<'_>);
+impl_dyn_playlist_repository!(LastfmPlaylistRepository<'_>);
+impl_dyn_playlist_repository!(SoundcloudPlaylistRepository<'_>);
+impl_dyn_playlist_repository!(SpotifyPlaylistRepository<'_>);
+impl_dyn_playlist_repository!(TidalPlaylistRepository<'_>);
+impl_dyn_playlist_repository!(YoutubePlaylistRepository<'_>);
+
+impl_dyn_track_search_repository!(DeezerPlaylistRepository<'_>);
+impl_dyn_track_search_repository!(SpotifyPlaylistRepository<'_>);
+
+impl_dyn_account_profile_repository!(DeezerPlaylistRepository<'_>);
+impl_dyn_account_profile_repository!(SpotifyPlaylistRepository<'_>);
+
+/// Registry of the providers accessed with the credentials of a user account
+pub fn provider_registry() -> ProviderRegistry {
+    let mut registry = ProviderRegistry::new();
+
+    registry
+        .register(ProviderId::DEEZER, deezer)
+        .register(ProviderId::LASTFM, lastfm)
+        .register(ProviderId::SOUNDCLOUD, soundcloud)
+        .register(ProviderId::SPOTIFY, spotify)
+        .register(ProviderId::TIDAL, tidal)
+        .register(ProviderId::YOUTUBE, youtube);
+
+    registry
+}
+
+fn service_error(err: &'static str) -> ProviderRegistryError {
+    ProviderRegistryError::ServiceError(err.to_string())
+}
+
+fn account_id(credentials: &ProviderCredentials) -> ProviderRegistryResult<String> {
+    credentials
+        .account_id()
+        .cloned()
+        .ok_or(ProviderRegistryError::MissingCredentials(format!(
+            "account id is required by {}",
+            credentials.provider_id().value()
+        )))
+}
+
+fn country_code(credentials: &ProviderCredentials) -> ProviderRegistryResult<String> {
+    credentials
+        .country_code()
+        .cloned()
+        .ok_or(ProviderRegistryError::MissingCredentials(format!(
+            "country code is required by {}",
+            credentials.provider_id().value()
+        )))
+}
+
+fn deezer<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    let repository = Arc::new(
+        DeezerPlaylistRepository::new(provider, credentials.access_token().clone())
+            .map_err(service_error)?,
+    );
+
+    Ok(ProviderRepositories {
+        playlists: repository.clone(),
+        track_search: Some(repository.clone()),
+        profile: Some(repository),
+    })
+}
+
+fn lastfm<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    Ok(ProviderRepositories {
+        playlists: Arc::new(
+            LastfmPlaylistRepository::new(
+                provider,
+                credentials.access_token().clone(),
+                account_id(credentials)?,
+            )
+            .map_err(service_error)?,
+        ),
+        track_search: None,
+        profile: None,
+    })
+}
+
+fn soundcloud<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    Ok(ProviderRepositories {
+        playlists: Arc::new(
+            SoundcloudPlaylistRepository::new(provider, credentials.access_token().clone())
+                .map_err(service_error)?,
+        ),
+        track_search: None,
+        profile: None,
+    })
+}
+
+fn spotify<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    let repository = Arc::new(
+        SpotifyPlaylistRepository::new(
+            provider,
+            account_id(credentials)?,
+            credentials.access_token().clone(),
+        )
+        .map_err(service_error)?
+        .with_market(credentials.country_code().cloned()),
+    );
+
+    Ok(ProviderRepositories {
+        playlists: repository.clone(),
+        track_search: Some(repository.clone()),
+        profile: Some(repository),
+    })
+}
+
+fn tidal<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    Ok(ProviderRepositories {
+        playlists: Arc::new(
+            TidalPlaylistRepository::new(
+                provider,
+                account_id(credentials)?,
+                country_code(credentials)?,
+                credentials.access_token().clone(),
+            )
+            .map_err(service_error)?,
+        ),
+        track_search: None,
+        profile: None,
+    })
+}
+
+fn youtube<'a>(
+    provider: &'a MusicAccountProvider,
+    credentials: &ProviderCredentials,
+) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+    Ok(ProviderRepositories {
+        playlists: Arc::new(
+            YoutubePlaylistRepository::new(provider, credentials.access_token().clone())
+                .map_err(service_error)?,
+        ),
+        track_search: None,
+        profile: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::{
+            music_account_provider::MusicAccountProvider, provider_credentials::ProviderCredentials,
+        },
+        services::provider_registry::ProviderRegistryError,
+        value_objects::provider::provider_id::ProviderId,
+    };
+
+    use super::provider_registry;
+
+    fn provider(id: &str) -> MusicAccountProvider {
+        MusicAccountProvider::new(
+            ProviderId::new(id.to_string()),
+            id.to_string(),
+            "#000000".to_string(),
+            "https://example.com/auth".parse().unwrap(),
+            "https://example.com/token".parse().unwrap(),
+            vec![],
+        )
+    }
+
+    fn credentials(
+        id: &str,
+        account_id: Option<&str>,
+        country_code: Option<&str>,
+    ) -> ProviderCredentials {
+        ProviderCredentials::new(
+            ProviderId::new(id.to_string()),
+            "token".to_string(),
+            account_id.map(str::to_string),
+            country_code.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_provider_registry() {
+        let registry = provider_registry();
+
+        let deezer = provider("deezer");
+        let repositories = registry
+            .repositories(&deezer, &credentials("deezer", None, None))
+            .expect("deezer repositories");
+
+        assert!(repositories.track_search.is_some());
+
+        let spotify = provider("spotify");
+
+        assert!(matches!(
+            registry.repositories(&spotify, &credentials("spotify", None, None)),
+            Err(ProviderRegistryError::MissingCredentials(_))
+        ));
+        assert!(registry
+            .repositories(&spotify, &credentials("spotify", Some("me"), None))
+            .is_ok());
+
+        let tidal = provider("tidal");
+
+        assert!(matches!(
+            registry.repositories(&tidal, &credentials("tidal", None, Some("FR"))),
+            Err(ProviderRegistryError::MissingCredentials(_))
+        ));
+        assert!(matches!(
+            registry.repositories(&tidal, &credentials("tidal", Some("42"), None)),
+            Err(ProviderRegistryError::MissingCredentials(_))
+        ));
+        assert!(registry
+            .repositories(&tidal, &credentials("tidal", Some("42"), Some("FR")))
+            .is_ok());
+
+        let unknown = provider("napster");
+
+        assert!(matches!(
+            registry.repositories(&unknown, &credentials("napster", None, None)),
+            Err(ProviderRegistryError::UnknownProvider(_))
+        ));
+    }
+}
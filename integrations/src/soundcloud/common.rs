@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snk_core::value_objects::image_cover::ImageCover;
+use url::Url;
+
+/// Page of a list endpoint requested with `linked_partitioning=true`
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudCollection<T> {
+    /// Items of the current page
+    pub collection: Vec<T>,
+    /// Absolute url of the next page, absent on the last one
+    pub next_href: Option<Url>,
+}
+
+/// Parse SoundCloud dates (ex: `2024/05/24 10:00:00 +0000`)
+pub fn parse_date(value: &str) -> Result<DateTime<Utc>, &'static str> {
+    DateTime::parse_from_str(value, "%Y/%m/%d %H:%M:%S %z")
+        .map(|date| date.to_utc())
+        .map_err(|_| "created_at is corrupted")
+}
+
+/// Artwork urls point to the "large" (100x100) version,
+/// other sizes are available by replacing the size suffix
+pub fn artwork_covers(artwork_url: &Url) -> HashSet<ImageCover> {
+    let resize = |size: &str| {
+        artwork_url
+            .as_str()
+            .replace("-large.", &format!("-{}.", size))
+            .parse::<Url>()
+            .ok()
+    };
+
+    let mut covers = HashSet::new();
+
+    covers.insert(ImageCover::Md(artwork_url.clone()));
+
+    if let Some(url) = resize("t500x500") {
+        covers.insert(ImageCover::Default(url.clone()));
+        covers.insert(ImageCover::Lg(url));
+    }
+    if let Some(url) = resize("small") {
+        covers.insert(ImageCover::Sm(url));
+    }
+    if let Some(url) = resize("original") {
+        covers.insert(ImageCover::Other(url));
+    }
+
+    covers
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::value_objects::image_cover::ImageCover;
+
+    use super::{artwork_covers, parse_date};
+
+    #[test]
+    fn test_parse_date() {
+        let date = parse_date("2024/05/24 10:00:00 +0000").expect("valid date");
+
+        assert_eq!(date.to_rfc3339(), "2024-05-24T10:00:00+00:00");
+        assert!(parse_date("2024-05-24").is_err());
+    }
+
+    #[test]
+    fn test_artwork_covers() {
+        let covers = artwork_covers(
+            &"https://i1.sndcdn.com/artworks-000123-abcdef-large.jpg"
+                .parse()
+                .unwrap(),
+        );
+
+        assert!(covers.contains(&ImageCover::Lg(
+            "https://i1.sndcdn.com/artworks-000123-abcdef-t500x500.jpg"
+                .parse()
+                .unwrap()
+        )));
+    }
+}
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudError {
+    /// HTTP status of the error
+    pub code: u16,
+    /// Human readable description of the error
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SoundcloudError;
+
+    #[test]
+    fn test_deserialize_error() {
+        let json_str = "{\"code\":404,\"message\":\"404 - Not Found\",\"link\":\"https://developers.soundcloud.com/docs/api/explorer/open-api\",\"status\":\"404 - Not Found\",\"errors\":[{\"error_message\":\"404 - Not Found\"}],\"error\":null}";
+        let json = serde_json::from_str::<SoundcloudError>(json_str).expect("valid json");
+
+        assert_eq!(json.code, 404);
+        assert_eq!(json.message, Some("404 - Not Found".to_string()));
+    }
+}
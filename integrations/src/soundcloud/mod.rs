@@ -0,0 +1,420 @@
+pub mod common;
+pub mod error;
+pub mod playlist;
+pub mod track;
+pub mod user;
+
+use std::time::Duration;
+
+use common::SoundcloudCollection;
+use error::SoundcloudError;
+use playlist::SoundcloudPlaylist;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, RequestBuilder, Response, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+};
+use track::SoundcloudTrack;
+use url::Url;
+use user::SoundcloudMe;
+
+use crate::chunks::send_chunks;
+
+static API_URL: &str = "https://api.soundcloud.com";
+
+/// Maximum page size accepted by the SoundCloud API
+static PAGE_LIMIT: &str = "200";
+
+pub struct SoundcloudPlaylistRepository<'a> {
+    http_client: Client,
+    #[allow(dead_code)]
+    music_account_provider: &'a MusicAccountProvider,
+}
+
+impl<'a> SoundcloudPlaylistRepository<'a> {
+    pub fn new(
+        music_account_provider: &'a MusicAccountProvider,
+        access_token: String,
+    ) -> Result<Self, &'static str> {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+        default_headers.insert(
+            "Authorization",
+            format!("OAuth {}", access_token).parse().map_err(|err| {
+                eprintln!("{:?}", err);
+                "SoundcloudPlaylistRepository::new: Could not parse header value"
+            })?,
+        );
+
+        Ok(Self {
+            http_client: Client::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .default_headers(default_headers)
+                .build()
+                .map_err(|err| {
+                    eprintln!("{:?}", err);
+                    "SoundcloudPlaylistRepository::new: Could not init HTTP client"
+                })?,
+            music_account_provider,
+        })
+    }
+
+    async fn send_unchecked(&self, request: RequestBuilder) -> PlaylistRepositoryResult<Response> {
+        request.send().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to send request - {:?}",
+                err
+            ))
+        })
+    }
+
+    /// Turn SoundCloud error payloads into [`PlaylistRepositoryError`]
+    async fn check(response: Response) -> PlaylistRepositoryResult<Response> {
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let message = match response.json::<SoundcloudError>().await {
+            Ok(SoundcloudError {
+                message: Some(message),
+                ..
+            }) => message,
+            _ => status.to_string(),
+        };
+
+        Err(PlaylistRepositoryError::ServiceError(format!(
+            "PlaylistRepository - Error during request - {} ({})",
+            message, status
+        )))
+    }
+
+    async fn send(&self, request: RequestBuilder) -> PlaylistRepositoryResult<Response> {
+        Self::check(self.send_unchecked(request).await?).await
+    }
+
+    async fn parse<T: DeserializeOwned>(response: Response) -> PlaylistRepositoryResult<T> {
+        response.json::<T>().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to parse response - {:?}",
+                err
+            ))
+        })
+    }
+
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> PlaylistRepositoryResult<T> {
+        Self::parse(self.send(request).await?).await
+    }
+
+    /// Fetch every page of a list endpoint by following `next_href`
+    async fn fetch_all<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        query: &[(&str, &str)],
+    ) -> PlaylistRepositoryResult<Vec<T>> {
+        let mut items = vec![];
+        let mut page = self
+            .fetch::<SoundcloudCollection<T>>(
+                self.http_client
+                    .get(format!("{}/{}", API_URL, endpoint))
+                    .query(query)
+                    .query(&[("linked_partitioning", "true"), ("limit", PAGE_LIMIT)]),
+            )
+            .await?;
+
+        loop {
+            items.extend(page.collection);
+
+            let Some(next_href) = page.next_href else {
+                break;
+            };
+
+            // next_href already contains every query parameter
+            page = self.fetch(self.http_client.get(next_href)).await?;
+        }
+
+        Ok(items)
+    }
+
+    async fn get_playlist(&self, id: &str) -> PlaylistRepositoryResult<Option<SoundcloudPlaylist>> {
+        let response = self
+            .send_unchecked(
+                self.http_client
+                    .get(format!("{}/playlists/{}", API_URL, id)),
+            )
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::parse(Self::check(response).await?).await?))
+    }
+
+    /// Playlists tracks can only be updated by replacing the whole list
+    async fn set_playlist_tracks(
+        &self,
+        id: &str,
+        track_ids: &[String],
+    ) -> PlaylistRepositoryResult<()> {
+        let tracks = track_ids
+            .iter()
+            .map(|track_id| json!({ "id": track_id }))
+            .collect::<Vec<_>>();
+
+        self.send(
+            self.http_client
+                .put(format!("{}/playlists/{}", API_URL, id))
+                .json(&json!({
+                    "playlist": {
+                        "tracks": tracks,
+                    },
+                })),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_playlist_track_ids(&self, id: &str) -> PlaylistRepositoryResult<Vec<String>> {
+        let Some(playlist) = self.get_playlist(id).await? else {
+            return Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist {} not found",
+                id
+            )));
+        };
+
+        Ok(playlist
+            .tracks
+            .into_iter()
+            .map(|track| track.id.to_string())
+            .collect())
+    }
+}
+
+/// Body of playlist creation & update requests, custom covers aren't supported
+fn playlist_details(spec: &PlaylistSpec) -> Value {
+    let mut playlist = json!({ "title": spec.name });
+
+    if let Some(description) = &spec.description {
+        playlist["description"] = json!(description);
+    }
+    if let Some(public) = spec.public {
+        playlist["sharing"] = json!(if public { "public" } else { "private" });
+    }
+
+    json!({ "playlist": playlist })
+}
+
+impl PlaylistRepository for SoundcloudPlaylistRepository<'_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => {
+                let me = self
+                    .fetch::<SoundcloudMe>(self.http_client.get(format!("{}/me", API_URL)))
+                    .await?;
+
+                Ok(Some(Playlist::new(
+                    id.clone(),
+                    id.to_string(),
+                    [ImageCover::Other(
+                        "https://cdn.icon-icons.com/icons2/72/PNG/256/favourite_14390.png"
+                            .parse::<Url>()
+                            .map_err(|err| {
+                                PlaylistRepositoryError::ServiceError(err.to_string())
+                            })?,
+                    )]
+                    .into(),
+                    "me".to_string(),
+                    me.public_favorites_count,
+                    "https://soundcloud.com/you/likes"
+                        .parse::<Url>()
+                        .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?,
+                )))
+            }
+            PlaylistId::Owned(soundcloud_id) => {
+                Ok(self.get_playlist(soundcloud_id).await?.map(Into::into))
+            }
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let playlists = self
+            .fetch_all::<SoundcloudPlaylist>("me/playlists", &[("show_tracks", "false")])
+            .await?;
+
+        Ok(playlists.into_iter().map(Into::into).collect())
+    }
+
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let mut details = playlist_details(spec);
+        if spec.public.is_none() {
+            details["playlist"]["sharing"] = json!("private");
+        }
+
+        let playlist = self
+            .fetch::<SoundcloudPlaylist>(
+                self.http_client
+                    .post(format!("{}/playlists", API_URL))
+                    .json(&details),
+            )
+            .await?;
+
+        Ok(playlist.into())
+    }
+
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        match id {
+            PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            )),
+            PlaylistId::Owned(soundcloud_id) => {
+                self.send(
+                    self.http_client
+                        .put(format!("{}/playlists/{}", API_URL, soundcloud_id))
+                        .json(&playlist_details(spec)),
+                )
+                .await?;
+
+                Ok(())
+            }
+        }
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            )),
+            PlaylistId::Owned(soundcloud_id) => {
+                let Some(playlist) = self.get_playlist(soundcloud_id).await? else {
+                    return Ok(None);
+                };
+
+                self.send(
+                    self.http_client
+                        .delete(format!("{}/playlists/{}", API_URL, soundcloud_id)),
+                )
+                .await?;
+
+                Ok(Some(playlist.into()))
+            }
+        }
+    }
+
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        match playlist_id {
+            PlaylistId::LikedSongs => {
+                send_chunks(ids.chunks(1).collect(), |chunk| async move {
+                    self.send(
+                        self.http_client
+                            .post(format!("{}/likes/tracks/{}", API_URL, chunk[0])),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+            PlaylistId::Owned(soundcloud_id) => {
+                let mut track_ids = self.get_playlist_track_ids(soundcloud_id).await?;
+
+                track_ids.extend(ids.iter().cloned());
+
+                // The whole list of tracks is sent at once
+                send_chunks(vec![ids], |_| {
+                    self.set_playlist_tracks(soundcloud_id, &track_ids)
+                })
+                .await
+            }
+        }
+    }
+
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        match playlist_id {
+            PlaylistId::LikedSongs => {
+                send_chunks(ids.chunks(1).collect(), |chunk| async move {
+                    self.send(
+                        self.http_client
+                            .delete(format!("{}/likes/tracks/{}", API_URL, chunk[0])),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+            PlaylistId::Owned(soundcloud_id) => {
+                let track_ids = self
+                    .get_playlist_track_ids(soundcloud_id)
+                    .await?
+                    .into_iter()
+                    .filter(|track_id| !ids.contains(track_id))
+                    .collect::<Vec<_>>();
+
+                // The whole list of tracks is sent at once
+                send_chunks(vec![ids], |_| {
+                    self.set_playlist_tracks(soundcloud_id, &track_ids)
+                })
+                .await
+            }
+        }
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let tracks = match playlist_id {
+            PlaylistId::LikedSongs => {
+                self.fetch_all::<SoundcloudTrack>("me/likes/tracks", &[])
+                    .await?
+            }
+            PlaylistId::Owned(soundcloud_id) => {
+                let Some(playlist) = self.get_playlist(soundcloud_id).await? else {
+                    return Err(PlaylistRepositoryError::ServiceError(format!(
+                        "PlaylistRepository - Playlist {} not found",
+                        soundcloud_id
+                    )));
+                };
+
+                playlist.tracks
+            }
+        };
+
+        tracks
+            .into_iter()
+            .map(TrackWithAlbumAndArtists::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err: &'static str| PlaylistRepositoryError::ServiceError(err.to_string()))
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Deserialize;
+use snk_core::{entities::playlist::Playlist, value_objects::playlist_id::PlaylistId};
+use url::Url;
+
+use super::{common::artwork_covers, track::SoundcloudTrack, user::SoundcloudUser};
+
+/// A SoundCloud playlist, also called "set"
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudPlaylist {
+    /// The SoundCloud id of the playlist
+    pub id: u64,
+    /// The title of the playlist
+    pub title: String,
+    /// The description of the playlist
+    pub description: Option<String>,
+    /// The playlist's duration (milliseconds)
+    #[allow(dead_code)]
+    pub duration: u64,
+    /// The visibility of the playlist => "public" | "private"
+    pub sharing: String,
+    /// Number of tracks in the playlist
+    pub track_count: u32,
+    /// The url of the playlist on SoundCloud
+    pub permalink_url: Url,
+    /// The url of the playlist artwork ("large" size)
+    pub artwork_url: Option<Url>,
+    /// The creator of the playlist
+    pub user: SoundcloudUser,
+    /// The tracks of the playlist, omitted when requested with `show_tracks=false`
+    #[serde(default)]
+    pub tracks: Vec<SoundcloudTrack>,
+}
+
+impl From<SoundcloudPlaylist> for Playlist {
+    fn from(soundcloud_playlist: SoundcloudPlaylist) -> Self {
+        let playlist_id = PlaylistId::Owned(soundcloud_playlist.id.to_string());
+        let covers = soundcloud_playlist
+            .artwork_url
+            .as_ref()
+            .map(artwork_covers)
+            .unwrap_or_default();
+
+        let mut playlist = Playlist::new(
+            playlist_id,
+            soundcloud_playlist.title,
+            covers,
+            soundcloud_playlist.user.username,
+            soundcloud_playlist.track_count,
+            soundcloud_playlist.permalink_url,
+        );
+
+        playlist.set_description(soundcloud_playlist.description);
+        playlist.set_public(Some(soundcloud_playlist.sharing == "public"));
+
+        playlist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{entities::playlist::Playlist, value_objects::playlist_id::PlaylistId};
+
+    use crate::soundcloud::common::SoundcloudCollection;
+
+    use super::SoundcloudPlaylist;
+
+    #[test]
+    fn test_deserialize_playlist() {
+        let payload = include_str!("../../tests/soundcloud/payload_playlist.json");
+        let json = serde_json::from_str::<SoundcloudPlaylist>(payload).expect("valid json");
+
+        assert_eq!(json.title, "Women of Rap");
+        assert_eq!(json.tracks.len(), 2);
+
+        let playlist: Playlist = json.into();
+
+        assert!(matches!(playlist.id(), PlaylistId::Owned(id) if id == "1846385471"));
+        assert_eq!(playlist.total_songs(), 2);
+        assert_eq!(
+            playlist.description(),
+            Some(&"The best of women in rap".to_string())
+        );
+        assert_eq!(playlist.public(), Some(false));
+    }
+
+    #[test]
+    fn test_deserialize_playlists_page() {
+        let payload = include_str!("../../tests/soundcloud/payload_playlists.json");
+        let json = serde_json::from_str::<SoundcloudCollection<SoundcloudPlaylist>>(payload)
+            .expect("valid json");
+
+        assert_eq!(json.collection.len(), 1);
+        assert!(json.collection[0].tracks.is_empty());
+        assert!(json.next_href.is_some());
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId},
+};
+use url::Url;
+
+use super::{
+    common::{artwork_covers, parse_date},
+    user::SoundcloudUser,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudPublisherMetadata {
+    /// The performing artist(s) declared by the publisher
+    pub artist: Option<String>,
+    /// The album on which the track appears
+    pub album_title: Option<String>,
+    /// The track ISRC
+    pub isrc: Option<String>,
+    /// The release UPC or EAN
+    pub upc_or_ean: Option<String>,
+    /// Whether the track contains explicit lyrics
+    #[allow(dead_code)]
+    pub explicit: Option<bool>,
+    /// Whether the track is music (and not a podcast, a sound...)
+    #[allow(dead_code)]
+    pub contains_music: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudTrack {
+    /// The SoundCloud id of the track
+    pub id: u64,
+    /// The title of the track
+    pub title: String,
+    /// The track's duration (milliseconds)
+    pub duration: u32,
+    /// The url of the track on SoundCloud
+    pub permalink_url: Url,
+    /// The url of the track artwork ("large" size), null when the uploader avatar is used
+    pub artwork_url: Option<Url>,
+    /// The date of upload of the track (ex: 2024/05/24 10:00:00 +0000)
+    pub created_at: String,
+    /// The release date of the track, when declared by the uploader
+    pub release_year: Option<i32>,
+    pub release_month: Option<u32>,
+    pub release_day: Option<u32>,
+    /// The genre of the track
+    #[allow(dead_code)]
+    pub genre: Option<String>,
+    /// Access level of the current user => "playable" | "preview" | "blocked"
+    #[allow(dead_code)]
+    pub access: Option<String>,
+    /// The uploader of the track
+    pub user: SoundcloudUser,
+    /// Metadata declared by the publisher (labels, distributors)
+    pub publisher_metadata: Option<SoundcloudPublisherMetadata>,
+}
+
+impl TryFrom<SoundcloudTrack> for TrackWithAlbumAndArtists {
+    type Error = &'static str;
+
+    fn try_from(soundcloud_track: SoundcloudTrack) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::SOUNDCLOUD;
+        let publisher_metadata = soundcloud_track.publisher_metadata;
+
+        let mut ids = HashSet::new();
+
+        ids.insert(ProductId::Provider((
+            provider_id.clone(),
+            soundcloud_track.id.to_string(),
+        )));
+
+        if let Some(isrc) = publisher_metadata
+            .as_ref()
+            .and_then(|metadata| metadata.isrc.as_ref())
+            .and_then(|isrc| Isrc::parse(isrc).ok())
+        {
+            ids.insert(ProductId::ISRC(isrc));
+        }
+
+        let mut urls = HashMap::new();
+
+        urls.insert(provider_id.clone(), soundcloud_track.permalink_url);
+
+        // Album
+
+        let mut album_ids = HashSet::new();
+
+        album_ids.extend(
+            publisher_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.upc_or_ean.as_ref())
+                .and_then(|barcode| ProductId::barcode(barcode).ok()),
+        );
+
+        let release_date = match (
+            soundcloud_track.release_year,
+            soundcloud_track.release_month,
+            soundcloud_track.release_day,
+        ) {
+            (Some(year), month, day) => {
+                NaiveDate::from_ymd_opt(year, month.unwrap_or(1).max(1), day.unwrap_or(1).max(1))
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|date| date.and_utc())
+            }
+            _ => None,
+        };
+        let release_date = match release_date {
+            Some(release_date) => release_date,
+            None => parse_date(&soundcloud_track.created_at)?,
+        };
+
+        let covers = soundcloud_track
+            .artwork_url
+            .as_ref()
+            .map(artwork_covers)
+            .unwrap_or_default();
+
+        let album = Album::new(
+            album_ids,
+            publisher_metadata
+                .as_ref()
+                .and_then(|metadata| metadata.album_title.clone())
+                .unwrap_or_default(),
+            release_date,
+            covers,
+            HashMap::new(),
+        );
+
+        // Artists: declared by the publisher, otherwise the uploader
+
+        let uploader = soundcloud_track.user;
+        let artists = match publisher_metadata.and_then(|metadata| metadata.artist) {
+            Some(artist) if !artist.is_empty() => artist
+                .split([',', '&'])
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    let mut artist_ids = HashMap::new();
+                    let mut artist_urls = HashMap::new();
+
+                    if name == uploader.username {
+                        artist_ids.insert(provider_id.clone(), uploader.id.to_string());
+                        artist_urls.insert(provider_id.clone(), uploader.permalink_url.clone());
+                    }
+
+                    Artist::new(artist_ids, name.to_string(), artist_urls)
+                })
+                .collect(),
+            _ => vec![Artist::new(
+                HashMap::from_iter([(provider_id.clone(), uploader.id.to_string())]),
+                uploader.username,
+                HashMap::from_iter([(provider_id, uploader.permalink_url)]),
+            )],
+        };
+
+        Ok(TrackWithAlbumAndArtists::new(
+            ids,
+            soundcloud_track.title,
+            soundcloud_track.duration,
+            urls,
+            album,
+            artists,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::track::TrackWithAlbumAndArtists,
+        value_objects::{barcode::Upc, isrc::Isrc, product_id::ProductId},
+    };
+
+    use super::SoundcloudTrack;
+
+    #[test]
+    fn test_deserialize_track() {
+        let payload = include_str!("../../tests/soundcloud/payload_track.json");
+        let json = serde_json::from_str::<SoundcloudTrack>(payload).expect("valid json");
+
+        assert_eq!(json.title, "How Sweet");
+        assert_eq!(json.user.username, "NewJeans");
+
+        let track = TrackWithAlbumAndArtists::try_from(json).expect("valid track");
+
+        assert!(track
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert!(track
+            .album()
+            .ids()
+            .contains(&ProductId::UPC(Upc::parse("196922889738").unwrap())));
+        assert_eq!(track.artists()[0].name(), "NewJeans");
+        assert_eq!(track.duration_ms(), 219_000);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudUser {
+    /// The SoundCloud id of the user
+    pub id: u64,
+    /// The name displayed on the user's profile
+    pub username: String,
+    /// The url of the user's profile
+    pub permalink_url: Url,
+    /// The url of the user's avatar
+    #[allow(dead_code)]
+    pub avatar_url: Option<Url>,
+}
+
+/// Current user, as returned by `/me`
+#[derive(Debug, Deserialize)]
+pub struct SoundcloudMe {
+    /// The SoundCloud id of the user
+    #[allow(dead_code)]
+    pub id: u64,
+    /// Number of tracks liked by the user
+    pub public_favorites_count: u32,
+}
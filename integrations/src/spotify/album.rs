@@ -2,7 +2,7 @@ use std::{collections::HashSet, str::FromStr};
 
 use serde::Deserialize;
 use snk_core::{
-    entities::album::Album,
+    entities::album::{Album, AlbumWithArtists},
     value_objects::{
         image_cover::ImageCover, product_id::ProductId, provider::provider_id::ProviderId,
     },
@@ -52,7 +52,9 @@ pub struct SpotifyAlbum {
     pub total_tracks: u32,
     /// The markets in which the album is available: ISO 3166-1 alpha-2 country codes.
     /// NOTE: an album is considered available in a market when at least 1 of its tracks is available in that market.
+    /// Omitted when a market is given.
     #[allow(dead_code)]
+    #[serde(default)]
     pub available_markets: Vec<String>,
     /// Known external URLs for this album.
     #[allow(dead_code)]
@@ -99,6 +101,55 @@ pub struct SpotifyAlbum {
     pub popularity: u32,
 }
 
+impl From<SpotifyAlbum> for AlbumWithArtists {
+    fn from(spotify_album: SpotifyAlbum) -> Self {
+        let upc = spotify_album
+            .external_ids
+            .upc
+            .and_then(|upc| ProductId::barcode(&upc).ok());
+        let ean = spotify_album
+            .external_ids
+            .ean
+            .and_then(|ean| ProductId::barcode(&ean).ok());
+
+        // Same fields as the album of a track, plus the barcodes
+        let mut album = Album::from(SpotifyTrackAlbum {
+            id: spotify_album.id,
+            album_type: spotify_album.album_type,
+            total_tracks: spotify_album.total_tracks,
+            available_markets: spotify_album.available_markets,
+            external_urls: spotify_album.external_urls,
+            href: spotify_album.href,
+            images: spotify_album.images,
+            name: spotify_album.name,
+            release_date: spotify_album.release_date,
+            release_date_precision: spotify_album.release_date_precision,
+            restrictions: spotify_album.restrictions,
+            _type: spotify_album._type,
+            artists: vec![],
+        });
+
+        album.extend_ids(upc.into_iter().chain(ean));
+
+        AlbumWithArtists::new(
+            album,
+            spotify_album.artists.into_iter().map(Into::into).collect(),
+        )
+    }
+}
+
+/// Simplified albums (search results) have no barcode
+impl From<SpotifyTrackAlbum> for AlbumWithArtists {
+    fn from(mut spotify_album: SpotifyTrackAlbum) -> Self {
+        let artists = std::mem::take(&mut spotify_album.artists)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        AlbumWithArtists::new(Album::from(spotify_album), artists)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SpotifyTrackAlbum {
     /// The Spotify ID for the album.
@@ -109,6 +160,8 @@ pub struct SpotifyTrackAlbum {
     pub total_tracks: u32,
     /// The markets in which the album is available: ISO 3166-1 alpha-2 country codes.
     /// NOTE: an album is considered available in a market when at least 1 of its tracks is available in that market.
+    /// Omitted when a market is given.
+    #[serde(default)]
     pub available_markets: Vec<String>,
     /// Known external URLs for this album.
     pub external_urls: SpotifyExternalUrls,
@@ -136,10 +189,7 @@ impl From<SpotifyTrackAlbum> for Album {
     fn from(spotify_album: SpotifyTrackAlbum) -> Self {
         let mut ids = HashSet::new();
 
-        ids.insert(ProductId::Provider((
-            ProviderId::new("spotify".to_string()),
-            spotify_album.id,
-        )));
+        ids.insert(ProductId::Provider((ProviderId::SPOTIFY, spotify_album.id)));
 
         let name = spotify_album.name;
         let mut covers: HashSet<ImageCover> = HashSet::new();
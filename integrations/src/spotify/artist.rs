@@ -29,7 +29,7 @@ impl From<SpotifySimplifiedArtist> for Artist {
         let provider_urls = spotify_artist.external_urls.into();
         let mut ids = HashMap::new();
 
-        ids.insert(ProviderId::new("spotify".to_string()), spotify_artist.id);
+        ids.insert(ProviderId::SPOTIFY, spotify_artist.id);
 
         Artist::new(ids, name, provider_urls)
     }
@@ -70,6 +70,18 @@ pub struct SpotifyArtist {
     pub popularity: u32,
 }
 
+impl From<SpotifyArtist> for Artist {
+    fn from(spotify_artist: SpotifyArtist) -> Self {
+        let name = spotify_artist.name;
+        let provider_urls = spotify_artist.external_urls.into();
+        let mut ids = HashMap::new();
+
+        ids.insert(ProviderId::SPOTIFY, spotify_artist.id);
+
+        Artist::new(ids, name, provider_urls)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::spotify::artist::SpotifyArtist;
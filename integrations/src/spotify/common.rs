@@ -1,6 +1,6 @@
 use std::{collections::HashMap, str::FromStr};
 
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use snk_core::value_objects::provider::provider_id::ProviderId;
 use url::Url;
@@ -11,7 +11,6 @@ pub struct SpotifyList<T> {
     pub href: Url,
     #[allow(dead_code)]
     pub limit: u32,
-    #[allow(dead_code)]
     pub next: Option<Url>,
     #[allow(dead_code)]
     pub previous: Option<Url>,
@@ -57,17 +56,19 @@ pub struct SpotifyDateTimeWrapper(pub DateTime<Utc>);
 
 impl From<(SpotifyReleaseDatePrecision, String)> for SpotifyDateTimeWrapper {
     fn from((precision, value): (SpotifyReleaseDatePrecision, String)) -> Self {
+        // Dates without time, missing month & day default to the first one
+        let date = match precision {
+            SpotifyReleaseDatePrecision::Year => format!("{}-01-01", value),
+            SpotifyReleaseDatePrecision::Month => format!("{}-01", value),
+            SpotifyReleaseDatePrecision::Day => value,
+        };
+
         SpotifyDateTimeWrapper(
-            NaiveDateTime::parse_from_str(
-                &value,
-                match precision {
-                    SpotifyReleaseDatePrecision::Year => "%Y",
-                    SpotifyReleaseDatePrecision::Month => "%Y-%m",
-                    SpotifyReleaseDatePrecision::Day => "%Y-%m-%d",
-                },
-            )
-            .unwrap()
-            .and_utc(),
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+                .unwrap_or_default(),
         )
     }
 }
@@ -81,10 +82,7 @@ impl From<SpotifyExternalUrls> for HashMap<ProviderId, Url> {
     fn from(external_urls: SpotifyExternalUrls) -> Self {
         let mut map = HashMap::new();
 
-        map.insert(
-            ProviderId::new("spotify".to_string()),
-            external_urls.spotify,
-        );
+        map.insert(ProviderId::SPOTIFY, external_urls.spotify);
         map
     }
 }
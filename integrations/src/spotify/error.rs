@@ -1,12 +1,14 @@
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct SpotifyError {
     #[allow(dead_code)]
     error: SpotifyErrorData,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct SpotifyErrorData {
     #[allow(dead_code)]
     status: u32,
@@ -0,0 +1,244 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{de::DeserializeOwned, Deserialize};
+use snk_core::{
+    contracts::repositories::{
+        album_library_repository::{
+            AlbumLibraryRepository, AlbumLibraryRepositoryError, AlbumLibraryRepositoryResult,
+        },
+        artist_library_repository::{
+            ArtistLibraryRepository, ArtistLibraryRepositoryError, ArtistLibraryRepositoryResult,
+        },
+    },
+    entities::{album::AlbumWithArtists, artist::Artist},
+    value_objects::provider::spotify_id::SpotifyId,
+};
+use url::Url;
+
+use crate::chunks::{send_library_chunks, LibraryActionError};
+
+use super::{
+    album::SpotifyAlbum, artist::SpotifyArtist, common::SpotifyList, SpotifyPlaylistRepository,
+    API_URL,
+};
+
+/// Maximum page size of the library endpoints
+static PAGE_LIMIT: &str = "50";
+
+/// Maximum number of albums saved or removed by request
+static ALBUMS_CHUNK_SIZE: usize = 20;
+
+/// Maximum number of artists followed or unfollowed by request
+static ARTISTS_CHUNK_SIZE: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct SpotifySavedAlbum {
+    /// The date and time the album was saved
+    #[allow(dead_code)]
+    pub added_at: DateTime<Utc>,
+    /// Information about the album
+    pub album: SpotifyAlbum,
+}
+
+/// Page of a list paginated with a cursor rather than an offset
+#[derive(Debug, Deserialize)]
+pub struct SpotifyCursorList<T> {
+    /// URL to the next page of items ( null if none)
+    pub next: Option<Url>,
+    pub total: u32,
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpotifyFollowedArtists {
+    pub artists: SpotifyCursorList<SpotifyArtist>,
+}
+
+impl SpotifyPlaylistRepository<'_> {
+    async fn library_page<T: DeserializeOwned>(&self, url: Url) -> Result<T, String> {
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| format!("Failed to send request - {:?}", err))?
+            .error_for_status()
+            .map_err(|err| format!("Error during request - {:?}", err))?
+            .json::<T>()
+            .await
+            .map_err(|err| format!("Failed to parse response - {:?}", err))
+    }
+
+    /// Save or remove items of the library, by chunks of the maximum size accepted
+    async fn library_action(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, &str)],
+        ids: &[String],
+        chunk_size: usize,
+    ) -> Result<(), LibraryActionError> {
+        // A malformed id would fail its whole chunk
+        for id in ids {
+            SpotifyId::parse(id).map_err(|err| LibraryActionError::Invalid(err.to_string()))?;
+        }
+
+        send_library_chunks(ids.chunks(chunk_size).collect(), |chunk| {
+            self.library_request(&method, endpoint, query, chunk)
+        })
+        .await
+    }
+
+    async fn library_request(
+        &self,
+        method: &Method,
+        endpoint: &str,
+        query: &[(&str, &str)],
+        ids: &[String],
+    ) -> Result<(), String> {
+        let ids = ids.join(",");
+        let url = Url::parse_with_params(
+            &format!("{}/{}", API_URL, endpoint),
+            query.iter().chain([("ids", ids.as_str())].iter()),
+        )
+        .map_err(|err| err.to_string())?;
+
+        self.http_client
+            .request(method.clone(), url)
+            .send()
+            .await
+            .map_err(|err| format!("Failed to send request - {:?}", err))?
+            .error_for_status()
+            .map_err(|err| format!("Error during request - {:?}", err))?;
+
+        Ok(())
+    }
+}
+
+impl AlbumLibraryRepository for SpotifyPlaylistRepository<'_> {
+    async fn get_all(&self) -> AlbumLibraryRepositoryResult<Vec<AlbumWithArtists>> {
+        let mut albums = vec![];
+        let mut url =
+            Url::parse_with_params(&format!("{}/me/albums", API_URL), [("limit", PAGE_LIMIT)]).ok();
+
+        while let Some(page_url) = url {
+            let page = self
+                .library_page::<SpotifyList<SpotifySavedAlbum>>(page_url)
+                .await
+                .map_err(|err| {
+                    AlbumLibraryRepositoryError::ServiceError(format!(
+                        "AlbumLibraryRepository - {}",
+                        err
+                    ))
+                })?;
+
+            albums.extend(page.items.into_iter().map(|saved| saved.album.into()));
+            url = page.next;
+        }
+
+        Ok(albums)
+    }
+
+    async fn save(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::PUT, "me/albums", &[], ids, ALBUMS_CHUNK_SIZE)
+            .await?)
+    }
+
+    async fn remove(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(Method::DELETE, "me/albums", &[], ids, ALBUMS_CHUNK_SIZE)
+            .await?)
+    }
+}
+
+impl ArtistLibraryRepository for SpotifyPlaylistRepository<'_> {
+    async fn get_all(&self) -> ArtistLibraryRepositoryResult<Vec<Artist>> {
+        let mut artists = vec![];
+        let mut url = Url::parse_with_params(
+            &format!("{}/me/following", API_URL),
+            [("type", "artist"), ("limit", PAGE_LIMIT)],
+        )
+        .ok();
+
+        while let Some(page_url) = url {
+            let page = self
+                .library_page::<SpotifyFollowedArtists>(page_url)
+                .await
+                .map_err(|err| {
+                    ArtistLibraryRepositoryError::ServiceError(format!(
+                        "ArtistLibraryRepository - {}",
+                        err
+                    ))
+                })?;
+
+            artists.extend(page.artists.items.into_iter().map(Into::into));
+            url = page.artists.next;
+        }
+
+        Ok(artists)
+    }
+
+    async fn save(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(
+                Method::PUT,
+                "me/following",
+                &[("type", "artist")],
+                ids,
+                ARTISTS_CHUNK_SIZE,
+            )
+            .await?)
+    }
+
+    async fn remove(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+        Ok(self
+            .library_action(
+                Method::DELETE,
+                "me/following",
+                &[("type", "artist")],
+                ids,
+                ARTISTS_CHUNK_SIZE,
+            )
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::{album::AlbumWithArtists, artist::Artist},
+        value_objects::{barcode::Upc, product_id::ProductId},
+    };
+
+    use crate::spotify::common::SpotifyList;
+
+    use super::{SpotifyFollowedArtists, SpotifySavedAlbum};
+
+    #[test]
+    fn test_deserialize_saved_albums() {
+        let payload = include_str!("../../tests/spotify/payload_saved_albums.json");
+        let json =
+            serde_json::from_str::<SpotifyList<SpotifySavedAlbum>>(payload).expect("valid json");
+
+        let album = AlbumWithArtists::from(json.items.into_iter().next().unwrap().album);
+
+        assert_eq!(album.album().name(), "Global Warming");
+        assert!(album
+            .album()
+            .ids()
+            .contains(&ProductId::UPC(Upc::parse("886443671584").unwrap())));
+        assert_eq!(album.artists()[0].name(), "Pitbull");
+    }
+
+    #[test]
+    fn test_deserialize_followed_artists() {
+        let payload = include_str!("../../tests/spotify/payload_followed_artists.json");
+        let json = serde_json::from_str::<SpotifyFollowedArtists>(payload).expect("valid json");
+
+        assert!(json.artists.next.is_some());
+
+        let artist = Artist::from(json.artists.items.into_iter().next().unwrap());
+
+        assert_eq!(artist.name(), "Pitbull");
+    }
+}
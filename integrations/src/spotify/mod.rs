@@ -1,37 +1,61 @@
-use std::{
-    collections::{HashMap, HashSet},
-    time::Duration,
-};
+use std::{collections::HashSet, time::Duration};
+
+use base64::prelude::{Engine, BASE64_STANDARD};
 
 use common::{SpotifyList, SpotifyUri};
 use playlist::{SpotifyPlaylist, SpotifySimplifiedPlaylist};
 use reqwest::{
     header::{HeaderMap, HeaderValue},
-    Client,
+    Client, Method,
 };
-use serde_json::json;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
 use snk_core::{
     contracts::repositories::playlist_repository::{
         PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
     },
     entities::{
-        music_account_provider::MusicAccountProvider, playlist::Playlist,
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
         track::TrackWithAlbumAndArtists,
     },
-    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+    value_objects::{
+        cover_image::CoverImage, image_cover::ImageCover, playlist_id::PlaylistId,
+        provider::spotify_id::SpotifyId,
+    },
 };
-use track::SpotifySavedTrack;
+use track::{SpotifyPlaylistTrack, SpotifySavedTrack};
 use url::Url;
 
+use crate::{chunks::send_chunks, cover::jpeg_bytes};
+
 mod album;
 mod artist;
 mod common;
 mod error;
+pub mod library;
 mod playlist;
+pub mod search;
 mod track;
+pub mod user;
 
 static API_URL: &str = "https://api.spotify.com/v1";
 
+/// Maximum number of items added to or removed from a playlist by request
+static PLAYLIST_CHUNK_SIZE: usize = 100;
+
+/// Maximum number of tracks saved or removed from the liked songs by request
+static LIKED_SONGS_CHUNK_SIZE: usize = 50;
+
+/// Maximum page size of the liked songs
+static LIKED_SONGS_PAGE_LIMIT: &str = "50";
+
+/// Maximum page size of the playlists of the user
+static PLAYLISTS_PAGE_LIMIT: &str = "50";
+
+/// Maximum size of a custom cover, once base64 encoded
+static COVER_MAX_ENCODED_LENGTH: usize = 256 * 1024;
+
 pub struct SpotifyPlaylistRepository<'a> {
     http_client: Client,
     #[allow(dead_code)]
@@ -39,6 +63,8 @@ pub struct SpotifyPlaylistRepository<'a> {
     // TODO Might chant that to metadata of current connected account
     /// Username of the Spotify account connected
     username: String,
+    /// ISO 3166-1 alpha-2 country of the account, tracks being relinked to versions playable there
+    market: Option<String>,
 }
 
 impl<'a> SpotifyPlaylistRepository<'a> {
@@ -69,17 +95,91 @@ impl<'a> SpotifyPlaylistRepository<'a> {
                 })?,
             username,
             music_account_provider,
+            market: None,
         })
     }
+
+    /// Apply Track Relinking for the country of the account (https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.market = market;
+        self
+    }
 }
 
-impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
-    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
-        let url = match id {
-            PlaylistId::LikedSongs => format!("{}/me/tracks", API_URL),
-            PlaylistId::Owned(playlist_id) => format!("{}/playlist/{}", API_URL, playlist_id),
-        };
+/// Id of a playlist or track, a malformed one being rejected before any request
+fn spotify_id(id: &str) -> PlaylistRepositoryResult<SpotifyId> {
+    SpotifyId::parse(id).map_err(|err| {
+        PlaylistRepositoryError::ServiceError(format!("PlaylistRepository - {}", err))
+    })
+}
 
+/// Reject the tracks to write before any request if one of their ids is malformed
+fn check_track_ids(ids: &[String]) -> PlaylistRepositoryResult<()> {
+    ids.iter().try_for_each(|id| spotify_id(id).map(|_| ()))
+}
+
+/// Body of the requests creating or updating a playlist, without the metadata left to `None`
+///
+/// Collaborative playlists are private on Spotify, unlike on other providers.
+fn playlist_details(spec: &PlaylistSpec) -> Value {
+    let mut details = json!({ "name": spec.name });
+
+    if let Some(description) = &spec.description {
+        details["description"] = json!(description);
+    }
+    if let Some(public) = spec.public {
+        details["public"] = json!(public && spec.collaborative != Some(true));
+    } else if spec.collaborative == Some(true) {
+        details["public"] = json!(false);
+    }
+    if let Some(collaborative) = spec.collaborative {
+        details["collaborative"] = json!(collaborative);
+    }
+
+    details
+}
+
+impl SpotifyPlaylistRepository<'_> {
+    /// Upload a custom cover, sent as a base64 encoded JPEG
+    async fn upload_cover(
+        &self,
+        spotify_id: &str,
+        cover: &CoverImage,
+    ) -> PlaylistRepositoryResult<()> {
+        let jpeg = jpeg_bytes(cover, COVER_MAX_ENCODED_LENGTH)
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to read cover - {}",
+                    err
+                ))
+            })?;
+
+        let response = self
+            .http_client
+            .put(format!("{}/playlists/{}/images", API_URL, spotify_id))
+            .header("Content-Type", "image/jpeg")
+            .body(BASE64_STANDARD.encode(jpeg))
+            .send()
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?;
+
+        match response.error_for_status() {
+            Ok(_) => Ok(()),
+            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Error during request - {:?}",
+                err
+            ))),
+        }
+    }
+
+    /// Read a page of a list (or any other resource)
+    async fn fetch_page<T: DeserializeOwned>(&self, url: Url) -> PlaylistRepositoryResult<T> {
         let response = self.http_client.get(url).send().await.map_err(|err| {
             PlaylistRepositoryError::ServiceError(format!(
                 "PlaylistRepository - Failed to fetch request - {:?}",
@@ -87,6 +187,104 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
             ))
         })?;
 
+        match response.error_for_status() {
+            Ok(res) => res.json::<T>().await.map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            }),
+            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Error during request - {:?}",
+                err
+            ))),
+        }
+    }
+
+    /// Items of the pages following the one already read, by following their `next` links
+    /// (which keep the query of the first page, market included)
+    async fn next_pages<T: DeserializeOwned>(
+        &self,
+        mut next: Option<Url>,
+    ) -> PlaylistRepositoryResult<Vec<T>> {
+        let mut items = vec![];
+
+        while let Some(url) = next {
+            let page = self.fetch_page::<SpotifyList<T>>(url).await?;
+
+            items.extend(page.items);
+            next = page.next;
+        }
+
+        Ok(items)
+    }
+
+    /// Url of an endpoint with its query parameters
+    fn endpoint_url(&self, path: &str, query: &[(&str, &str)]) -> PlaylistRepositoryResult<Url> {
+        Url::parse_with_params(&format!("{}/{}", API_URL, path), query)
+            .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))
+    }
+
+    /// Query parameters of the requests reading tracks, empty without market
+    fn market_query(&self) -> Vec<(&str, &str)> {
+        self.market
+            .iter()
+            .map(|market| ("market", market.as_str()))
+            .collect()
+    }
+
+    /// Write tracks of the liked songs or of a playlist (or its details), with a JSON body
+    async fn tracks_request(
+        &self,
+        method: Method,
+        url: String,
+        body: Value,
+    ) -> PlaylistRepositoryResult<()> {
+        let response = self
+            .http_client
+            .request(method, url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?;
+
+        match response.error_for_status() {
+            Ok(_) => Ok(()),
+            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Error during request - {:?}",
+                err
+            ))),
+        }
+    }
+}
+
+impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        let url = match id {
+            PlaylistId::LikedSongs => format!("{}/me/tracks", API_URL),
+            PlaylistId::Owned(playlist_id) => {
+                format!("{}/playlists/{}", API_URL, spotify_id(playlist_id)?)
+            }
+        };
+
+        let response = self
+            .http_client
+            .get(url)
+            .query(&self.market_query())
+            .send()
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to fetch request - {:?}",
+                    err
+                ))
+            })?;
+
         match response.error_for_status() {
             Ok(res) => match id {
                 PlaylistId::LikedSongs => {
@@ -151,52 +349,28 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
     }
 
     async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
-        let url = format!("{}/me/playlists", API_URL);
-
-        let response = self.http_client.get(url).send().await.map_err(|err| {
-            PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Failed to fetch request - {:?}",
-                err
-            ))
-        })?;
-
-        match response.error_for_status() {
-            Ok(res) => {
-                let playlists = res
-                    .json::<SpotifyList<SpotifySimplifiedPlaylist>>()
-                    .await
-                    .map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to parse response - {:?}",
-                            err
-                        ))
-                    })?;
-
-                Ok(playlists
-                    .items
-                    .into_iter()
-                    .map(|playlist| playlist.into())
-                    .collect())
-            }
-            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Error during request - {:?}",
-                err
-            ))),
-        }
+        let first_page = self
+            .fetch_page::<SpotifyList<SpotifySimplifiedPlaylist>>(
+                self.endpoint_url("me/playlists", &[("limit", PLAYLISTS_PAGE_LIMIT)])?,
+            )
+            .await?;
+        let mut playlists = first_page.items;
+
+        playlists.extend(self.next_pages(first_page.next).await?);
+
+        Ok(playlists
+            .into_iter()
+            .map(|playlist| playlist.into())
+            .collect())
     }
 
-    async fn create(&self, name: &str) -> PlaylistRepositoryResult<Playlist> {
-        let url = format!("{}/user/{}/playlists", API_URL, self.username);
-
-        let mut body: HashMap<&str, &str> = HashMap::new();
-
-        body.insert("name", name);
-        body.insert("description", "Playlist created thanks SonikSwap");
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let url = format!("{}/users/{}/playlists", API_URL, self.username);
 
         let response = self
             .http_client
             .post(url)
-            .json(&body)
+            .json(&playlist_details(spec))
             .send()
             .await
             .map_err(|err| {
@@ -206,22 +380,57 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
                 ))
             })?;
 
-        match response.error_for_status() {
-            Ok(res) => {
-                let playlist = res.json::<SpotifyPlaylist>().await.map_err(|err| {
+        let playlist: Playlist = match response.error_for_status() {
+            Ok(res) => res
+                .json::<SpotifyPlaylist>()
+                .await
+                .map_err(|err| {
                     PlaylistRepositoryError::ServiceError(format!(
                         "PlaylistRepository - Failed to parse response - {:?}",
                         err
                     ))
-                })?;
-
-                Ok(playlist.into())
+                })?
+                .into(),
+            Err(err) => {
+                return Err(PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Error during request - {:?}",
+                    err
+                )))
             }
-            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Error during request - {:?}",
-                err
-            ))),
+        };
+
+        // Best effort: failing now would leave a playlist created behind the back of the caller
+        if let (Some(cover), PlaylistId::Owned(spotify_id)) = (&spec.cover, playlist.id()) {
+            let _ = self.upload_cover(spotify_id, cover).await;
         }
+
+        Ok(playlist)
+    }
+
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let PlaylistId::Owned(playlist_id) = id else {
+            return Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            ));
+        };
+        let spotify_id = spotify_id(playlist_id)?;
+
+        self.tracks_request(
+            Method::PUT,
+            format!("{}/playlists/{}", API_URL, spotify_id),
+            playlist_details(spec),
+        )
+        .await?;
+
+        if let Some(cover) = &spec.cover {
+            self.upload_cover(&spotify_id, cover).await?;
+        }
+
+        Ok(())
     }
 
     async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
@@ -229,8 +438,12 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
             PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
                 "operation not permitted with favourite tracks list".to_string(),
             )),
-            PlaylistId::Owned(spotify_id) => {
-                let url = format!("{}/playlists/{}/followers", API_URL, spotify_id);
+            PlaylistId::Owned(playlist_id) => {
+                let url = format!(
+                    "{}/playlists/{}/followers",
+                    API_URL,
+                    spotify_id(playlist_id)?
+                );
 
                 let response = self.http_client.delete(url).send().await.map_err(|err| {
                     PlaylistRepositoryError::ServiceError(format!(
@@ -256,60 +469,40 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
         ids: &[String],
         snapshot_id: Option<String>,
     ) -> PlaylistRepositoryResult<()> {
-        let response = match playlist_id {
+        match playlist_id {
             PlaylistId::LikedSongs => {
-                let url = format!("{}/me/tracks", API_URL);
-                let mut payload = HashMap::new();
+                check_track_ids(ids)?;
+
+                send_chunks(ids.chunks(LIKED_SONGS_CHUNK_SIZE).collect(), |chunk| {
+                    self.tracks_request(
+                        Method::PUT,
+                        format!("{}/me/tracks", API_URL),
+                        json!({ "ids": chunk }),
+                    )
+                })
+                .await
+            }
+            PlaylistId::Owned(playlist_id) => {
+                let spotify_id = spotify_id(playlist_id)?;
+                check_track_ids(ids)?;
 
-                payload.insert(
-                    "ids",
-                    ids.iter()
+                send_chunks(ids.chunks(PLAYLIST_CHUNK_SIZE).collect(), |chunk| {
+                    let uris = chunk
+                        .iter()
                         .map(|id| format!("spotify:track:{}", id))
-                        .collect::<Vec<String>>(),
-                );
-
-                self.http_client
-                    .post(url)
-                    .json(&payload)
-                    .send()
-                    .await
-                    .map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to send request - {:?}",
-                            err
-                        ))
-                    })?
-            }
-            PlaylistId::Owned(spotify_id) => {
-                let url = format!("{}/playlists/{}/tracks", API_URL, spotify_id);
-                let uris = ids
-                    .iter()
-                    .map(|id| format!("spotify:track:{}", id))
-                    .collect::<Vec<String>>();
-
-                self.http_client
-                    .post(url)
-                    .json(&json!({
-                        "ids": uris,
-                        "snapshot_id": snapshot_id,
-                    }))
-                    .send()
-                    .await
-                    .map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to send request - {:?}",
-                            err
-                        ))
-                    })?
+                        .collect::<Vec<String>>();
+
+                    self.tracks_request(
+                        Method::POST,
+                        format!("{}/playlists/{}/tracks", API_URL, spotify_id),
+                        json!({
+                            "uris": uris,
+                            "snapshot_id": snapshot_id,
+                        }),
+                    )
+                })
+                .await
             }
-        };
-
-        match response.error_for_status() {
-            Ok(_) => Ok(()),
-            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Error during request - {:?}",
-                err
-            ))),
         }
     }
 
@@ -319,62 +512,43 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
         ids: &[String],
         snapshot_id: Option<String>,
     ) -> PlaylistRepositoryResult<()> {
-        let response = match playlist_id {
+        match playlist_id {
             PlaylistId::LikedSongs => {
-                let url = format!("{}/me/tracks", API_URL);
-                let mut payload = HashMap::new();
-
-                payload.insert(
-                    "ids",
-                    ids.iter()
-                        .map(|id| format!("spotify:track:{}", id))
-                        .collect::<Vec<String>>(),
-                );
-
-                self.http_client
-                    .delete(url)
-                    .json(&payload)
-                    .send()
-                    .await
-                    .map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to send request - {:?}",
-                            err
-                        ))
-                    })?
+                check_track_ids(ids)?;
+
+                send_chunks(ids.chunks(LIKED_SONGS_CHUNK_SIZE).collect(), |chunk| {
+                    self.tracks_request(
+                        Method::DELETE,
+                        format!("{}/me/tracks", API_URL),
+                        json!({ "ids": chunk }),
+                    )
+                })
+                .await
             }
-            PlaylistId::Owned(spotify_id) => {
-                let url = format!("{}/playlists/{}/tracks", API_URL, spotify_id);
-                let uris = ids
-                    .iter()
-                    .map(|id| SpotifyUri {
-                        uri: format!("spotify:track:{}", id),
-                    })
-                    .collect::<Vec<_>>();
-
-                self.http_client
-                    .delete(url)
-                    .json(&json!({
-                        "tracks": uris,
-                        "snapshot_id": snapshot_id,
-                    }))
-                    .send()
-                    .await
-                    .map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to send request - {:?}",
-                            err
-                        ))
-                    })?
+            PlaylistId::Owned(playlist_id) => {
+                let spotify_id = spotify_id(playlist_id)?;
+                check_track_ids(ids)?;
+
+                // Positions are resolved against the snapshot, which stays valid across chunks
+                send_chunks(ids.chunks(PLAYLIST_CHUNK_SIZE).collect(), |chunk| {
+                    let uris = chunk
+                        .iter()
+                        .map(|id| SpotifyUri {
+                            uri: format!("spotify:track:{}", id),
+                        })
+                        .collect::<Vec<_>>();
+
+                    self.tracks_request(
+                        Method::DELETE,
+                        format!("{}/playlists/{}/tracks", API_URL, spotify_id),
+                        json!({
+                            "tracks": uris,
+                            "snapshot_id": snapshot_id,
+                        }),
+                    )
+                })
+                .await
             }
-        };
-
-        match response.error_for_status() {
-            Ok(_) => Ok(()),
-            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Error during request - {:?}",
-                err
-            ))),
         }
     }
 
@@ -382,59 +556,84 @@ impl PlaylistRepository for SpotifyPlaylistRepository<'_> {
         &self,
         playlist_id: &PlaylistId,
     ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
-        let url = match playlist_id {
-            PlaylistId::LikedSongs => format!("{}/me/tracks", API_URL),
-            PlaylistId::Owned(spotify_id) => format!("{}/playlist/{}", API_URL, spotify_id),
-        };
+        let mut query = self.market_query();
 
-        let response = self.http_client.get(url).send().await.map_err(|err| {
-            PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Failed to fetch request - {:?}",
-                err
-            ))
-        })?;
+        match playlist_id {
+            PlaylistId::LikedSongs => {
+                query.push(("limit", LIKED_SONGS_PAGE_LIMIT));
 
-        match response.error_for_status() {
-            Ok(res) => match playlist_id {
-                PlaylistId::LikedSongs => {
-                    let favorite_tracks = res
-                        .json::<SpotifyList<SpotifySavedTrack>>()
-                        .await
-                        .map_err(|err| {
-                            PlaylistRepositoryError::ServiceError(format!(
-                                "PlaylistRepository - Failed to parse response - {:?}",
-                                err
-                            ))
-                        })?;
+                let first_page = self
+                    .fetch_page::<SpotifyList<SpotifySavedTrack>>(
+                        self.endpoint_url("me/tracks", &query)?,
+                    )
+                    .await?;
+                let mut favorite_tracks = first_page.items;
 
-                    // TODO Fetch all tracks
-                    Ok(favorite_tracks
-                        .items
-                        .into_iter()
-                        .map(|track| TrackWithAlbumAndArtists::from(track.track))
-                        .collect())
-                }
-                PlaylistId::Owned(_) => {
-                    let playlist = res.json::<SpotifyPlaylist>().await.map_err(|err| {
-                        PlaylistRepositoryError::ServiceError(format!(
-                            "PlaylistRepository - Failed to parse response - {:?}",
-                            err
-                        ))
-                    })?;
+                favorite_tracks.extend(self.next_pages(first_page.next).await?);
 
-                    // TODO Fetch all tracks
-                    Ok(playlist
-                        .tracks
-                        .items
-                        .into_iter()
-                        .map(|track| TrackWithAlbumAndArtists::from(track.track))
-                        .collect())
-                }
-            },
-            Err(err) => Err(PlaylistRepositoryError::ServiceError(format!(
-                "PlaylistRepository - Error during request - {:?}",
-                err
-            ))),
+                Ok(favorite_tracks
+                    .into_iter()
+                    .map(|track| TrackWithAlbumAndArtists::from(track.track))
+                    .collect())
+            }
+            PlaylistId::Owned(playlist_id) => {
+                // The first page of tracks comes with the playlist
+                let playlist =
+                    self.fetch_page::<SpotifyPlaylist>(self.endpoint_url(
+                        &format!("playlists/{}", spotify_id(playlist_id)?),
+                        &query,
+                    )?)
+                    .await?;
+                let mut tracks = playlist.tracks.items;
+
+                tracks.extend(
+                    self.next_pages::<SpotifyPlaylistTrack>(playlist.tracks.next)
+                        .await?,
+                );
+
+                Ok(tracks
+                    .into_iter()
+                    .map(|track| TrackWithAlbumAndArtists::from(track.track))
+                    .collect())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use snk_core::entities::playlist::PlaylistSpec;
+
+    use super::playlist_details;
+
+    #[test]
+    fn test_playlist_details() {
+        let spec =
+            PlaylistSpec::new("Road trip".to_string()).with_description(Some("Summer".to_string()));
+
+        assert_eq!(
+            playlist_details(&spec),
+            json!({ "name": "Road trip", "description": "Summer" })
+        );
+
+        // Public & collaborative on Deezer
+        let spec = PlaylistSpec {
+            public: Some(true),
+            collaborative: Some(true),
+            ..PlaylistSpec::new("Road trip".to_string())
+        };
+
+        assert_eq!(
+            playlist_details(&spec),
+            json!({ "name": "Road trip", "public": false, "collaborative": true })
+        );
+        assert_eq!(
+            playlist_details(&PlaylistSpec {
+                public: None,
+                ..spec
+            }),
+            json!({ "name": "Road trip", "public": false, "collaborative": true })
+        );
+    }
+}
@@ -83,6 +83,7 @@ impl From<SpotifySimplifiedPlaylist> for Playlist {
         let playlist_id = PlaylistId::Owned(spotify_playlist.id);
         let name = spotify_playlist.name;
         let owner = spotify_playlist.owner.display_name;
+        let owner_id = spotify_playlist.owner.id;
         let total_songs = spotify_playlist.tracks.total;
         let provider_url = spotify_playlist.external_urls.spotify;
         let mut covers: HashSet<ImageCover> = HashSet::new();
@@ -106,7 +107,15 @@ impl From<SpotifySimplifiedPlaylist> for Playlist {
             covers.insert(ImageCover::Sm(image.url));
         }
 
-        Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url)
+        let mut playlist =
+            Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url);
+
+        playlist.set_description(Some(spotify_playlist.description));
+        playlist.set_public(Some(spotify_playlist.public));
+        playlist.set_collaborative(Some(spotify_playlist.collaborative));
+        playlist.set_owner_id(Some(owner_id));
+
+        playlist
     }
 }
 
@@ -150,6 +159,7 @@ impl From<SpotifyPlaylist> for Playlist {
         let playlist_id = PlaylistId::Owned(spotify_playlist.id);
         let name = spotify_playlist.name;
         let owner = spotify_playlist.owner.display_name;
+        let owner_id = spotify_playlist.owner.id;
         let total_songs = spotify_playlist.tracks.total;
         let provider_url = spotify_playlist.external_urls.spotify;
         let mut covers: HashSet<ImageCover> = HashSet::new();
@@ -173,12 +183,22 @@ impl From<SpotifyPlaylist> for Playlist {
             covers.insert(ImageCover::Sm(image.url));
         }
 
-        Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url)
+        let mut playlist =
+            Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url);
+
+        playlist.set_description(spotify_playlist.description);
+        playlist.set_public(Some(spotify_playlist.public));
+        playlist.set_collaborative(Some(spotify_playlist.collaborative));
+        playlist.set_owner_id(Some(owner_id));
+
+        playlist
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use snk_core::entities::playlist::Playlist;
+
     use super::SpotifyPlaylist;
 
     #[test]
@@ -187,5 +207,12 @@ mod tests {
         let json = serde_json::from_str::<SpotifyPlaylist>(&payload).expect("valid json");
 
         assert_eq!(json.name, "My Dearest OST");
+
+        let playlist = Playlist::from(json);
+
+        assert_eq!(
+            playlist.owner_id(),
+            Some(&"p5trk6os56n9421jb0oolpisl".to_string())
+        );
     }
 }
@@ -0,0 +1,211 @@
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Deserialize};
+use snk_core::{
+    contracts::repositories::{
+        catalog_search_repository::{
+            CatalogSearchRepository, CatalogSearchRepositoryError, CatalogSearchRepositoryResult,
+        },
+        track_search_repository::{
+            TrackSearchRepository, TrackSearchRepositoryError, TrackSearchRepositoryResult,
+        },
+    },
+    entities::{album::AlbumWithArtists, artist::Artist, track::TrackWithAlbumAndArtists},
+};
+
+use super::{
+    album::SpotifyTrackAlbum, artist::SpotifyArtist, common::SpotifyList, track::SpotifyTrack,
+    SpotifyPlaylistRepository, API_URL,
+};
+
+/// Maximum number of candidates fetched by search
+static SEARCH_LIMIT: &str = "10";
+
+#[derive(Debug, Deserialize)]
+pub struct SpotifySearchResult {
+    /// Tracks matching the query, only requested type
+    pub tracks: SpotifyList<SpotifyTrack>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpotifyAlbumSearchResult {
+    /// Albums matching the query, simplified (without UPC)
+    pub albums: SpotifyList<SpotifyTrackAlbum>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpotifyArtistSearchResult {
+    pub artists: SpotifyList<SpotifyArtist>,
+}
+
+/// Search query with field filters (https://developer.spotify.com/documentation/web-api/reference/search)
+fn search_query(track: &TrackWithAlbumAndArtists) -> String {
+    let mut query = format!("track:{}", track.name());
+
+    if let Some(artist) = track.artists().first() {
+        query.push_str(&format!(" artist:{}", artist.name()));
+    }
+
+    query
+}
+
+/// Album search query with field filters
+fn album_search_query(album: &AlbumWithArtists) -> String {
+    let mut query = format!("album:{}", album.album().name());
+
+    if let Some(artist) = album.artists().first() {
+        query.push_str(&format!(" artist:{}", artist.name()));
+    }
+
+    query
+}
+
+impl SpotifyPlaylistRepository<'_> {
+    async fn search_catalog<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        search_type: &str,
+        limit: &str,
+    ) -> CatalogSearchRepositoryResult<T> {
+        let url = Url::parse_with_params(
+            &format!("{}/search", API_URL),
+            [("q", query), ("type", search_type), ("limit", limit)],
+        )
+        .map_err(|err| CatalogSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        let response = self
+            .http_client
+            .get(url)
+            .query(&self.market_query())
+            .send()
+            .await
+            .map_err(|err| {
+                CatalogSearchRepositoryError::ServiceError(format!(
+                    "CatalogSearchRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?;
+
+        response
+            .error_for_status()
+            .map_err(|err| {
+                CatalogSearchRepositoryError::ServiceError(format!(
+                    "CatalogSearchRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<T>()
+            .await
+            .map_err(|err| {
+                CatalogSearchRepositoryError::ServiceError(format!(
+                    "CatalogSearchRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })
+    }
+
+    async fn search_tracks(
+        &self,
+        query: &str,
+        limit: &str,
+    ) -> TrackSearchRepositoryResult<Vec<SpotifyTrack>> {
+        let url = Url::parse_with_params(
+            &format!("{}/search", API_URL),
+            [("q", query), ("type", "track"), ("limit", limit)],
+        )
+        .map_err(|err| TrackSearchRepositoryError::ServiceError(err.to_string()))?;
+
+        let response = self
+            .http_client
+            .get(url)
+            .query(&self.market_query())
+            .send()
+            .await
+            .map_err(|err| {
+                TrackSearchRepositoryError::ServiceError(format!(
+                    "TrackSearchRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })?;
+
+        let result = response
+            .error_for_status()
+            .map_err(|err| {
+                TrackSearchRepositoryError::ServiceError(format!(
+                    "TrackSearchRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<SpotifySearchResult>()
+            .await
+            .map_err(|err| {
+                TrackSearchRepositoryError::ServiceError(format!(
+                    "TrackSearchRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })?;
+
+        Ok(result.tracks.items)
+    }
+}
+
+impl TrackSearchRepository for SpotifyPlaylistRepository<'_> {
+    async fn find_by_isrc(
+        &self,
+        isrc: &str,
+    ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        let tracks = self.search_tracks(&format!("isrc:{}", isrc), "1").await?;
+
+        Ok(tracks.into_iter().next().map(Into::into))
+    }
+
+    async fn search(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let tracks = self
+            .search_tracks(&search_query(track), SEARCH_LIMIT)
+            .await?;
+
+        Ok(tracks.into_iter().map(Into::into).collect())
+    }
+}
+
+impl CatalogSearchRepository for SpotifyPlaylistRepository<'_> {
+    async fn find_album_by_upc(
+        &self,
+        upc: &str,
+    ) -> CatalogSearchRepositoryResult<Option<AlbumWithArtists>> {
+        let result = self
+            .search_catalog::<SpotifyAlbumSearchResult>(&format!("upc:{}", upc), "album", "1")
+            .await?;
+
+        Ok(result.albums.items.into_iter().next().map(Into::into))
+    }
+
+    async fn search_albums(
+        &self,
+        album: &AlbumWithArtists,
+    ) -> CatalogSearchRepositoryResult<Vec<AlbumWithArtists>> {
+        let result = self
+            .search_catalog::<SpotifyAlbumSearchResult>(
+                &album_search_query(album),
+                "album",
+                SEARCH_LIMIT,
+            )
+            .await?;
+
+        Ok(result.albums.items.into_iter().map(Into::into).collect())
+    }
+
+    async fn search_artists(&self, artist: &Artist) -> CatalogSearchRepositoryResult<Vec<Artist>> {
+        let result = self
+            .search_catalog::<SpotifyArtistSearchResult>(
+                &format!("artist:{}", artist.name()),
+                "artist",
+                SEARCH_LIMIT,
+            )
+            .await?;
+
+        Ok(result.artists.items.into_iter().map(Into::into).collect())
+    }
+}
@@ -3,8 +3,8 @@ use std::collections::HashSet;
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use snk_core::{
-    entities::track::TrackWithAlbumAndArtists,
-    value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    entities::track::{Track, TrackWithAlbumAndArtists},
+    value_objects::{isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId},
 };
 use url::Url;
 
@@ -32,46 +32,36 @@ pub struct SpotifyLinkedTrack {
 #[derive(Debug, Deserialize)]
 pub struct SpotifySimplifiedTrack {
     /// The Spotify ID for the track.
-    #[allow(dead_code)]
     pub id: String,
     /// A link to the Web API endpoint providing full details of the track.
     #[allow(dead_code)]
     pub href: Url,
     /// External URLs for this track.
-    #[allow(dead_code)]
     pub external_urls: SpotifyExternalUrls,
     /// Whether or not the track has explicit lyrics ( true = yes it does; false = no it does not OR unknown).
-    #[allow(dead_code)]
     pub explicit: bool,
     /// The track length in milliseconds.
-    #[allow(dead_code)]
     pub duration_ms: u32,
     /// The disc number (usually 1 unless the album consists of more than one disc).
-    #[allow(dead_code)]
     pub disc_number: u32,
     /// A list of the countries in which the track can be played, identified by their ISO 3166-1 alpha-2 code.
-    #[allow(dead_code)]
-    pub available_markets: Vec<String>,
+    /// Omitted when a market is given.
+    pub available_markets: Option<Vec<String>>,
     /// The artists who performed the track. Each artist object includes a link in href to more detailed information about the artist.
     #[allow(dead_code)]
     pub artists: Vec<SpotifySimplifiedArtist>,
     /// Part of the response when Track Relinking is applied. If true, the track is playable in the given market. Otherwise false.
-    #[allow(dead_code)]
     pub is_playable: Option<bool>,
     /// Part of the response when Track Relinking is applied and is only part of the response if the track linking, in fact, exists. The requested track has been replaced with a different track. The track in the linked_from object contains information about the originally requested track.
-    #[allow(dead_code)]
     pub linked_from: Option<SpotifyLinkedTrack>,
     /// Included in the response when a content restriction is applied.
     #[allow(dead_code)]
     pub restrictions: Option<SpotifyRestriction>,
     /// The name of the track.
-    #[allow(dead_code)]
     pub name: String,
     /// A URL to a 30 second preview (MP3 format) of the track.
-    #[allow(dead_code)]
     pub preview_url: Option<Url>,
     /// The number of the track. If an album has several discs, the track number is the number on the specified disc.
-    #[allow(dead_code)]
     pub track_number: u32,
     /// The object type => "track"
     #[serde(alias = "type")]
@@ -132,7 +122,8 @@ pub struct SpotifyTrack {
     /// Each artist object includes a link in href to more detailed information about the artist.
     pub artists: Vec<SpotifySimplifiedArtist>,
     /// A list of the countries in which the track can be played, identified by their ISO 3166-1 alpha-2 code.
-    pub available_markets: Vec<String>,
+    /// Omitted when a market is given.
+    pub available_markets: Option<Vec<String>>,
     /// The disc number (usually 1 unless the album consists of more than one disc).
     pub disc_number: u32,
     /// The track length in milliseconds
@@ -172,14 +163,56 @@ pub struct SpotifyTrack {
     pub is_local: bool,
 }
 
+impl From<SpotifySimplifiedTrack> for Track {
+    fn from(spotify_track: SpotifySimplifiedTrack) -> Self {
+        // A relinked track is known by the id it was requested with (playlist items, liked songs)
+        let id = spotify_track
+            .linked_from
+            .map(|linked_track| linked_track.id)
+            .unwrap_or(spotify_track.id);
+
+        let mut track = Track::new(
+            HashSet::from_iter([ProductId::Provider((ProviderId::SPOTIFY, id))]),
+            spotify_track.name,
+            spotify_track.duration_ms,
+            spotify_track.external_urls.into(),
+        );
+
+        track.set_explicit(Some(spotify_track.explicit));
+        track.set_position(
+            Some(spotify_track.disc_number),
+            Some(spotify_track.track_number),
+        );
+        track.set_preview_url(spotify_track.preview_url);
+        track.set_available_markets(spotify_track.available_markets.map(HashSet::from_iter));
+        track.set_playable(spotify_track.is_playable);
+
+        track
+    }
+}
+
 impl From<SpotifyTrack> for TrackWithAlbumAndArtists {
+    // The preview url is deprecated, yet still returned for some tracks
+    #[allow(deprecated)]
     fn from(spotify_track: SpotifyTrack) -> Self {
         let mut ids = HashSet::new();
 
-        ids.insert(ProductId::Provider((
-            ProviderId::new("spotify".to_string()),
-            spotify_track.id,
-        )));
+        // A relinked track is known by the id it was requested with (playlist items, liked songs)
+        let id = spotify_track
+            .linked_from
+            .map(|linked_track| linked_track.id)
+            .unwrap_or(spotify_track.id);
+
+        ids.insert(ProductId::Provider((ProviderId::SPOTIFY, id)));
+
+        if let Some(isrc) = spotify_track
+            .external_ids
+            .isrc
+            .and_then(|isrc| Isrc::parse(&isrc).ok())
+        {
+            ids.insert(ProductId::ISRC(isrc));
+        }
+
         let name = spotify_track.name;
         let duration_ms = spotify_track.duration_ms;
         let provider_urls = spotify_track.external_urls.into();
@@ -187,12 +220,27 @@ impl From<SpotifyTrack> for TrackWithAlbumAndArtists {
         let album = spotify_track.album.into();
         let artists = spotify_track.artists.into_iter().map(Into::into).collect();
 
-        TrackWithAlbumAndArtists::new(ids, name, duration_ms, provider_urls, album, artists)
+        let mut track =
+            TrackWithAlbumAndArtists::new(ids, name, duration_ms, provider_urls, album, artists);
+
+        track.explicit = Some(spotify_track.explicit);
+        track.disc_number = Some(spotify_track.disc_number);
+        track.track_number = Some(spotify_track.track_number);
+        track.preview_url = spotify_track.preview_url;
+        track.available_markets = spotify_track.available_markets.map(HashSet::from_iter);
+        track.playable = spotify_track.is_playable;
+
+        track
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use snk_core::{
+        entities::track::TrackWithAlbumAndArtists,
+        value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
     use crate::spotify::track::SpotifyTrack;
 
     #[test]
@@ -202,5 +250,43 @@ mod tests {
 
         assert_eq!(json.name, "How Sweet");
         assert_eq!(json.artists[0].name, "NewJeans");
+
+        let track = TrackWithAlbumAndArtists::from(json);
+
+        assert_eq!(track.explicit(), Some(false));
+        assert_eq!(track.disc_number(), Some(1));
+        assert_eq!(track.track_number(), Some(1));
+        assert_eq!(track.is_available_in("AR"), Some(true));
+        assert_eq!(track.is_available_in("XX"), Some(false));
+        assert_eq!(track.is_playable_in(Some("AR")), Some(true));
+    }
+
+    #[test]
+    fn test_deserialize_relinked_track() {
+        let payload = include_str!("../../tests/spotify/payload_track.json");
+        let mut json = serde_json::from_str::<serde_json::Value>(payload).expect("valid json");
+
+        // Shape of a track requested with a market
+        json.as_object_mut()
+            .expect("object")
+            .remove("available_markets");
+        json["is_playable"] = serde_json::Value::Bool(false);
+        json["linked_from"] = serde_json::json!({
+            "id": "6YHvN6XFkGgHxr4Ow6Os3X",
+            "href": "https://api.spotify.com/v1/tracks/6YHvN6XFkGgHxr4Ow6Os3X",
+            "external_urls": { "spotify": "https://open.spotify.com/track/6YHvN6XFkGgHxr4Ow6Os3X" },
+            "type": "track",
+            "uri": "spotify:track:6YHvN6XFkGgHxr4Ow6Os3X"
+        });
+
+        let json = serde_json::from_value::<SpotifyTrack>(json).expect("valid track");
+        let track = TrackWithAlbumAndArtists::from(json);
+
+        assert!(track.ids().contains(&ProductId::Provider((
+            ProviderId::SPOTIFY,
+            "6YHvN6XFkGgHxr4Ow6Os3X".to_string()
+        ))));
+        assert_eq!(track.available_markets(), None);
+        assert_eq!(track.is_playable_in(Some("AR")), Some(false));
     }
 }
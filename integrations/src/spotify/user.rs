@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use snk_core::{
+    contracts::repositories::account_profile_repository::{
+        AccountProfileRepository, AccountProfileRepositoryError, AccountProfileRepositoryResult,
+    },
+    entities::account_profile::AccountProfile,
+};
+
+use super::{SpotifyPlaylistRepository, API_URL};
+
+/// Current user, as returned by `/me`
+#[derive(Debug, Deserialize)]
+pub struct SpotifyCurrentUser {
+    /// The Spotify user ID for the user.
+    pub id: String,
+    /// The name displayed on the user's profile. null if not available.
+    pub display_name: Option<String>,
+    /// The country of the user, as set in the user's account profile. An ISO 3166-1 alpha-2 country code.
+    /// This field is only available when the current user has granted access to the user-read-private scope.
+    pub country: Option<String>,
+}
+
+impl From<SpotifyCurrentUser> for AccountProfile {
+    fn from(user: SpotifyCurrentUser) -> Self {
+        AccountProfile::new(
+            user.id.clone(),
+            user.display_name.unwrap_or(user.id),
+            user.country,
+        )
+    }
+}
+
+impl AccountProfileRepository for SpotifyPlaylistRepository<'_> {
+    async fn get_profile(&self) -> AccountProfileRepositoryResult<AccountProfile> {
+        let url = format!("{}/me", API_URL);
+
+        let response = self.http_client.get(url).send().await.map_err(|err| {
+            AccountProfileRepositoryError::ServiceError(format!(
+                "AccountProfileRepository - Failed to send request - {:?}",
+                err
+            ))
+        })?;
+
+        let user = response
+            .error_for_status()
+            .map_err(|err| {
+                AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Error during request - {:?}",
+                    err
+                ))
+            })?
+            .json::<SpotifyCurrentUser>()
+            .await
+            .map_err(|err| {
+                AccountProfileRepositoryError::ServiceError(format!(
+                    "AccountProfileRepository - Failed to parse response - {:?}",
+                    err
+                ))
+            })?;
+
+        Ok(user.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::entities::account_profile::AccountProfile;
+
+    use super::SpotifyCurrentUser;
+
+    #[test]
+    fn test_deserialize_current_user() {
+        let payload = include_str!("../../tests/spotify/payload_current_user.json");
+        let json = serde_json::from_str::<SpotifyCurrentUser>(payload).expect("valid json");
+
+        let profile = AccountProfile::from(json);
+
+        assert_eq!(profile.id(), "smedjan");
+        assert_eq!(profile.name(), "JM Wizzler");
+        assert_eq!(profile.country_code(), Some(&"SE".to_string()));
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use snk_core::value_objects::image_cover::ImageCover;
+use url::Url;
+
+static RESOURCES_URL: &str = "https://resources.tidal.com/images";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TidalList<T> {
+    pub limit: u32,
+    pub offset: u32,
+    pub total_number_of_items: u32,
+    pub items: Vec<T>,
+}
+
+/// Item of a playlist or of the favorites
+#[derive(Debug, Deserialize)]
+pub struct TidalListItem<T> {
+    /// The item itself (track, video...)
+    pub item: T,
+    /// The type of the item => "track" | "video" (absent from favorites)
+    #[serde(rename = "type")]
+    pub _type: Option<String>,
+}
+
+/// Build the url of a Tidal image resource from its uuid
+/// (ex: `1f0e1b6a-...` -> `https://resources.tidal.com/images/1f0e1b6a/.../640x640.jpg`)
+pub fn image_url(uuid: &str, width: u32, height: u32) -> Option<Url> {
+    format!(
+        "{}/{}/{}x{}.jpg",
+        RESOURCES_URL,
+        uuid.replace('-', "/"),
+        width,
+        height
+    )
+    .parse()
+    .ok()
+}
+
+/// Square image covers (albums & square playlist pictures)
+pub fn square_covers(uuid: &str) -> HashSet<ImageCover> {
+    let mut covers = HashSet::new();
+
+    if let Some(url) = image_url(uuid, 640, 640) {
+        covers.insert(ImageCover::Default(url));
+    }
+    if let Some(url) = image_url(uuid, 160, 160) {
+        covers.insert(ImageCover::Sm(url));
+    }
+    if let Some(url) = image_url(uuid, 320, 320) {
+        covers.insert(ImageCover::Md(url));
+    }
+    if let Some(url) = image_url(uuid, 640, 640) {
+        covers.insert(ImageCover::Lg(url));
+    }
+    if let Some(url) = image_url(uuid, 1080, 1080) {
+        covers.insert(ImageCover::Other(url));
+    }
+
+    covers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::image_url;
+
+    #[test]
+    fn test_image_url() {
+        let url = image_url("3bd0e9ca-5b58-4d5a-a4e6-c7a8e76c8b26", 320, 320).expect("valid url");
+
+        assert_eq!(
+            url.as_str(),
+            "https://resources.tidal.com/images/3bd0e9ca/5b58/4d5a/a4e6/c7a8e76c8b26/320x320.jpg"
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TidalError {
+    /// HTTP status of the error
+    pub status: u16,
+    /// Tidal specific error code (ex: 2001 resource not found, 6001 user not allowed)
+    #[allow(dead_code)]
+    pub sub_status: u32,
+    /// Human readable description of the error
+    pub user_message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TidalError;
+
+    #[test]
+    fn test_deserialize_error() {
+        let json_str = "{\"status\":412,\"subStatus\":7003,\"userMessage\":\"The playlist has been modified by someone else\"}";
+        let json = serde_json::from_str::<TidalError>(json_str).expect("valid json");
+
+        assert_eq!(json.status, 412);
+        assert_eq!(json.sub_status, 7003);
+    }
+}
@@ -0,0 +1,570 @@
+pub mod common;
+pub mod error;
+pub mod playlist;
+pub mod track;
+
+use std::time::Duration;
+
+use common::{TidalList, TidalListItem};
+use error::TidalError;
+use playlist::TidalPlaylist;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ETAG, IF_NONE_MATCH},
+    Client, RequestBuilder, Response, StatusCode,
+};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+};
+use track::TidalTrack;
+use url::Url;
+
+use crate::chunks::send_chunks;
+
+static API_URL: &str = "https://api.tidal.com/v1";
+
+/// Maximum page size accepted by the Tidal API
+static PAGE_LIMIT: u32 = 100;
+
+pub struct TidalPlaylistRepository<'a> {
+    http_client: Client,
+    #[allow(dead_code)]
+    music_account_provider: &'a MusicAccountProvider,
+    /// Tidal id of the account connected
+    user_id: String,
+    /// Country of the account connected (ISO 3166-1 alpha-2), required by most endpoints
+    country_code: String,
+}
+
+impl<'a> TidalPlaylistRepository<'a> {
+    pub fn new(
+        music_account_provider: &'a MusicAccountProvider,
+        user_id: String,
+        country_code: String,
+        access_token: String,
+    ) -> Result<Self, &'static str> {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+        default_headers.insert(
+            "Authorization",
+            format!("Bearer {}", access_token).parse().map_err(|err| {
+                eprintln!("{:?}", err);
+                "TidalPlaylistRepository::new: Could not parse header value"
+            })?,
+        );
+
+        Ok(Self {
+            http_client: Client::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .default_headers(default_headers)
+                .build()
+                .map_err(|err| {
+                    eprintln!("{:?}", err);
+                    "TidalPlaylistRepository::new: Could not init HTTP client"
+                })?,
+            music_account_provider,
+            user_id,
+            country_code,
+        })
+    }
+
+    async fn send_unchecked(&self, request: RequestBuilder) -> PlaylistRepositoryResult<Response> {
+        request
+            .query(&[("countryCode", &self.country_code)])
+            .send()
+            .await
+            .map_err(|err| {
+                PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Failed to send request - {:?}",
+                    err
+                ))
+            })
+    }
+
+    /// Turn Tidal error payloads into [`PlaylistRepositoryError`]
+    async fn check(response: Response) -> PlaylistRepositoryResult<Response> {
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let message = match response.json::<TidalError>().await {
+            Ok(error) => error.user_message,
+            Err(_) => status.to_string(),
+        };
+
+        match status {
+            StatusCode::PRECONDITION_FAILED => Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist modified concurrently, refresh its ETag - {}",
+                message
+            ))),
+            _ => Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Error during request - {} ({})",
+                message, status
+            ))),
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder) -> PlaylistRepositoryResult<Response> {
+        Self::check(self.send_unchecked(request).await?).await
+    }
+
+    async fn parse<T: DeserializeOwned>(response: Response) -> PlaylistRepositoryResult<T> {
+        response.json::<T>().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to parse response - {:?}",
+                err
+            ))
+        })
+    }
+
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> PlaylistRepositoryResult<T> {
+        Self::parse(self.send(request).await?).await
+    }
+
+    /// Fetch every page of a list endpoint using offset pagination
+    async fn fetch_all<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> PlaylistRepositoryResult<Vec<T>> {
+        let mut items = vec![];
+        let mut offset = 0;
+
+        loop {
+            let page = self
+                .fetch::<TidalList<T>>(
+                    self.http_client
+                        .get(format!("{}/{}", API_URL, endpoint))
+                        .query(&[("limit", PAGE_LIMIT), ("offset", offset)]),
+                )
+                .await?;
+
+            let count = page.items.len() as u32;
+
+            items.extend(page.items);
+            offset += count;
+
+            if count == 0 || offset >= page.total_number_of_items {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Items of a playlist, tracks & videos
+    async fn get_playlist_items(
+        &self,
+        uuid: &str,
+    ) -> PlaylistRepositoryResult<Vec<TidalListItem<serde_json::Value>>> {
+        self.fetch_all::<TidalListItem<serde_json::Value>>(&format!("playlists/{}/items", uuid))
+            .await
+    }
+
+    /// Tracks of a playlist, videos are ignored
+    async fn get_playlist_tracks(&self, uuid: &str) -> PlaylistRepositoryResult<Vec<TidalTrack>> {
+        let items = self.get_playlist_items(uuid).await?;
+
+        keep_tracks(items).map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to parse response - {:?}",
+                err
+            ))
+        })
+    }
+
+    /// Current ETag of a playlist, required to modify its items
+    pub async fn get_etag(&self, uuid: &str) -> PlaylistRepositoryResult<String> {
+        let response = self
+            .send(
+                self.http_client
+                    .get(format!("{}/playlists/{}", API_URL, uuid)),
+            )
+            .await?;
+
+        response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(str::to_string)
+            .ok_or(PlaylistRepositoryError::ServiceError(
+                "PlaylistRepository - Missing ETag header".to_string(),
+            ))
+    }
+
+    async fn etag_or_fetch(
+        &self,
+        uuid: &str,
+        snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<String> {
+        match snapshot_id {
+            Some(etag) => Ok(etag),
+            None => self.get_etag(uuid).await,
+        }
+    }
+}
+
+/// Playlists may contain videos, only tracks are kept
+fn keep_tracks(
+    items: Vec<TidalListItem<serde_json::Value>>,
+) -> Result<Vec<TidalTrack>, serde_json::Error> {
+    items
+        .into_iter()
+        .filter(|item| item._type.as_deref() == Some("track"))
+        .map(|item| serde_json::from_value::<TidalTrack>(item.item))
+        .collect()
+}
+
+/// Indexes of the tracks to remove from a playlist.
+/// Videos are not returned as tracks but still take an index in the playlist.
+fn track_indexes(items: &[TidalListItem<serde_json::Value>], ids: &[String]) -> Vec<String> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item._type.as_deref() == Some("track"))
+        .filter(|(_, item)| {
+            item.item
+                .get("id")
+                .is_some_and(|id| ids.contains(&id.to_string()))
+        })
+        .map(|(index, _)| index.to_string())
+        .collect()
+}
+
+/// Form of a metadata update, the description is left untouched when missing
+fn metadata_form(spec: &PlaylistSpec) -> Vec<(&str, &str)> {
+    let mut form = vec![("title", spec.name.as_str())];
+
+    if let Some(description) = &spec.description {
+        form.push(("description", description.as_str()));
+    }
+
+    form
+}
+
+impl PlaylistRepository for TidalPlaylistRepository<'_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => {
+                let favorites = self
+                    .fetch::<TidalList<IgnoredAny>>(
+                        self.http_client
+                            .get(format!(
+                                "{}/users/{}/favorites/tracks",
+                                API_URL, self.user_id
+                            ))
+                            .query(&[("limit", 1)]),
+                    )
+                    .await?;
+
+                Ok(Some(Playlist::new(
+                    id.clone(),
+                    id.to_string(),
+                    [ImageCover::Other(
+                        "https://cdn.icon-icons.com/icons2/72/PNG/256/favourite_14390.png"
+                            .parse::<Url>()
+                            .map_err(|err| {
+                                PlaylistRepositoryError::ServiceError(err.to_string())
+                            })?,
+                    )]
+                    .into(),
+                    "me".to_string(),
+                    favorites.total_number_of_items,
+                    "https://listen.tidal.com/my-collection/tracks"
+                        .parse::<Url>()
+                        .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?,
+                )))
+            }
+            PlaylistId::Owned(uuid) => {
+                let response = self
+                    .send_unchecked(
+                        self.http_client
+                            .get(format!("{}/playlists/{}", API_URL, uuid)),
+                    )
+                    .await?;
+
+                if response.status() == StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+
+                let playlist = Self::parse::<TidalPlaylist>(Self::check(response).await?).await?;
+
+                Ok(Some(playlist.into()))
+            }
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let playlists = self
+            .fetch_all::<TidalPlaylist>(&format!("users/{}/playlists", self.user_id))
+            .await?;
+
+        Ok(playlists.into_iter().map(Into::into).collect())
+    }
+
+    /// Playlists are created private, the visibility & cover of the spec are ignored
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let playlist = self
+            .fetch::<TidalPlaylist>(
+                self.http_client
+                    .post(format!("{}/users/{}/playlists", API_URL, self.user_id))
+                    .form(&[
+                        ("title", spec.name.as_str()),
+                        (
+                            "description",
+                            spec.description.as_deref().unwrap_or_default(),
+                        ),
+                    ]),
+            )
+            .await?;
+
+        Ok(playlist.into())
+    }
+
+    /// Only the title & description can be set, other metadata of the spec are ignored
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let PlaylistId::Owned(uuid) = id else {
+            return Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            ));
+        };
+
+        let etag = self.get_etag(uuid).await?;
+
+        self.send(
+            self.http_client
+                .post(format!("{}/playlists/{}", API_URL, uuid))
+                .header(IF_NONE_MATCH, etag)
+                .form(&metadata_form(spec)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            )),
+            PlaylistId::Owned(uuid) => {
+                let Some(playlist) = self.get(id).await? else {
+                    return Ok(None);
+                };
+
+                self.send(
+                    self.http_client
+                        .delete(format!("{}/playlists/{}", API_URL, uuid)),
+                )
+                .await?;
+
+                Ok(Some(playlist))
+            }
+        }
+    }
+
+    /// For owned playlists, `snapshot_id` is the ETag of the playlist:
+    /// the request is rejected if the playlist has been modified since.
+    /// When missing, the current ETag is fetched beforehand.
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        let track_ids = ids.join(",");
+        let track_ids = track_ids.as_str();
+
+        // All the tracks are sent at once, the ETag changing with each write
+        match playlist_id {
+            PlaylistId::LikedSongs => {
+                send_chunks(vec![ids], |_| async move {
+                    self.send(
+                        self.http_client
+                            .post(format!(
+                                "{}/users/{}/favorites/tracks",
+                                API_URL, self.user_id
+                            ))
+                            .form(&[("trackIds", track_ids)]),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+            PlaylistId::Owned(uuid) => {
+                let etag = self.etag_or_fetch(uuid, snapshot_id).await?;
+                let etag = &etag;
+
+                send_chunks(vec![ids], |_| async move {
+                    self.send(
+                        self.http_client
+                            .post(format!("{}/playlists/{}/items", API_URL, uuid))
+                            .header(IF_NONE_MATCH, etag)
+                            .form(&[("trackIds", track_ids), ("onDupes", "SKIP")]),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+        }
+    }
+
+    /// Owned playlist items are removed by index, `snapshot_id` (ETag) guarantees
+    /// that indexes computed from the current tracks are still valid
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        // All the tracks are removed at once, the indexes changing with each write
+        match playlist_id {
+            PlaylistId::LikedSongs => {
+                send_chunks(vec![ids], |_| async move {
+                    self.send(self.http_client.delete(format!(
+                        "{}/users/{}/favorites/tracks/{}",
+                        API_URL,
+                        self.user_id,
+                        ids.join(",")
+                    )))
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+            PlaylistId::Owned(uuid) => {
+                let etag = self.etag_or_fetch(uuid, snapshot_id).await?;
+                let indexes = track_indexes(&self.get_playlist_items(uuid).await?, ids);
+
+                if indexes.is_empty() {
+                    return Ok(());
+                }
+
+                let (etag, indexes) = (&etag, &indexes);
+
+                send_chunks(vec![ids], |_| async move {
+                    self.send(
+                        self.http_client
+                            .delete(format!(
+                                "{}/playlists/{}/items/{}",
+                                API_URL,
+                                uuid,
+                                indexes.join(",")
+                            ))
+                            .header(IF_NONE_MATCH, etag),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+        }
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let tracks = match playlist_id {
+            PlaylistId::LikedSongs => self
+                .fetch_all::<TidalListItem<TidalTrack>>(&format!(
+                    "users/{}/favorites/tracks",
+                    self.user_id
+                ))
+                .await?
+                .into_iter()
+                .map(|favorite| favorite.item)
+                .collect(),
+            PlaylistId::Owned(uuid) => self.get_playlist_tracks(uuid).await?,
+        };
+
+        tracks
+            .into_iter()
+            .map(TrackWithAlbumAndArtists::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err: &'static str| PlaylistRepositoryError::ServiceError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::entities::playlist::PlaylistSpec;
+
+    use super::{
+        common::{TidalList, TidalListItem},
+        keep_tracks, metadata_form, track_indexes,
+    };
+
+    #[test]
+    fn test_keep_tracks() {
+        let payload = include_str!("../../tests/tidal/payload_playlist_items.json");
+        let json = serde_json::from_str::<TidalList<TidalListItem<serde_json::Value>>>(payload)
+            .expect("valid json");
+
+        assert_eq!(json.total_number_of_items, 3);
+
+        let tracks = keep_tracks(json.items).expect("valid tracks");
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[1].title, "Nights Like This");
+        assert_eq!(tracks[1].artists.len(), 2);
+    }
+
+    #[test]
+    fn test_track_indexes() {
+        let payload = include_str!("../../tests/tidal/payload_playlist_items.json");
+        let json = serde_json::from_str::<TidalList<TidalListItem<serde_json::Value>>>(payload)
+            .expect("valid json");
+
+        // "Nights Like This" comes after a video
+        assert_eq!(
+            track_indexes(&json.items, &["77646172".to_string()]),
+            vec!["2".to_string()]
+        );
+        assert_eq!(
+            track_indexes(
+                &json.items,
+                &["361163006".to_string(), "77646172".to_string()]
+            ),
+            vec!["0".to_string(), "2".to_string()]
+        );
+        // Videos are never removed
+        assert!(track_indexes(&json.items, &["104218237".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_metadata_form() {
+        let spec = PlaylistSpec::new("Road trip".to_string());
+
+        assert_eq!(metadata_form(&spec), vec![("title", "Road trip")]);
+
+        let spec = spec.with_description(Some("Songs for the road".to_string()));
+
+        assert_eq!(
+            metadata_form(&spec),
+            vec![
+                ("title", "Road trip"),
+                ("description", "Songs for the road")
+            ]
+        );
+    }
+}
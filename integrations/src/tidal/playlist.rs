@@ -0,0 +1,107 @@
+use serde::Deserialize;
+use snk_core::{
+    entities::playlist::Playlist,
+    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+};
+use url::Url;
+
+use super::common::{image_url, square_covers};
+
+#[derive(Debug, Deserialize)]
+pub struct TidalPlaylistCreator {
+    /// The Tidal id of the user (0 for Tidal editorial playlists)
+    #[allow(dead_code)]
+    pub id: u64,
+    /// The name of the user, absent for most users
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TidalPlaylist {
+    /// The Tidal uuid of the playlist
+    pub uuid: String,
+    /// The title of the playlist
+    pub title: String,
+    /// The description of the playlist
+    pub description: Option<String>,
+    /// Number of tracks in the playlist
+    pub number_of_tracks: u32,
+    /// Number of videos in the playlist
+    #[allow(dead_code)]
+    pub number_of_videos: u32,
+    /// The duration of the playlist (seconds)
+    #[allow(dead_code)]
+    pub duration: u32,
+    /// The creator of the playlist
+    pub creator: TidalPlaylistCreator,
+    /// The type of the playlist => "USER" | "EDITORIAL" | "ARTIST"
+    #[allow(dead_code)]
+    #[serde(rename = "type")]
+    pub _type: String,
+    /// If the playlist is public or not
+    pub public_playlist: bool,
+    /// The url of the playlist on Tidal
+    pub url: Url,
+    /// The uuid of the playlist picture (rectangle)
+    pub image: Option<String>,
+    /// The uuid of the playlist picture (square)
+    pub square_image: Option<String>,
+}
+
+impl From<TidalPlaylist> for Playlist {
+    fn from(tidal_playlist: TidalPlaylist) -> Self {
+        let playlist_id = PlaylistId::Owned(tidal_playlist.uuid);
+        let name = tidal_playlist.title;
+        let owner = tidal_playlist
+            .creator
+            .name
+            .unwrap_or_else(|| "me".to_string());
+        let total_songs = tidal_playlist.number_of_tracks;
+        let provider_url = tidal_playlist.url;
+
+        let mut covers = tidal_playlist
+            .square_image
+            .map(|square_image| square_covers(&square_image))
+            .unwrap_or_default();
+
+        if let Some(url) = tidal_playlist
+            .image
+            .and_then(|image| image_url(&image, 1080, 720))
+        {
+            covers.insert(ImageCover::Other(url));
+        }
+
+        let mut playlist =
+            Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url);
+
+        playlist.set_description(tidal_playlist.description);
+        playlist.set_public(Some(tidal_playlist.public_playlist));
+
+        playlist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{entities::playlist::Playlist, value_objects::playlist_id::PlaylistId};
+
+    use super::TidalPlaylist;
+
+    #[test]
+    fn test_deserialize_playlist() {
+        let payload = include_str!("../../tests/tidal/payload_playlist.json");
+        let json = serde_json::from_str::<TidalPlaylist>(payload).expect("valid json");
+
+        assert_eq!(json.title, "Women of Rap");
+        assert_eq!(json.number_of_tracks, 2);
+
+        let playlist: Playlist = json.into();
+
+        assert!(matches!(
+            playlist.id(),
+            PlaylistId::Owned(uuid) if uuid == "7ab5d2b6-93fb-4181-a008-a1d18e2cebfa"
+        ));
+        assert_eq!(playlist.owner(), "me");
+    }
+}
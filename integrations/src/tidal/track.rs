@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId},
+};
+use url::Url;
+
+use super::common::square_covers;
+
+#[derive(Debug, Deserialize)]
+pub struct TidalTrackArtist {
+    /// The Tidal id of the artist
+    pub id: u64,
+    /// The name of the artist
+    pub name: String,
+    /// The role of the artist on the track => "MAIN" | "FEATURED"
+    #[allow(dead_code)]
+    #[serde(rename = "type")]
+    pub _type: String,
+    /// The uuid of the artist picture
+    #[allow(dead_code)]
+    pub picture: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TidalTrackAlbum {
+    /// The Tidal id of the album
+    pub id: u64,
+    /// The title of the album
+    pub title: String,
+    /// The uuid of the album cover
+    pub cover: Option<String>,
+    /// The dominant color of the album cover
+    #[allow(dead_code)]
+    pub vibrant_color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TidalTrack {
+    /// The Tidal id of the track
+    pub id: u64,
+    /// The title of the track
+    pub title: String,
+    /// The version of the track (ex: "Remastered"), null for the original one
+    pub version: Option<String>,
+    /// The duration of the track (seconds)
+    pub duration: u32,
+    /// The position of the track in its album
+    #[allow(dead_code)]
+    pub track_number: u32,
+    /// The disc number of the track
+    #[allow(dead_code)]
+    pub volume_number: u32,
+    /// The track ISRC
+    pub isrc: Option<String>,
+    /// Whether the track contains explicit lyrics
+    #[allow(dead_code)]
+    pub explicit: bool,
+    /// Whether the track can be streamed in the requested country
+    #[allow(dead_code)]
+    pub allow_streaming: bool,
+    /// Whether the track is ready to be streamed
+    #[allow(dead_code)]
+    pub stream_ready: bool,
+    /// The date from which the track is available (ex: 2024-05-24T00:00:00.000+0000)
+    pub stream_start_date: Option<String>,
+    /// The url of the track on Tidal
+    pub url: Url,
+    /// The best audio quality available (ex: LOSSLESS, HI_RES_LOSSLESS)
+    #[allow(dead_code)]
+    pub audio_quality: String,
+    /// The artists who performed the track
+    pub artists: Vec<TidalTrackArtist>,
+    /// The album on which the track appears
+    pub album: TidalTrackAlbum,
+}
+
+impl TryFrom<TidalTrack> for TrackWithAlbumAndArtists {
+    type Error = &'static str;
+
+    fn try_from(tidal_track: TidalTrack) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::TIDAL;
+        let mut ids = HashSet::new();
+
+        if let Some(isrc) = tidal_track.isrc.and_then(|isrc| Isrc::parse(&isrc).ok()) {
+            ids.insert(ProductId::ISRC(isrc));
+        }
+        ids.insert(ProductId::Provider((
+            provider_id.clone(),
+            tidal_track.id.to_string(),
+        )));
+
+        let name = match tidal_track.version {
+            Some(version) if !version.is_empty() => format!("{} ({})", tidal_track.title, version),
+            _ => tidal_track.title,
+        };
+
+        let mut urls = HashMap::new();
+
+        urls.insert(provider_id.clone(), tidal_track.url);
+
+        // Tracks returned in lists do not embed the album release date
+        let Some(stream_start_date) = tidal_track.stream_start_date else {
+            return Err("track.streamStartDate is missing");
+        };
+        let Ok(release_date) =
+            DateTime::parse_from_str(&stream_start_date, "%Y-%m-%dT%H:%M:%S%.3f%z")
+        else {
+            return Err("track.streamStartDate is corrupted");
+        };
+
+        let album = get_album(tidal_track.album, release_date.to_utc())?;
+        let artists = get_artists(tidal_track.artists)?;
+
+        Ok(TrackWithAlbumAndArtists::new(
+            ids,
+            name,
+            tidal_track.duration * 1000,
+            urls,
+            album,
+            artists,
+        ))
+    }
+}
+
+fn get_album(
+    tidal_album: TidalTrackAlbum,
+    release_date: DateTime<Utc>,
+) -> Result<Album, &'static str> {
+    let provider_id = ProviderId::TIDAL;
+    let mut ids = HashSet::new();
+
+    ids.insert(ProductId::Provider((
+        provider_id.clone(),
+        tidal_album.id.to_string(),
+    )));
+
+    let Ok(link) = format!("https://tidal.com/browse/album/{}", tidal_album.id).parse::<Url>()
+    else {
+        return Err("album.id is corrupted");
+    };
+
+    let mut urls = HashMap::new();
+
+    urls.insert(provider_id, link);
+
+    let covers = tidal_album
+        .cover
+        .map(|cover| square_covers(&cover))
+        .unwrap_or_default();
+
+    Ok(Album::new(
+        ids,
+        tidal_album.title,
+        release_date,
+        covers,
+        urls,
+    ))
+}
+
+fn get_artists(tidal_artists: Vec<TidalTrackArtist>) -> Result<Vec<Artist>, &'static str> {
+    tidal_artists
+        .into_iter()
+        .map(|tidal_artist| {
+            let provider_id = ProviderId::TIDAL;
+            let mut ids = HashMap::new();
+
+            ids.insert(provider_id.clone(), tidal_artist.id.to_string());
+
+            let Ok(link) =
+                format!("https://tidal.com/browse/artist/{}", tidal_artist.id).parse::<Url>()
+            else {
+                return Err("artist.id is corrupted");
+            };
+
+            let mut urls = HashMap::new();
+
+            urls.insert(provider_id, link);
+
+            Ok(Artist::new(ids, tidal_artist.name, urls))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::track::TrackWithAlbumAndArtists,
+        value_objects::{isrc::Isrc, product_id::ProductId},
+    };
+
+    use super::TidalTrack;
+
+    #[test]
+    fn test_deserialize_track() {
+        let payload = include_str!("../../tests/tidal/payload_track.json");
+        let json = serde_json::from_str::<TidalTrack>(payload).expect("valid json");
+
+        assert_eq!(json.title, "How Sweet");
+        assert_eq!(json.artists[0].name, "NewJeans");
+
+        let track = TrackWithAlbumAndArtists::try_from(json).expect("valid track");
+
+        assert!(track
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert_eq!(track.duration_ms(), 219_000);
+        assert_eq!(track.album().name(), "How Sweet");
+        assert_eq!(track.album().covers().len(), 5);
+    }
+}
@@ -0,0 +1,284 @@
+pub mod playlist;
+pub mod track;
+
+use std::{collections::HashSet, io::ErrorKind, path::PathBuf};
+
+use playlist::XspfPlaylist;
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{cover_image::CoverImage, image_cover::ImageCover, playlist_id::PlaylistId},
+};
+use tokio::fs;
+use track::XspfTrack;
+use url::Url;
+
+use crate::local::{file_url, io_error, new_playlist_id, playlist_file_name, playlist_ids};
+
+/// Flavour of the playlist files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XspfFormat {
+    /// XML, `.xspf` files
+    Xspf,
+    /// JSON, `.jspf` files
+    Jspf,
+}
+
+impl XspfFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            XspfFormat::Xspf => "xspf",
+            XspfFormat::Jspf => "jspf",
+        }
+    }
+
+    pub fn parse(&self, content: &str) -> Result<XspfPlaylist, String> {
+        match self {
+            XspfFormat::Xspf => XspfPlaylist::from_xspf(content),
+            XspfFormat::Jspf => XspfPlaylist::from_jspf(content),
+        }
+    }
+
+    pub fn serialize(&self, playlist: &XspfPlaylist) -> Result<String, String> {
+        match self {
+            XspfFormat::Xspf => playlist.to_xspf(),
+            XspfFormat::Jspf => playlist.to_jspf(),
+        }
+    }
+}
+
+/// Playlist repository backed by a directory of `.xspf` or `.jspf` files.
+///
+/// Each file is a playlist, its id being the file name without extension.
+/// Liked songs are stored in `favourites.xspf` (or `.jspf`).
+/// Track ids are the first locations of the tracks.
+pub struct XspfPlaylistRepository {
+    directory: PathBuf,
+    format: XspfFormat,
+}
+
+impl XspfPlaylistRepository {
+    pub fn new(directory: PathBuf, format: XspfFormat) -> Self {
+        Self { directory, format }
+    }
+
+    fn path(&self, id: &PlaylistId) -> PlaylistRepositoryResult<PathBuf> {
+        Ok(self.directory.join(format!(
+            "{}.{}",
+            playlist_file_name(id)?,
+            self.format.extension()
+        )))
+    }
+
+    async fn read(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<XspfPlaylist>> {
+        let path = self.path(id)?;
+
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(io_error(&path, err)),
+        };
+
+        self.format.parse(&content).map(Some).map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - {} - {}",
+                path.display(),
+                err
+            ))
+        })
+    }
+
+    async fn write(
+        &self,
+        id: &PlaylistId,
+        playlist: &XspfPlaylist,
+    ) -> PlaylistRepositoryResult<()> {
+        let path = self.path(id)?;
+        let content = self.format.serialize(playlist).map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!("PlaylistRepository - {}", err))
+        })?;
+
+        fs::write(&path, content)
+            .await
+            .map_err(|err| io_error(&path, err))
+    }
+
+    fn to_playlist(
+        &self,
+        id: &PlaylistId,
+        xspf_playlist: &XspfPlaylist,
+    ) -> PlaylistRepositoryResult<Playlist> {
+        let covers = xspf_playlist
+            .image
+            .as_ref()
+            .and_then(|image| image.parse::<Url>().ok())
+            .map(|url| HashSet::from_iter([ImageCover::Other(url)]))
+            .unwrap_or_default();
+
+        let mut playlist = Playlist::new(
+            id.clone(),
+            xspf_playlist.title.clone().unwrap_or(id.to_string()),
+            covers,
+            xspf_playlist.creator.clone().unwrap_or("me".to_string()),
+            xspf_playlist.track.len() as u32,
+            file_url(&self.path(id)?)?,
+        );
+
+        playlist.set_description(xspf_playlist.annotation.clone());
+
+        Ok(playlist)
+    }
+
+    /// Append tracks with their metadata (title, artists, album, duration, ISRC).
+    ///
+    /// Used to back up streaming playlists, [`PlaylistRepository::add_tracks`]
+    /// only knows the locations.
+    pub async fn add_track_entries(
+        &self,
+        playlist_id: &PlaylistId,
+        tracks: &[TrackWithAlbumAndArtists],
+    ) -> PlaylistRepositoryResult<()> {
+        let mut xspf_playlist = self.read(playlist_id).await?.unwrap_or_default();
+
+        xspf_playlist.track.extend(tracks.iter().map(Into::into));
+
+        self.write(playlist_id, &xspf_playlist).await
+    }
+}
+
+/// Title, annotation & image of the playlist, embedded JPEG covers can't be stored
+fn set_metadata(xspf_playlist: &mut XspfPlaylist, spec: &PlaylistSpec) {
+    xspf_playlist.title = Some(spec.name.clone());
+
+    if let Some(description) = &spec.description {
+        xspf_playlist.annotation = Some(description.clone());
+    }
+    if let Some(CoverImage::Url(url)) = &spec.cover {
+        xspf_playlist.image = Some(url.to_string());
+    }
+}
+
+impl PlaylistRepository for XspfPlaylistRepository {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match self.read(id).await? {
+            Some(xspf_playlist) => Ok(Some(self.to_playlist(id, &xspf_playlist)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let ids = playlist_ids(&self.directory, &[self.format.extension()]).await?;
+        let mut playlists = vec![];
+
+        for id in ids {
+            if let Some(playlist) = self.get(&id).await? {
+                playlists.push(playlist);
+            }
+        }
+
+        Ok(playlists)
+    }
+
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let id = new_playlist_id(&spec.name);
+
+        if fs::try_exists(self.path(&id)?).await.unwrap_or(false) {
+            return Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist {} already exists",
+                id
+            )));
+        }
+
+        let mut xspf_playlist = XspfPlaylist::default();
+        set_metadata(&mut xspf_playlist, spec);
+
+        self.write(&id, &xspf_playlist).await?;
+        self.to_playlist(&id, &xspf_playlist)
+    }
+
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let Some(mut xspf_playlist) = self.read(id).await? else {
+            return Err(PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Playlist {} not found",
+                id
+            )));
+        };
+
+        set_metadata(&mut xspf_playlist, spec);
+
+        self.write(id, &xspf_playlist).await
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        let Some(playlist) = self.get(id).await? else {
+            return Ok(None);
+        };
+
+        let path = self.path(id)?;
+
+        fs::remove_file(&path)
+            .await
+            .map_err(|err| io_error(&path, err))?;
+
+        Ok(Some(playlist))
+    }
+
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        let mut xspf_playlist = match (self.read(playlist_id).await?, playlist_id) {
+            (Some(xspf_playlist), _) => xspf_playlist,
+            (None, PlaylistId::LikedSongs) => XspfPlaylist::default(),
+            (None, PlaylistId::Owned(_)) => {
+                return Err(PlaylistRepositoryError::ServiceError(format!(
+                    "PlaylistRepository - Playlist {} not found",
+                    playlist_id
+                )))
+            }
+        };
+
+        xspf_playlist
+            .track
+            .extend(ids.iter().map(|id| XspfTrack::from_location(id)));
+
+        self.write(playlist_id, &xspf_playlist).await
+    }
+
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        let Some(mut xspf_playlist) = self.read(playlist_id).await? else {
+            return Ok(());
+        };
+
+        xspf_playlist
+            .track
+            .retain(|track| !track.id().is_some_and(|id| ids.contains(id)));
+
+        self.write(playlist_id, &xspf_playlist).await
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let xspf_playlist = self.read(playlist_id).await?.unwrap_or_default();
+
+        Ok(xspf_playlist.track.into_iter().map(Into::into).collect())
+    }
+}
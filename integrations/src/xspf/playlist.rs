@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use super::track::XspfTrack;
+
+static XSPF_VERSION: &str = "1";
+static XSPF_NAMESPACE: &str = "http://xspf.org/ns/0/";
+static XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+/// A XSPF playlist (<https://xspf.org/spec>), shaped as its JSON sibling JSPF
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XspfPlaylist {
+    /// The name of the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The author of the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// A human-readable comment on the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    /// URI of a web page to find out more about the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+    /// Source URI of the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Canonical id of the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// URI of an image to display for the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Creation date of the playlist (XML schema dateTime)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// The tracks of the playlist, named "trackList" in XSPF
+    #[serde(default)]
+    pub track: Vec<XspfTrack>,
+}
+
+/// JSPF document, the playlist is wrapped in a "playlist" object
+#[derive(Debug, Serialize, Deserialize)]
+struct JspfDocument {
+    playlist: XspfPlaylist,
+}
+
+/// XSPF document, tracks are wrapped in a "trackList" element
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "playlist")]
+struct XspfDocument {
+    #[serde(rename = "@version")]
+    version: String,
+    #[serde(rename = "@xmlns", default)]
+    xmlns: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(rename = "trackList", default)]
+    track_list: XspfTrackList,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XspfTrackList {
+    #[serde(default)]
+    track: Vec<XspfTrack>,
+}
+
+impl XspfPlaylist {
+    pub fn from_xspf(content: &str) -> Result<Self, String> {
+        let document = quick_xml::de::from_str::<XspfDocument>(content)
+            .map_err(|err| format!("Invalid XSPF playlist - {}", err))?;
+
+        Ok(Self {
+            title: document.title,
+            creator: document.creator,
+            annotation: document.annotation,
+            info: document.info,
+            location: document.location,
+            identifier: document.identifier,
+            image: document.image,
+            date: document.date,
+            track: document.track_list.track,
+        })
+    }
+
+    pub fn to_xspf(&self) -> Result<String, String> {
+        let document = XspfDocument {
+            version: XSPF_VERSION.to_string(),
+            xmlns: XSPF_NAMESPACE.to_string(),
+            title: self.title.clone(),
+            creator: self.creator.clone(),
+            annotation: self.annotation.clone(),
+            info: self.info.clone(),
+            location: self.location.clone(),
+            identifier: self.identifier.clone(),
+            image: self.image.clone(),
+            date: self.date.clone(),
+            track_list: XspfTrackList {
+                track: self.track.clone(),
+            },
+        };
+
+        let mut content = format!("{}\n", XML_DECLARATION);
+        let mut serializer = quick_xml::se::Serializer::new(&mut content);
+
+        serializer.indent(' ', 2);
+        document
+            .serialize(serializer)
+            .map_err(|err| format!("Could not serialize XSPF playlist - {}", err))?;
+        content.push('\n');
+
+        Ok(content)
+    }
+
+    pub fn from_jspf(content: &str) -> Result<Self, String> {
+        serde_json::from_str::<JspfDocument>(content)
+            .map(|document| document.playlist)
+            .map_err(|err| format!("Invalid JSPF playlist - {}", err))
+    }
+
+    pub fn to_jspf(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&JspfDocument {
+            playlist: self.clone(),
+        })
+        .map_err(|err| format!("Could not serialize JSPF playlist - {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XspfPlaylist;
+
+    #[test]
+    fn test_parse_xspf() {
+        let content = include_str!("../../tests/xspf/playlist.xspf");
+        let playlist = XspfPlaylist::from_xspf(content).expect("valid playlist");
+
+        assert_eq!(playlist.title, Some("Women of Rap".to_string()));
+        assert_eq!(playlist.track.len(), 3);
+        assert_eq!(playlist.track[0].isrc(), Some("USA2P2414843"));
+        assert_eq!(playlist.track[0].track_num, Some(1));
+        assert_eq!(playlist.track[1].location.len(), 2);
+        assert_eq!(playlist.track[1].isrc(), Some("USAT21900366"));
+        assert_eq!(
+            playlist.track[1].album,
+            Some("It Was Good Until It Wasn't".to_string())
+        );
+        assert_eq!(playlist.track[2].title, None);
+    }
+
+    #[test]
+    fn test_parse_jspf() {
+        let xspf = XspfPlaylist::from_xspf(include_str!("../../tests/xspf/playlist.xspf"))
+            .expect("valid playlist");
+        let jspf = XspfPlaylist::from_jspf(include_str!("../../tests/xspf/playlist.jspf"))
+            .expect("valid playlist");
+
+        assert_eq!(xspf, jspf);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let playlist = XspfPlaylist::from_xspf(include_str!("../../tests/xspf/playlist.xspf"))
+            .expect("valid playlist");
+
+        let xspf = playlist.to_xspf().expect("serialized playlist");
+
+        assert_eq!(
+            XspfPlaylist::from_xspf(&xspf).expect("valid playlist"),
+            playlist
+        );
+
+        let jspf = playlist.to_jspf().expect("serialized playlist");
+
+        assert_eq!(
+            XspfPlaylist::from_jspf(&jspf).expect("valid playlist"),
+            playlist
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_xspf() {
+        assert!(XspfPlaylist::from_xspf("<playlist><trackList>").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{image_cover::ImageCover, isrc::Isrc, product_id::ProductId},
+};
+use url::Url;
+
+use crate::local::provider_id;
+
+/// Prefixes of the identifiers holding an ISRC, the first one is used when serializing
+static ISRC_PREFIXES: [&str; 2] = ["isrc:", "urn:isrc:"];
+
+/// A track of a XSPF / JSPF playlist, every field is optional
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XspfTrack {
+    /// URIs of the resource to be rendered (file, stream, provider page...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub location: Vec<String>,
+    /// Canonical ids of the track (ex: isrc:USA2P2414843, MusicBrainz recording URL)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub identifier: Vec<String>,
+    /// The name of the track
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The performing artist(s), comma separated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// A human-readable comment on the track
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    /// URI of a place where this track may be bought or get more info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+    /// URI of an image to display for the track, usually the album cover
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// The name of the album on which the track appears
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+    /// The position of the track on its album
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_num: Option<u32>,
+    /// The track's duration (milliseconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+}
+
+impl XspfTrack {
+    /// Track without metadata, only known by its location
+    pub fn from_location(location: &str) -> Self {
+        Self {
+            location: vec![location.to_string()],
+            ..Default::default()
+        }
+    }
+
+    /// Id of the track in the playlist: its first location, otherwise its first identifier
+    pub fn id(&self) -> Option<&String> {
+        self.location.first().or(self.identifier.first())
+    }
+
+    pub fn isrc(&self) -> Option<&str> {
+        self.identifier.iter().find_map(|identifier| {
+            ISRC_PREFIXES
+                .iter()
+                .find_map(|prefix| identifier.strip_prefix(prefix))
+        })
+    }
+}
+
+impl From<XspfTrack> for TrackWithAlbumAndArtists {
+    fn from(xspf_track: XspfTrack) -> Self {
+        let provider_id = provider_id();
+        let mut ids = HashSet::new();
+
+        if let Some(isrc) = xspf_track.isrc().and_then(|isrc| Isrc::parse(isrc).ok()) {
+            ids.insert(ProductId::ISRC(isrc));
+        }
+
+        if let Some(id) = xspf_track.id() {
+            ids.insert(ProductId::Provider((provider_id.clone(), id.clone())));
+        }
+
+        let mut urls = HashMap::new();
+
+        // Relative paths are not valid URIs
+        if let Some(url) = xspf_track
+            .location
+            .iter()
+            .find_map(|location| location.parse::<Url>().ok())
+        {
+            urls.insert(provider_id, url);
+        }
+
+        let artists = xspf_track
+            .creator
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| Artist::new(HashMap::new(), name.to_string(), HashMap::new()))
+            .collect();
+
+        let covers = xspf_track
+            .image
+            .and_then(|image| image.parse::<Url>().ok())
+            .map(|url| HashSet::from_iter([ImageCover::Other(url)]))
+            .unwrap_or_default();
+
+        // Release date is unknown from playlist files
+        let album = Album::new(
+            HashSet::new(),
+            xspf_track.album.unwrap_or_default(),
+            DateTime::<Utc>::default(),
+            covers,
+            HashMap::new(),
+        );
+
+        let title = match xspf_track.title {
+            Some(title) => title,
+            None => xspf_track
+                .location
+                .first()
+                .and_then(|location| location.rsplit('/').next())
+                .unwrap_or_default()
+                .to_string(),
+        };
+
+        TrackWithAlbumAndArtists::new(
+            ids,
+            title,
+            xspf_track.duration.unwrap_or_default(),
+            urls,
+            album,
+            artists,
+        )
+    }
+}
+
+impl From<&TrackWithAlbumAndArtists> for XspfTrack {
+    fn from(track: &TrackWithAlbumAndArtists) -> Self {
+        let mut location = track
+            .provider_urls()
+            .values()
+            .map(Url::to_string)
+            .collect::<Vec<_>>();
+
+        location.sort();
+
+        let identifier = track
+            .ids()
+            .iter()
+            .filter_map(|id| match id {
+                ProductId::ISRC(isrc) => Some(format!("{}{}", ISRC_PREFIXES[0], isrc)),
+                _ => None,
+            })
+            .collect();
+
+        let creator = track
+            .artists()
+            .iter()
+            .map(|artist| artist.name().as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let image = track
+            .album()
+            .covers()
+            .iter()
+            .max_by_key(|cover| match cover {
+                ImageCover::Lg(_) => 4,
+                ImageCover::Default(_) => 3,
+                ImageCover::Md(_) => 2,
+                ImageCover::Other(_) => 1,
+                ImageCover::Sm(_) => 0,
+            })
+            .map(|cover| cover.url().to_string());
+
+        Self {
+            location,
+            identifier,
+            title: Some(track.name().clone()),
+            creator: Some(creator).filter(|creator| !creator.is_empty()),
+            image,
+            album: Some(track.album().name().clone()).filter(|album| !album.is_empty()),
+            duration: Some(track.duration_ms()).filter(|duration| *duration > 0),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{
+        entities::track::TrackWithAlbumAndArtists,
+        value_objects::{isrc::Isrc, product_id::ProductId},
+    };
+
+    use super::XspfTrack;
+
+    #[test]
+    fn test_track_round_trip() {
+        let xspf_track = XspfTrack {
+            location: vec!["https://www.deezer.com/track/630286212".to_string()],
+            identifier: vec!["urn:isrc:USAT21900366".to_string()],
+            title: Some("Nights Like This".to_string()),
+            creator: Some("Kehlani, Ty Dolla $ign".to_string()),
+            album: Some("It Was Good Until It Wasn't".to_string()),
+            duration: Some(201_000),
+            ..Default::default()
+        };
+
+        let track = TrackWithAlbumAndArtists::from(xspf_track);
+
+        assert!(track
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USAT21900366").unwrap())));
+        assert_eq!(track.artists().len(), 2);
+        assert_eq!(track.artists()[1].name(), "Ty Dolla $ign");
+
+        let xspf_track = XspfTrack::from(&track);
+
+        assert_eq!(
+            xspf_track.location,
+            vec!["https://www.deezer.com/track/630286212".to_string()]
+        );
+        assert_eq!(xspf_track.identifier, vec!["isrc:USAT21900366".to_string()]);
+        assert_eq!(
+            xspf_track.creator,
+            Some("Kehlani, Ty Dolla $ign".to_string())
+        );
+        assert_eq!(xspf_track.duration, Some(201_000));
+    }
+}
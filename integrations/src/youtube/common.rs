@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use snk_core::value_objects::image_cover::ImageCover;
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePageInfo {
+    /// The total number of results in the result set.
+    pub total_results: u32,
+    /// The number of results included in the API response.
+    #[allow(dead_code)]
+    pub results_per_page: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubeList<T> {
+    /// The token that can be used as the value of the pageToken parameter to retrieve the next page in the result set.
+    pub next_page_token: Option<String>,
+    /// Paging information for the result set.
+    pub page_info: YoutubePageInfo,
+    /// A list of resources that match the request criteria.
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeThumbnail {
+    /// The image's URL.
+    pub url: Url,
+    /// The image's width.
+    #[allow(dead_code)]
+    pub width: Option<u32>,
+    /// The image's height.
+    #[allow(dead_code)]
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeThumbnails {
+    /// 120px wide and 90px tall (video), 88px wide and 88px tall (channel)
+    pub default: Option<YoutubeThumbnail>,
+    /// 320px wide and 180px tall (video), 240px wide and 240px tall (channel)
+    pub medium: Option<YoutubeThumbnail>,
+    /// 480px wide and 360px tall (video), 800px wide and 800px tall (channel)
+    pub high: Option<YoutubeThumbnail>,
+    /// 640px wide and 480px tall
+    pub standard: Option<YoutubeThumbnail>,
+    /// 1280px wide and 720px tall
+    pub maxres: Option<YoutubeThumbnail>,
+}
+
+impl From<YoutubeThumbnails> for HashSet<ImageCover> {
+    fn from(thumbnails: YoutubeThumbnails) -> Self {
+        let mut covers = HashSet::new();
+
+        // Default cover is the biggest available one
+        if let Some(thumbnail) = thumbnails
+            .maxres
+            .as_ref()
+            .or(thumbnails.standard.as_ref())
+            .or(thumbnails.high.as_ref())
+        {
+            covers.insert(ImageCover::Default(thumbnail.url.clone()));
+        }
+
+        if let Some(thumbnail) = thumbnails.default {
+            covers.insert(ImageCover::Sm(thumbnail.url));
+        }
+        if let Some(thumbnail) = thumbnails.medium {
+            covers.insert(ImageCover::Md(thumbnail.url));
+        }
+        if let Some(thumbnail) = thumbnails.high {
+            covers.insert(ImageCover::Lg(thumbnail.url));
+        }
+        if let Some(thumbnail) = thumbnails.maxres {
+            covers.insert(ImageCover::Other(thumbnail.url));
+        }
+
+        covers
+    }
+}
+
+/// Parse an ISO 8601 duration (ex: `PT1H3M25S`) as returned in `contentDetails.duration`
+/// into milliseconds
+pub fn parse_iso8601_duration(value: &str) -> Result<u32, &'static str> {
+    let Some(value) = value.strip_prefix('P') else {
+        return Err("duration: missing 'P' designator");
+    };
+
+    let mut total_seconds: u32 = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            designator => {
+                let amount = number
+                    .parse::<u32>()
+                    .map_err(|_| "duration: invalid number")?;
+                number.clear();
+
+                let seconds = match (in_time, designator) {
+                    (false, 'W') => amount.checked_mul(7 * 24 * 3600),
+                    (false, 'D') => amount.checked_mul(24 * 3600),
+                    (true, 'H') => amount.checked_mul(3600),
+                    (true, 'M') => amount.checked_mul(60),
+                    (true, 'S') => Some(amount),
+                    _ => return Err("duration: unsupported designator"),
+                };
+
+                total_seconds = seconds
+                    .and_then(|seconds| total_seconds.checked_add(seconds))
+                    .ok_or("duration: overflow")?;
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err("duration: dangling number");
+    }
+
+    total_seconds.checked_mul(1000).ok_or("duration: overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_iso8601_duration;
+
+    #[test]
+    fn test_parse_iso8601_duration() {
+        assert_eq!(parse_iso8601_duration("PT3M39S"), Ok(219_000));
+        assert_eq!(parse_iso8601_duration("PT1H2M3S"), Ok(3_723_000));
+        assert_eq!(parse_iso8601_duration("PT45S"), Ok(45_000));
+        assert_eq!(parse_iso8601_duration("P0D"), Ok(0));
+        assert!(parse_iso8601_duration("3M39S").is_err());
+        assert!(parse_iso8601_duration("PT3X").is_err());
+        assert!(parse_iso8601_duration("PT9999999H").is_err());
+        assert!(parse_iso8601_duration("PT1194H").is_err());
+    }
+}
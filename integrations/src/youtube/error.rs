@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeErrorPayload {
+    pub error: YoutubeError,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeError {
+    /// HTTP status code of the error
+    pub code: u16,
+    /// Human readable description of the error
+    pub message: String,
+    /// Details of each error raised by the request
+    #[allow(dead_code)]
+    pub errors: Vec<YoutubeErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YoutubeErrorDetail {
+    #[allow(dead_code)]
+    pub domain: String,
+    /// Machine readable reason (ex: quotaExceeded, playlistNotFound)
+    #[allow(dead_code)]
+    pub reason: String,
+    #[allow(dead_code)]
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::YoutubeErrorPayload;
+
+    #[test]
+    fn test_deserialize_error() {
+        let json_str = "{\"error\":{\"code\":404,\"message\":\"The playlist identified with the request's <code>playlistId</code> parameter cannot be found.\",\"errors\":[{\"message\":\"The playlist identified with the request's <code>playlistId</code> parameter cannot be found.\",\"domain\":\"youtube.playlistItem\",\"reason\":\"playlistNotFound\",\"location\":\"playlistId\",\"locationType\":\"parameter\"}]}}";
+        let json = serde_json::from_str::<YoutubeErrorPayload>(json_str).expect("valid json");
+
+        assert_eq!(json.error.code, 404);
+        assert_eq!(json.error.errors[0].reason, "playlistNotFound");
+    }
+}
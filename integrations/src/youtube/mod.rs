@@ -0,0 +1,433 @@
+pub mod common;
+pub mod error;
+pub mod playlist;
+pub mod video;
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use common::YoutubeList;
+use error::YoutubeErrorPayload;
+use playlist::{YoutubePlaylist, YoutubePlaylistItem};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client, RequestBuilder, Response,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use snk_core::{
+    contracts::repositories::playlist_repository::{
+        PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+    },
+    entities::{
+        music_account_provider::MusicAccountProvider,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::{image_cover::ImageCover, playlist_id::PlaylistId},
+};
+use url::Url;
+use video::YoutubeVideo;
+
+use crate::chunks::send_chunks;
+
+static API_URL: &str = "https://www.googleapis.com/youtube/v3";
+
+/// Maximum page size & number of ids per request accepted by the YouTube Data API
+static MAX_RESULTS: usize = 50;
+
+pub struct YoutubePlaylistRepository<'a> {
+    http_client: Client,
+    #[allow(dead_code)]
+    music_account_provider: &'a MusicAccountProvider,
+}
+
+impl<'a> YoutubePlaylistRepository<'a> {
+    pub fn new(
+        music_account_provider: &'a MusicAccountProvider,
+        access_token: String,
+    ) -> Result<Self, &'static str> {
+        let mut default_headers = HeaderMap::new();
+
+        default_headers.insert("Accept", HeaderValue::from_static("application/json"));
+        default_headers.insert(
+            "Authorization",
+            format!("Bearer {}", access_token).parse().map_err(|err| {
+                eprintln!("{:?}", err);
+                "YoutubePlaylistRepository::new: Could not parse header value"
+            })?,
+        );
+
+        Ok(Self {
+            http_client: Client::builder()
+                .connect_timeout(Duration::from_secs(5))
+                .default_headers(default_headers)
+                .build()
+                .map_err(|err| {
+                    eprintln!("{:?}", err);
+                    "YoutubePlaylistRepository::new: Could not init HTTP client"
+                })?,
+            music_account_provider,
+        })
+    }
+
+    /// Send request & turn YouTube error payloads into [`PlaylistRepositoryError`]
+    async fn send(&self, request: RequestBuilder) -> PlaylistRepositoryResult<Response> {
+        let response = request.send().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to send request - {:?}",
+                err
+            ))
+        })?;
+
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let message = match response.json::<YoutubeErrorPayload>().await {
+            Ok(payload) => payload.error.message,
+            Err(_) => status.to_string(),
+        };
+
+        Err(PlaylistRepositoryError::ServiceError(format!(
+            "PlaylistRepository - Error during request - {} ({})",
+            message, status
+        )))
+    }
+
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> PlaylistRepositoryResult<T> {
+        self.send(request).await?.json::<T>().await.map_err(|err| {
+            PlaylistRepositoryError::ServiceError(format!(
+                "PlaylistRepository - Failed to parse response - {:?}",
+                err
+            ))
+        })
+    }
+
+    /// Fetch the liked music videos of the user
+    async fn liked_videos(&self) -> PlaylistRepositoryResult<Vec<YoutubeVideo>> {
+        Ok(self
+            .fetch_all::<YoutubeVideo>(
+                "videos",
+                &[("part", "snippet,contentDetails"), ("myRating", "like")],
+            )
+            .await?
+            .into_iter()
+            // Liked videos also contain non-music videos
+            .filter(YoutubeVideo::is_music)
+            .collect())
+    }
+
+    /// Fetch every page of a list endpoint by following `nextPageToken`
+    async fn fetch_all<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        query: &[(&str, &str)],
+    ) -> PlaylistRepositoryResult<Vec<T>> {
+        let mut items = vec![];
+        let mut page_token: Option<String> = None;
+        let max_results = MAX_RESULTS.to_string();
+
+        loop {
+            let mut request = self
+                .http_client
+                .get(format!("{}/{}", API_URL, endpoint))
+                .query(query)
+                .query(&[("maxResults", max_results.as_str())]);
+
+            if let Some(page_token) = &page_token {
+                request = request.query(&[("pageToken", page_token)]);
+            }
+
+            let page = self.fetch::<YoutubeList<T>>(request).await?;
+
+            items.extend(page.items);
+
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => break,
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Fetch videos details, keeping the order of the given ids and skipping unavailable videos
+    async fn get_videos(&self, ids: &[String]) -> PlaylistRepositoryResult<Vec<YoutubeVideo>> {
+        let mut videos = HashMap::new();
+
+        for chunk in ids.chunks(MAX_RESULTS) {
+            let page = self
+                .fetch::<YoutubeList<YoutubeVideo>>(
+                    self.http_client
+                        .get(format!("{}/videos", API_URL))
+                        .query(&[("part", "snippet,contentDetails"), ("id", &chunk.join(","))]),
+                )
+                .await?;
+
+            videos.extend(
+                page.items
+                    .into_iter()
+                    .map(|video| (video.id.clone(), video)),
+            );
+        }
+
+        Ok(ids.iter().filter_map(|id| videos.remove(id)).collect())
+    }
+
+    /// Rate videos: "like" adds videos to the liked music, "none" removes them
+    async fn rate(&self, ids: &[String], rating: &str) -> PlaylistRepositoryResult<()> {
+        // The API only supports rating one video per request
+        send_chunks(ids.chunks(1).collect(), |chunk| async move {
+            self.send(
+                self.http_client
+                    .post(format!("{}/videos/rate", API_URL))
+                    .query(&[("id", chunk[0].as_str()), ("rating", rating)]),
+            )
+            .await
+            .map(|_| ())
+        })
+        .await
+    }
+}
+
+/// Playlist resource with the snippet & status of the spec, private unless public
+fn playlist_resource(id: Option<&String>, spec: &PlaylistSpec) -> Value {
+    let mut resource = json!({
+        "snippet": {
+            "title": spec.name,
+            "description": spec.description.as_deref().unwrap_or_default(),
+        },
+        "status": {
+            "privacyStatus": match spec.public {
+                Some(true) => "public",
+                _ => "private",
+            },
+        },
+    });
+
+    if let Some(id) = id {
+        resource["id"] = json!(id);
+    }
+
+    resource
+}
+
+impl PlaylistRepository for YoutubePlaylistRepository<'_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => {
+                // Counted like `get_tracks` lists them, without the non-music videos
+                let liked_videos = self.liked_videos().await?;
+
+                Ok(Some(Playlist::new(
+                    id.clone(),
+                    id.to_string(),
+                    HashSet::from_iter([ImageCover::Other(
+                        "https://cdn.icon-icons.com/icons2/72/PNG/256/favourite_14390.png"
+                            .parse::<Url>()
+                            .map_err(|err| {
+                                PlaylistRepositoryError::ServiceError(err.to_string())
+                            })?,
+                    )]),
+                    "me".to_string(),
+                    liked_videos.len() as u32,
+                    "https://music.youtube.com/playlist?list=LM"
+                        .parse::<Url>()
+                        .map_err(|err| PlaylistRepositoryError::ServiceError(err.to_string()))?,
+                )))
+            }
+            PlaylistId::Owned(youtube_id) => {
+                let request = self
+                    .http_client
+                    .get(format!("{}/playlists", API_URL))
+                    .query(&[
+                        ("part", "snippet,contentDetails,status"),
+                        ("id", youtube_id.as_str()),
+                    ]);
+
+                // Unknown ids are answered with an empty list
+                let playlists = self.fetch::<YoutubeList<YoutubePlaylist>>(request).await?;
+
+                Ok(playlists.items.into_iter().next().map(Into::into))
+            }
+        }
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        let playlists = self
+            .fetch_all::<YoutubePlaylist>(
+                "playlists",
+                &[("part", "snippet,contentDetails,status"), ("mine", "true")],
+            )
+            .await?;
+
+        Ok(playlists.into_iter().map(Into::into).collect())
+    }
+
+    /// Playlists are private unless public in the spec, the cover of the spec is ignored
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        let playlist = self
+            .fetch::<YoutubePlaylist>(
+                self.http_client
+                    .post(format!("{}/playlists", API_URL))
+                    .query(&[("part", "snippet,contentDetails,status")])
+                    .json(&playlist_resource(None, spec)),
+            )
+            .await?;
+
+        Ok(playlist.into())
+    }
+
+    /// The title, description & privacy are replaced, the cover of the spec is ignored
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        let PlaylistId::Owned(youtube_id) = id else {
+            return Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            ));
+        };
+
+        self.send(
+            self.http_client
+                .put(format!("{}/playlists", API_URL))
+                .query(&[("part", "snippet,status")])
+                .json(&playlist_resource(Some(youtube_id), spec)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        match id {
+            PlaylistId::LikedSongs => Err(PlaylistRepositoryError::ServiceError(
+                "operation not permitted with favourite tracks list".to_string(),
+            )),
+            PlaylistId::Owned(youtube_id) => {
+                let Some(playlist) = self.get(id).await? else {
+                    return Ok(None);
+                };
+
+                self.send(
+                    self.http_client
+                        .delete(format!("{}/playlists", API_URL))
+                        .query(&[("id", youtube_id)]),
+                )
+                .await?;
+
+                Ok(Some(playlist))
+            }
+        }
+    }
+
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        match playlist_id {
+            PlaylistId::LikedSongs => self.rate(ids, "like").await,
+            PlaylistId::Owned(youtube_id) => {
+                // The API only supports inserting one item per request
+                send_chunks(ids.chunks(1).collect(), |chunk| async move {
+                    self.send(
+                        self.http_client
+                            .post(format!("{}/playlistItems", API_URL))
+                            .query(&[("part", "snippet")])
+                            .json(&json!({
+                                "snippet": {
+                                    "playlistId": youtube_id,
+                                    "resourceId": {
+                                        "kind": "youtube#video",
+                                        "videoId": chunk[0],
+                                    },
+                                },
+                            })),
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await
+            }
+        }
+    }
+
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        _snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        match playlist_id {
+            PlaylistId::LikedSongs => self.rate(ids, "none").await,
+            PlaylistId::Owned(youtube_id) => {
+                // Playlist items have their own ids, different from the video ones
+                let items = self
+                    .fetch_all::<YoutubePlaylistItem>(
+                        "playlistItems",
+                        &[("part", "contentDetails"), ("playlistId", youtube_id)],
+                    )
+                    .await?;
+
+                let items = &items;
+
+                // One chunk per video, removing all of its occurrences
+                send_chunks(ids.chunks(1).collect(), |chunk| async move {
+                    for item in items
+                        .iter()
+                        .filter(|item| item.content_details.video_id == chunk[0])
+                    {
+                        self.send(
+                            self.http_client
+                                .delete(format!("{}/playlistItems", API_URL))
+                                .query(&[("id", &item.id)]),
+                        )
+                        .await?;
+                    }
+
+                    Ok(())
+                })
+                .await
+            }
+        }
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        let videos = match playlist_id {
+            PlaylistId::LikedSongs => self.liked_videos().await?,
+            PlaylistId::Owned(youtube_id) => {
+                let video_ids = self
+                    .fetch_all::<YoutubePlaylistItem>(
+                        "playlistItems",
+                        &[("part", "contentDetails"), ("playlistId", youtube_id)],
+                    )
+                    .await?
+                    .into_iter()
+                    .map(|item| item.content_details.video_id)
+                    .collect::<Vec<_>>();
+
+                self.get_videos(&video_ids).await?
+            }
+        };
+
+        videos
+            .into_iter()
+            .map(TrackWithAlbumAndArtists::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err: &'static str| PlaylistRepositoryError::ServiceError(err.to_string()))
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use snk_core::{entities::playlist::Playlist, value_objects::playlist_id::PlaylistId};
+use url::Url;
+
+use super::common::YoutubeThumbnails;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylistSnippet {
+    /// The date and time that the playlist was created.
+    #[allow(dead_code)]
+    pub published_at: DateTime<Utc>,
+    /// The ID that YouTube uses to uniquely identify the channel that published the playlist.
+    #[allow(dead_code)]
+    pub channel_id: String,
+    /// The playlist's title.
+    pub title: String,
+    /// The playlist's description.
+    pub description: String,
+    /// A map of thumbnail images associated with the playlist.
+    pub thumbnails: YoutubeThumbnails,
+    /// The channel title of the channel that the video belongs to.
+    pub channel_title: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylistContentDetails {
+    /// The number of videos in the playlist.
+    pub item_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylistStatus {
+    /// The playlist's privacy status (private / public / unlisted)
+    pub privacy_status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylist {
+    /// The ID that YouTube uses to uniquely identify the playlist.
+    pub id: String,
+    /// Basic details about the playlist, such as its title and description.
+    pub snippet: YoutubePlaylistSnippet,
+    /// Information about the playlist content, including the number of videos in the playlist.
+    pub content_details: Option<YoutubePlaylistContentDetails>,
+    /// Status information for the playlist.
+    pub status: Option<YoutubePlaylistStatus>,
+}
+
+impl From<YoutubePlaylist> for Playlist {
+    fn from(youtube_playlist: YoutubePlaylist) -> Self {
+        let provider_url = Url::parse_with_params(
+            "https://music.youtube.com/playlist",
+            [("list", youtube_playlist.id.as_str())],
+        )
+        .expect("valid playlist url");
+        let playlist_id = PlaylistId::Owned(youtube_playlist.id);
+        let name = youtube_playlist.snippet.title;
+        let owner = youtube_playlist.snippet.channel_title;
+        let total_songs = youtube_playlist
+            .content_details
+            .map(|content_details| content_details.item_count)
+            .unwrap_or_default();
+        let covers = youtube_playlist.snippet.thumbnails.into();
+
+        let mut playlist =
+            Playlist::new(playlist_id, name, covers, owner, total_songs, provider_url);
+
+        playlist.set_description(Some(youtube_playlist.snippet.description));
+        playlist.set_public(
+            youtube_playlist
+                .status
+                .map(|status| status.privacy_status == "public"),
+        );
+
+        playlist
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylistItemContentDetails {
+    /// The ID that YouTube uses to uniquely identify a video.
+    pub video_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubePlaylistItem {
+    /// The ID that YouTube uses to uniquely identify the playlist item.
+    pub id: String,
+    /// Information about the video referenced by the playlist item.
+    pub content_details: YoutubePlaylistItemContentDetails,
+}
+
+#[cfg(test)]
+mod tests {
+    use snk_core::{entities::playlist::Playlist, value_objects::playlist_id::PlaylistId};
+
+    use crate::youtube::common::YoutubeList;
+
+    use super::{YoutubePlaylist, YoutubePlaylistItem};
+
+    #[test]
+    fn test_deserialize_playlist() {
+        let payload = include_str!("../../tests/youtube/payload_playlist.json");
+        let json = serde_json::from_str::<YoutubePlaylist>(payload).expect("valid json");
+
+        assert_eq!(json.snippet.title, "Women of Rap");
+
+        let playlist: Playlist = json.into();
+
+        assert!(
+            matches!(playlist.id(), PlaylistId::Owned(id) if id == "PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf")
+        );
+        assert_eq!(playlist.total_songs(), 3);
+        assert_eq!(
+            playlist.description(),
+            Some(&"The best of women in rap".to_string())
+        );
+        assert_eq!(playlist.public(), Some(false));
+        assert_eq!(
+            playlist.provider_url().as_str(),
+            "https://music.youtube.com/playlist?list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_playlist_items() {
+        let payload = include_str!("../../tests/youtube/payload_playlist_items.json");
+        let json =
+            serde_json::from_str::<YoutubeList<YoutubePlaylistItem>>(payload).expect("valid json");
+
+        assert_eq!(json.items.len(), 2);
+        assert_eq!(json.items[0].content_details.video_id, "Gv5iHqQXYVE");
+        assert_eq!(json.next_page_token, Some("EAAaBlBUOkNBSQ".to_string()));
+    }
+}
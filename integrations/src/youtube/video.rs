@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use snk_core::{
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+};
+use url::Url;
+
+use super::common::{parse_iso8601_duration, YoutubeThumbnails};
+
+/// YouTube category id of music videos
+pub static MUSIC_CATEGORY_ID: &str = "10";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubeVideoSnippet {
+    /// The date and time that the video was published.
+    pub published_at: DateTime<Utc>,
+    /// The ID that YouTube uses to uniquely identify the channel that the video was uploaded to.
+    pub channel_id: String,
+    /// The video's title.
+    pub title: String,
+    /// The video's description.
+    pub description: String,
+    /// A map of thumbnail images associated with the video.
+    pub thumbnails: YoutubeThumbnails,
+    /// Channel title for the channel that the video belongs to.
+    pub channel_title: String,
+    /// The YouTube video category associated with the video.
+    pub category_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubeVideoContentDetails {
+    /// The length of the video (ISO 8601 duration)
+    pub duration: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YoutubeVideo {
+    /// The ID that YouTube uses to uniquely identify the video.
+    pub id: String,
+    /// Basic details about the video, such as its title, description, and category.
+    pub snippet: YoutubeVideoSnippet,
+    /// Information about the video content, including the length of the video.
+    pub content_details: YoutubeVideoContentDetails,
+}
+
+impl YoutubeVideo {
+    pub fn is_music(&self) -> bool {
+        self.snippet.category_id.as_deref() == Some(MUSIC_CATEGORY_ID)
+    }
+}
+
+/// Music metadata extracted from a YouTube video.
+///
+/// YouTube does not expose ISRCs nor structured music metadata,
+/// so title, artists & album are deduced from the video title, channel and description
+/// to be usable by a fuzzy matcher.
+#[derive(Debug, PartialEq)]
+pub struct YoutubeTrackMetadata {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub release_date: Option<NaiveDate>,
+}
+
+static TOPIC_CHANNEL_SUFFIX: &str = " - Topic";
+static AUTO_GENERATED_PREFIX: &str = "Provided to YouTube by";
+static RELEASE_DATE_PREFIX: &str = "Released on:";
+static TITLE_SEPARATORS: [&str; 3] = [" - ", " – ", " — "];
+static FEATURING_MARKERS: [&str; 4] = ["feat.", "ft.", "featuring", "feat"];
+static NOISE_KEYWORDS: [&str; 9] = [
+    "official",
+    "video",
+    "audio",
+    "lyric",
+    "visualizer",
+    "visualiser",
+    "mv",
+    "hd",
+    "4k",
+];
+
+impl YoutubeTrackMetadata {
+    pub fn parse(snippet: &YoutubeVideoSnippet) -> Self {
+        Self::from_auto_generated_description(&snippet.description)
+            .unwrap_or_else(|| Self::from_title(&snippet.title, &snippet.channel_title))
+    }
+
+    /// YouTube Music "Topic" uploads come with a generated description :
+    ///
+    /// ```text
+    /// Provided to YouTube by <Label>
+    ///
+    /// <Title> · <Artist> · <Artist>
+    ///
+    /// <Album>
+    ///
+    /// ℗ 2024 <Label>
+    ///
+    /// Released on: 2024-05-24
+    /// ```
+    fn from_auto_generated_description(description: &str) -> Option<Self> {
+        let mut lines = description
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        if !lines.next()?.starts_with(AUTO_GENERATED_PREFIX) {
+            return None;
+        }
+
+        let mut credits = lines.next()?.split(" · ").map(str::trim);
+        let title = credits.next()?.to_string();
+        let artists = credits.map(str::to_string).collect::<Vec<_>>();
+
+        if artists.is_empty() {
+            return None;
+        }
+
+        let album = lines.next().map(str::to_string);
+        let release_date = lines
+            .find_map(|line| line.strip_prefix(RELEASE_DATE_PREFIX))
+            .and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok());
+
+        Some(Self {
+            title,
+            artists,
+            album,
+            release_date,
+        })
+    }
+
+    /// Parse a regular video title (ex: `Artist - Title (feat. Other) [Official Video]`)
+    fn from_title(video_title: &str, channel_title: &str) -> Self {
+        let title = strip_noise(video_title);
+
+        let (artists, title) = match channel_title.strip_suffix(TOPIC_CHANNEL_SUFFIX) {
+            Some(artist) => (vec![artist.to_string()], title),
+            None => match TITLE_SEPARATORS
+                .iter()
+                .find_map(|separator| title.split_once(separator))
+            {
+                Some((artists, title)) => (split_artists(artists), title.trim().to_string()),
+                None => (vec![clean_channel_title(channel_title)], title),
+            },
+        };
+
+        let (title, featured) = extract_featuring(&title);
+
+        Self {
+            title,
+            artists: artists.into_iter().chain(featured).collect(),
+            album: None,
+            release_date: None,
+        }
+    }
+}
+
+/// Remove bracketed segments such as `(Official Music Video)` or `[Lyrics]`
+fn strip_noise(title: &str) -> String {
+    let mut result = String::new();
+    let mut rest = title;
+
+    while let Some(start) = rest.find(['(', '[']) {
+        let closing = if rest[start..].starts_with('(') {
+            ')'
+        } else {
+            ']'
+        };
+
+        let Some(end) = rest[start..].find(closing).map(|end| start + end) else {
+            break;
+        };
+
+        let segment = rest[start + 1..end].to_lowercase();
+        let is_noise = segment
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| NOISE_KEYWORDS.contains(&word));
+
+        result.push_str(&rest[..start]);
+        if !is_noise {
+            result.push_str(&rest[start..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extract featured artists from a title (ex: `Title (feat. Artist)`)
+fn extract_featuring(title: &str) -> (String, Vec<String>) {
+    let lowercase = title.to_ascii_lowercase();
+
+    for marker in FEATURING_MARKERS {
+        let Some(position) = lowercase
+            .match_indices(marker)
+            .map(|(position, _)| position)
+            .find(|position| {
+                let starts_word =
+                    *position == 0 || !title[..*position].ends_with(|c: char| c.is_alphanumeric());
+                let ends_word =
+                    !title[position + marker.len()..].starts_with(|c: char| c.is_alphanumeric());
+
+                starts_word && ends_word
+            })
+        else {
+            continue;
+        };
+
+        let before = title[..position].trim_end().trim_end_matches(['(', '[']);
+        let after = title[position + marker.len()..].trim_start_matches('.');
+        let (featured, remainder) = match after.find([')', ']']) {
+            Some(end) => (&after[..end], &after[end + 1..]),
+            None => (after, ""),
+        };
+
+        let title = format!("{} {}", before.trim(), remainder.trim());
+
+        return (title.trim().to_string(), split_artists(featured));
+    }
+
+    (title.to_string(), vec![])
+}
+
+fn split_artists(artists: &str) -> Vec<String> {
+    artists
+        .split([',', '&'])
+        .flat_map(|artist| artist.split(" x "))
+        .map(str::trim)
+        .filter(|artist| !artist.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn clean_channel_title(channel_title: &str) -> String {
+    channel_title
+        .strip_suffix("VEVO")
+        .unwrap_or(channel_title)
+        .trim()
+        .to_string()
+}
+
+impl TryFrom<YoutubeVideo> for TrackWithAlbumAndArtists {
+    type Error = &'static str;
+
+    fn try_from(video: YoutubeVideo) -> Result<Self, Self::Error> {
+        let provider_id = ProviderId::YOUTUBE;
+        let duration_ms = parse_iso8601_duration(&video.content_details.duration)?;
+        let metadata = YoutubeTrackMetadata::parse(&video.snippet);
+
+        let mut ids = HashSet::new();
+        let mut urls = HashMap::new();
+
+        let Ok(video_url) = Url::parse_with_params(
+            "https://music.youtube.com/watch",
+            [("v", video.id.as_str())],
+        ) else {
+            return Err("video.id is corrupted");
+        };
+
+        urls.insert(provider_id.clone(), video_url);
+        ids.insert(ProductId::Provider((provider_id.clone(), video.id)));
+
+        let Ok(channel_url) = format!(
+            "https://www.youtube.com/channel/{}",
+            video.snippet.channel_id
+        )
+        .parse::<Url>() else {
+            return Err("snippet.channel_id is corrupted");
+        };
+
+        // Only "Topic" channels or auto-generated uploads are reliably owned by the main artist
+        let is_artist_channel =
+            video.snippet.channel_title.ends_with(TOPIC_CHANNEL_SUFFIX) || metadata.album.is_some();
+
+        let artists = metadata
+            .artists
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let mut artist_ids = HashMap::new();
+                let mut artist_urls = HashMap::new();
+
+                if index == 0 && is_artist_channel {
+                    artist_ids.insert(provider_id.clone(), video.snippet.channel_id.clone());
+                    artist_urls.insert(provider_id.clone(), channel_url.clone());
+                }
+
+                Artist::new(artist_ids, name, artist_urls)
+            })
+            .collect();
+
+        let release_date = metadata
+            .release_date
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+            .unwrap_or(video.snippet.published_at);
+
+        let album = Album::new(
+            HashSet::new(),
+            metadata.album.unwrap_or_default(),
+            release_date,
+            video.snippet.thumbnails.into(),
+            HashMap::new(),
+        );
+
+        Ok(TrackWithAlbumAndArtists::new(
+            ids,
+            metadata.title,
+            duration_ms,
+            urls,
+            album,
+            artists,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use snk_core::entities::track::TrackWithAlbumAndArtists;
+
+    use super::{YoutubeTrackMetadata, YoutubeVideo};
+
+    #[test]
+    fn test_deserialize_video() {
+        let payload = include_str!("../../tests/youtube/payload_video.json");
+        let json = serde_json::from_str::<YoutubeVideo>(payload).expect("valid json");
+
+        assert!(json.is_music());
+        assert_eq!(json.snippet.channel_title, "NewJeans - Topic");
+
+        let track = TrackWithAlbumAndArtists::try_from(json).expect("valid track");
+
+        assert_eq!(track.name(), "How Sweet");
+        assert_eq!(track.duration_ms(), 219_000);
+        assert_eq!(track.artists()[0].name(), "NewJeans");
+        assert_eq!(track.album().name(), "How Sweet");
+        assert_eq!(
+            track.album().release_date().date_naive(),
+            NaiveDate::from_ymd_opt(2024, 5, 24).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_metadata_from_title() {
+        let parse = |title: &str, channel: &str| YoutubeTrackMetadata::from_title(title, channel);
+
+        assert_eq!(
+            parse(
+                "Kehlani - Nights Like This (feat. Ty Dolla $ign) [Official Video]",
+                "Kehlani"
+            ),
+            YoutubeTrackMetadata {
+                title: "Nights Like This".to_string(),
+                artists: vec!["Kehlani".to_string(), "Ty Dolla $ign".to_string()],
+                album: None,
+                release_date: None,
+            }
+        );
+        assert_eq!(
+            parse("Bad Guy (Official Audio)", "BillieEilishVEVO").artists,
+            vec!["BillieEilish".to_string()]
+        );
+        assert_eq!(
+            parse("Daft Punk & Pharrell Williams - Get Lucky", "Daft Punk").artists,
+            vec!["Daft Punk".to_string(), "Pharrell Williams".to_string()]
+        );
+        assert_eq!(
+            parse("Supernatural (Remix)", "NewJeans - Topic"),
+            YoutubeTrackMetadata {
+                title: "Supernatural (Remix)".to_string(),
+                artists: vec!["NewJeans".to_string()],
+                album: None,
+                release_date: None,
+            }
+        );
+    }
+}
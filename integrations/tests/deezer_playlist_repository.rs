@@ -9,7 +9,7 @@ use snk_core::{
 async fn test_get_playlist_no_auth() {
     // Init repository
     let music_account_provider = MusicAccountProvider::new(
-        ProviderId::new("deezer".to_string()),
+        ProviderId::DEEZER,
         "Deezer".to_string(),
         "#000000".to_string(),
         "https://connect.deezer.com/oauth/auth.php"
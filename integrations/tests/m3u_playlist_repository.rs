@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+
+use integrations::m3u::M3uPlaylistRepository;
+use snk_core::{
+    contracts::repositories::playlist_repository::PlaylistRepository,
+    entities::playlist::PlaylistSpec, value_objects::playlist_id::PlaylistId,
+};
+
+fn playlists_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("snk-m3u-{}-{}", name, std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).expect("directory created");
+    std::fs::copy(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/m3u/playlist.m3u8"),
+        directory.join("women-of-rap.m3u8"),
+    )
+    .expect("fixture copied");
+
+    directory
+}
+
+#[tokio::test]
+async fn test_get_playlist_and_tracks() {
+    let directory = playlists_directory("get");
+    let playlist_repo = M3uPlaylistRepository::new(directory.clone());
+    let playlist_id = PlaylistId::Owned("women-of-rap".to_string());
+
+    let playlist = playlist_repo
+        .get(&playlist_id)
+        .await
+        .expect("valid playlist")
+        .expect("playlist exists");
+
+    assert_eq!(playlist.name(), "Women of Rap");
+    assert_eq!(playlist.total_songs(), 3);
+
+    let tracks = playlist_repo
+        .get_tracks(&playlist_id)
+        .await
+        .expect("valid tracks");
+
+    assert_eq!(tracks.len(), 3);
+    assert_eq!(tracks[0].artists()[0].name(), "NewJeans");
+
+    assert!(playlist_repo
+        .get(&PlaylistId::Owned("unknown".to_string()))
+        .await
+        .expect("valid result")
+        .is_none());
+    assert!(playlist_repo
+        .get(&PlaylistId::Owned("../women-of-rap".to_string()))
+        .await
+        .is_err());
+
+    let _ = std::fs::remove_dir_all(directory);
+}
+
+#[tokio::test]
+async fn test_get_tracks_overflowing_duration() {
+    let directory = playlists_directory("duration");
+    let playlist_repo = M3uPlaylistRepository::new(directory.clone());
+
+    std::fs::write(
+        directory.join("long.m3u8"),
+        "#EXTM3U\n#EXTINF:4294968,NewJeans - How Sweet\nMusic/how-sweet.mp3\n",
+    )
+    .expect("playlist written");
+
+    let tracks = playlist_repo
+        .get_tracks(&PlaylistId::Owned("long".to_string()))
+        .await
+        .expect("valid tracks");
+
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].duration_ms(), 0);
+
+    let _ = std::fs::remove_dir_all(directory);
+}
+
+#[tokio::test]
+async fn test_create_and_edit_playlist() {
+    let directory = playlists_directory("edit");
+    let playlist_repo = M3uPlaylistRepository::new(directory.clone());
+
+    let playlist = playlist_repo
+        .create(&PlaylistSpec::new("Road trip".to_string()))
+        .await
+        .expect("playlist created");
+
+    assert!(directory.join("Road trip.m3u8").exists());
+
+    let ids = [
+        "Music/track-1.mp3".to_string(),
+        "https://www.deezer.com/track/3135556".to_string(),
+    ];
+
+    playlist_repo
+        .add_tracks(playlist.id(), &ids, None)
+        .await
+        .expect("tracks added");
+    playlist_repo
+        .delete_tracks(playlist.id(), &ids[..1], None)
+        .await
+        .expect("tracks deleted");
+
+    let tracks = playlist_repo
+        .get_tracks(playlist.id())
+        .await
+        .expect("valid tracks");
+
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].name(), "3135556");
+
+    let playlists = playlist_repo.get_all().await.expect("valid playlists");
+
+    assert_eq!(playlists.len(), 2);
+
+    playlist_repo
+        .delete(playlist.id())
+        .await
+        .expect("playlist deleted");
+
+    assert!(!directory.join("Road trip.m3u8").exists());
+
+    let _ = std::fs::remove_dir_all(directory);
+}
+
+#[tokio::test]
+async fn test_m3u8_read_before_m3u() {
+    let directory = playlists_directory("extensions");
+    let playlist_repo = M3uPlaylistRepository::new(directory.clone());
+
+    std::fs::write(
+        directory.join("women-of-rap.m3u"),
+        "#EXTM3U\n#PLAYLIST:Legacy\n#EXTINF:219,NewJeans - How Sweet\nMusic/how-sweet.mp3\n",
+    )
+    .expect("legacy playlist written");
+    std::fs::write(
+        directory.join("legacy.m3u"),
+        "#EXTM3U\n#PLAYLIST:Legacy only\n",
+    )
+    .expect("legacy playlist written");
+
+    let playlists = playlist_repo.get_all().await.expect("valid playlists");
+
+    // Both files of "women-of-rap" are listed once
+    assert_eq!(playlists.len(), 2);
+
+    let playlist = playlists
+        .iter()
+        .find(|playlist| matches!(playlist.id(), PlaylistId::Owned(id) if id == "women-of-rap"))
+        .expect("playlist exists");
+
+    assert_eq!(playlist.name(), "Women of Rap");
+    assert_eq!(playlist.total_songs(), 3);
+
+    let legacy_playlist = playlist_repo
+        .get(&PlaylistId::Owned("legacy".to_string()))
+        .await
+        .expect("valid playlist")
+        .expect("playlist exists");
+
+    assert_eq!(legacy_playlist.name(), "Legacy only");
+
+    let _ = std::fs::remove_dir_all(directory);
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::DateTime;
+use integrations::musicbrainz::MusicBrainzRepository;
+use snk_core::{
+    contracts::repositories::track_metadata_repository::TrackMetadataRepository,
+    entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+    services::metadata_enricher::MetadataEnricher,
+    value_objects::{barcode::Upc, isrc::Isrc, product_id::ProductId},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+static USER_AGENT: &str = "SonikSwap-tests/0.1.0 ( tests@sonikswap.local )";
+
+/// Request received by the stub server: time, path & query, user agent
+type StubRequest = (Instant, String, Option<String>);
+
+/// Minimal MusicBrainz server answering the recording search & lookup with fixtures
+async fn stub_server() -> (String, Arc<Mutex<Vec<StubRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("listener bound");
+    let address = listener.local_addr().expect("local address");
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = requests.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                break;
+            };
+            let mut buffer = vec![0; 8192];
+            let mut length = 0;
+
+            while !buffer[..length]
+                .windows(4)
+                .any(|window| window == b"\r\n\r\n")
+            {
+                match stream.read(&mut buffer[length..]).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => length += read,
+                }
+            }
+
+            let request = String::from_utf8_lossy(&buffer[..length]).to_string();
+            let target = request
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let user_agent = request.lines().find_map(|line| {
+                line.split_once(':')
+                    .filter(|(name, _)| name.eq_ignore_ascii_case("user-agent"))
+                    .map(|(_, value)| value.trim().to_string())
+            });
+
+            received
+                .lock()
+                .unwrap()
+                .push((Instant::now(), target.clone(), user_agent));
+
+            let (status, body) = if target.starts_with("/ws/2/recording?") {
+                (
+                    "200 OK",
+                    include_str!("musicbrainz/payload_search.json").to_string(),
+                )
+            } else if target.starts_with("/ws/2/recording/4c1e0d37-5f7d-4b55-8d37-ea6b3d4f6c10?") {
+                (
+                    "200 OK",
+                    include_str!("musicbrainz/payload_recording.json").to_string(),
+                )
+            } else {
+                ("404 Not Found", "{\"error\":\"Not Found\"}".to_string())
+            };
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+
+    (format!("http://{}/ws/2", address), requests)
+}
+
+fn track(name: &str, album: &str, duration_ms: u32) -> TrackWithAlbumAndArtists {
+    TrackWithAlbumAndArtists::new(
+        HashSet::new(),
+        name.to_string(),
+        duration_ms,
+        HashMap::new(),
+        Album::new(
+            HashSet::new(),
+            album.to_string(),
+            DateTime::default(),
+            HashSet::new(),
+            HashMap::new(),
+        ),
+        vec![Artist::new(
+            HashMap::new(),
+            "NewJeans".to_string(),
+            HashMap::new(),
+        )],
+    )
+}
+
+#[tokio::test]
+async fn test_find_recording() {
+    let (api_url, requests) = stub_server().await;
+    let repository =
+        MusicBrainzRepository::with_api_url(&api_url, USER_AGENT).expect("valid repository");
+
+    let recording = repository
+        .find_recording(&track("How Sweet", "How Sweet", 219_000))
+        .await
+        .expect("valid response")
+        .expect("recording found");
+
+    assert!(recording
+        .ids()
+        .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+    assert!(recording
+        .album()
+        .ids()
+        .contains(&ProductId::UPC(Upc::parse("196922995354").unwrap())));
+
+    let requests = requests.lock().unwrap();
+
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].1.contains("fmt=json"));
+    assert!(requests[0].1.contains("query=recording"));
+    assert!(requests[1].1.contains("inc=isrcs"));
+    assert!(requests
+        .iter()
+        .all(|(_, _, user_agent)| user_agent.as_deref() == Some(USER_AGENT)));
+}
+
+#[tokio::test]
+async fn test_enrich_rate_limited() {
+    let (api_url, requests) = stub_server().await;
+    let repository =
+        MusicBrainzRepository::with_api_url(&api_url, USER_AGENT).expect("valid repository");
+    let enricher = MetadataEnricher::new(&repository);
+
+    let mut tracks = vec![
+        track("How Sweet", "How Sweet", 219_000),
+        // Found by search, but not the same recording
+        track("Supernatural", "Supernatural", 191_000),
+    ];
+
+    assert_eq!(enricher.enrich_all(&mut tracks).await.expect("enriched"), 1);
+    assert!(tracks[0]
+        .ids()
+        .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+    assert!(tracks[1].ids().is_empty());
+
+    let requests = requests.lock().unwrap();
+
+    assert_eq!(requests.len(), 3);
+
+    for pair in requests.windows(2) {
+        assert!(pair[1].0.duration_since(pair[0].0) >= Duration::from_millis(950));
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use integrations::xspf::{XspfFormat, XspfPlaylistRepository};
+use snk_core::{
+    contracts::repositories::playlist_repository::PlaylistRepository,
+    entities::playlist::PlaylistSpec,
+    value_objects::{isrc::Isrc, playlist_id::PlaylistId, product_id::ProductId},
+};
+
+fn playlists_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("snk-xspf-{}-{}", name, std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).expect("directory created");
+
+    directory
+}
+
+#[tokio::test]
+async fn test_export_and_read_playlist() {
+    let directory = playlists_directory("export");
+    let xspf_repo = XspfPlaylistRepository::new(directory.clone(), XspfFormat::Xspf);
+    let jspf_repo = XspfPlaylistRepository::new(directory.clone(), XspfFormat::Jspf);
+
+    std::fs::copy(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/xspf/playlist.xspf"),
+        directory.join("women-of-rap.xspf"),
+    )
+    .expect("fixture copied");
+
+    let playlist_id = PlaylistId::Owned("women-of-rap".to_string());
+    let tracks = xspf_repo
+        .get_tracks(&playlist_id)
+        .await
+        .expect("valid tracks");
+
+    assert_eq!(tracks.len(), 3);
+
+    // Export the XSPF playlist as JSPF
+    let playlist = jspf_repo
+        .create(
+            &PlaylistSpec::new("Women of Rap".to_string())
+                .with_description(Some("The best of women in rap".to_string())),
+        )
+        .await
+        .expect("playlist created");
+
+    jspf_repo
+        .add_track_entries(playlist.id(), &tracks)
+        .await
+        .expect("tracks added");
+
+    let exported_tracks = jspf_repo
+        .get_tracks(playlist.id())
+        .await
+        .expect("valid tracks");
+
+    assert_eq!(exported_tracks.len(), 3);
+    assert_eq!(exported_tracks[1].name(), "Nights Like This");
+    assert!(exported_tracks[1]
+        .ids()
+        .contains(&ProductId::ISRC(Isrc::parse("USAT21900366").unwrap())));
+
+    // Each repository only sees its own format
+    assert_eq!(xspf_repo.get_all().await.expect("valid playlists").len(), 1);
+    assert_eq!(jspf_repo.get_all().await.expect("valid playlists").len(), 1);
+
+    jspf_repo
+        .delete_tracks(
+            playlist.id(),
+            &["https://www.deezer.com/track/2801558052".to_string()],
+            None,
+        )
+        .await
+        .expect("tracks deleted");
+
+    let playlist = jspf_repo
+        .get(playlist.id())
+        .await
+        .expect("valid playlist")
+        .expect("playlist exists");
+
+    assert_eq!(playlist.total_songs(), 2);
+    assert_eq!(
+        playlist.description(),
+        Some(&"The best of women in rap".to_string())
+    );
+
+    jspf_repo
+        .update_metadata(
+            playlist.id(),
+            &PlaylistSpec::new("Queens of Rap".to_string()),
+        )
+        .await
+        .expect("metadata updated");
+
+    let playlist = jspf_repo
+        .get(playlist.id())
+        .await
+        .expect("valid playlist")
+        .expect("playlist exists");
+
+    assert_eq!(playlist.name(), "Queens of Rap");
+    assert_eq!(
+        playlist.description(),
+        Some(&"The best of women in rap".to_string())
+    );
+
+    let _ = std::fs::remove_dir_all(directory);
+}
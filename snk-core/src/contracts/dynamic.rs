@@ -0,0 +1,358 @@
+//! Dyn-compatible versions of the repositories.
+//!
+//! Repositories use `async fn` in traits, which can't be turned into trait objects.
+//! These traits return boxed futures instead, so that a repository can be picked at runtime
+//! (ex: from the provider of a request) as an `Arc<dyn DynPlaylistRepository>`.
+//! The `Arc`s implement the original traits, to be given to services & use cases,
+//! and let a single repository back several traits.
+//!
+//! Repositories get the dyn version with [`impl_dyn_playlist_repository`],
+//! [`impl_dyn_track_search_repository`] and [`impl_dyn_account_profile_repository`].
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    contracts::repositories::{
+        account_profile_repository::{AccountProfileRepository, AccountProfileRepositoryResult},
+        playlist_repository::{PlaylistRepository, PlaylistRepositoryResult},
+        track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+    },
+    entities::{
+        account_profile::AccountProfile,
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::playlist_id::PlaylistId,
+};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Dyn-compatible [`PlaylistRepository`]
+pub trait DynPlaylistRepository: Send + Sync {
+    fn get<'a>(
+        &'a self,
+        id: &'a PlaylistId,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<Option<Playlist>>>;
+
+    fn get_all(&self) -> BoxFuture<'_, PlaylistRepositoryResult<Vec<Playlist>>>;
+
+    fn create<'a>(
+        &'a self,
+        spec: &'a PlaylistSpec,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<Playlist>>;
+
+    fn update_metadata<'a>(
+        &'a self,
+        id: &'a PlaylistId,
+        spec: &'a PlaylistSpec,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<()>>;
+
+    fn delete<'a>(
+        &'a self,
+        id: &'a PlaylistId,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<Option<Playlist>>>;
+
+    fn add_tracks<'a>(
+        &'a self,
+        playlist_id: &'a PlaylistId,
+        ids: &'a [String],
+        snapshot_id: Option<String>,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<()>>;
+
+    fn delete_tracks<'a>(
+        &'a self,
+        playlist_id: &'a PlaylistId,
+        ids: &'a [String],
+        snapshot_id: Option<String>,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<()>>;
+
+    fn get_tracks<'a>(
+        &'a self,
+        playlist_id: &'a PlaylistId,
+    ) -> BoxFuture<'a, PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>>>;
+}
+
+/// Dyn-compatible [`TrackSearchRepository`]
+pub trait DynTrackSearchRepository: Send + Sync {
+    fn find_by_isrc<'a>(
+        &'a self,
+        isrc: &'a str,
+    ) -> BoxFuture<'a, TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>>>;
+
+    fn search<'a>(
+        &'a self,
+        track: &'a TrackWithAlbumAndArtists,
+    ) -> BoxFuture<'a, TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>>>;
+}
+
+/// Dyn-compatible [`AccountProfileRepository`]
+pub trait DynAccountProfileRepository: Send + Sync {
+    fn get_profile(&self) -> BoxFuture<'_, AccountProfileRepositoryResult<AccountProfile>>;
+}
+
+/// Implement [`DynPlaylistRepository`] for a type implementing [`PlaylistRepository`]
+/// with `Send` futures.
+///
+/// A blanket implementation can't be written, as the futures of a generic
+/// repository aren't known to be `Send`.
+#[macro_export]
+macro_rules! impl_dyn_playlist_repository {
+    ($repository:ty) => {
+        impl $crate::contracts::dynamic::DynPlaylistRepository for $repository {
+            fn get<'a>(
+                &'a self,
+                id: &'a $crate::value_objects::playlist_id::PlaylistId,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<
+                    Option<$crate::entities::playlist::Playlist>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::get(self, id),
+                )
+            }
+
+            fn get_all(
+                &self,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                '_,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<
+                    Vec<$crate::entities::playlist::Playlist>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::get_all(self),
+                )
+            }
+
+            fn create<'a>(
+                &'a self,
+                spec: &'a $crate::entities::playlist::PlaylistSpec,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<
+                    $crate::entities::playlist::Playlist,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::create(self, spec),
+                )
+            }
+
+            fn update_metadata<'a>(
+                &'a self,
+                id: &'a $crate::value_objects::playlist_id::PlaylistId,
+                spec: &'a $crate::entities::playlist::PlaylistSpec,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<()>,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::update_metadata(self, id, spec),
+                )
+            }
+
+            fn delete<'a>(
+                &'a self,
+                id: &'a $crate::value_objects::playlist_id::PlaylistId,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<
+                    Option<$crate::entities::playlist::Playlist>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::delete(self, id),
+                )
+            }
+
+            fn add_tracks<'a>(
+                &'a self,
+                playlist_id: &'a $crate::value_objects::playlist_id::PlaylistId,
+                ids: &'a [String],
+                snapshot_id: Option<String>,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<()>,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::add_tracks(
+                        self,
+                        playlist_id,
+                        ids,
+                        snapshot_id,
+                    ),
+                )
+            }
+
+            fn delete_tracks<'a>(
+                &'a self,
+                playlist_id: &'a $crate::value_objects::playlist_id::PlaylistId,
+                ids: &'a [String],
+                snapshot_id: Option<String>,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<()>,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::delete_tracks(
+                        self,
+                        playlist_id,
+                        ids,
+                        snapshot_id,
+                    ),
+                )
+            }
+
+            fn get_tracks<'a>(
+                &'a self,
+                playlist_id: &'a $crate::value_objects::playlist_id::PlaylistId,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::playlist_repository::PlaylistRepositoryResult<
+                    Vec<$crate::entities::track::TrackWithAlbumAndArtists>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::playlist_repository::PlaylistRepository>::get_tracks(
+                        self,
+                        playlist_id,
+                    ),
+                )
+            }
+        }
+    };
+}
+
+/// Implement [`DynTrackSearchRepository`] for a type implementing [`TrackSearchRepository`]
+/// with `Send` futures.
+#[macro_export]
+macro_rules! impl_dyn_track_search_repository {
+    ($repository:ty) => {
+        impl $crate::contracts::dynamic::DynTrackSearchRepository for $repository {
+            fn find_by_isrc<'a>(
+                &'a self,
+                isrc: &'a str,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::track_search_repository::TrackSearchRepositoryResult<
+                    Option<$crate::entities::track::TrackWithAlbumAndArtists>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::track_search_repository::TrackSearchRepository>::find_by_isrc(self, isrc),
+                )
+            }
+
+            fn search<'a>(
+                &'a self,
+                track: &'a $crate::entities::track::TrackWithAlbumAndArtists,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                'a,
+                $crate::contracts::repositories::track_search_repository::TrackSearchRepositoryResult<
+                    Vec<$crate::entities::track::TrackWithAlbumAndArtists>,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::track_search_repository::TrackSearchRepository>::search(self, track),
+                )
+            }
+        }
+    };
+}
+
+/// Implement [`DynAccountProfileRepository`] for a type implementing [`AccountProfileRepository`]
+/// with `Send` futures.
+#[macro_export]
+macro_rules! impl_dyn_account_profile_repository {
+    ($repository:ty) => {
+        impl $crate::contracts::dynamic::DynAccountProfileRepository for $repository {
+            fn get_profile(
+                &self,
+            ) -> $crate::contracts::dynamic::BoxFuture<
+                '_,
+                $crate::contracts::repositories::account_profile_repository::AccountProfileRepositoryResult<
+                    $crate::entities::account_profile::AccountProfile,
+                >,
+            > {
+                Box::pin(
+                    <Self as $crate::contracts::repositories::account_profile_repository::AccountProfileRepository>::get_profile(self),
+                )
+            }
+        }
+    };
+}
+
+impl PlaylistRepository for Arc<dyn DynPlaylistRepository + '_> {
+    async fn get(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        DynPlaylistRepository::get(self.as_ref(), id).await
+    }
+
+    async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+        DynPlaylistRepository::get_all(self.as_ref()).await
+    }
+
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+        DynPlaylistRepository::create(self.as_ref(), spec).await
+    }
+
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()> {
+        DynPlaylistRepository::update_metadata(self.as_ref(), id, spec).await
+    }
+
+    async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+        DynPlaylistRepository::delete(self.as_ref(), id).await
+    }
+
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        DynPlaylistRepository::add_tracks(self.as_ref(), playlist_id, ids, snapshot_id).await
+    }
+
+    async fn delete_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        ids: &[String],
+        snapshot_id: Option<String>,
+    ) -> PlaylistRepositoryResult<()> {
+        DynPlaylistRepository::delete_tracks(self.as_ref(), playlist_id, ids, snapshot_id).await
+    }
+
+    async fn get_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        DynPlaylistRepository::get_tracks(self.as_ref(), playlist_id).await
+    }
+}
+
+impl TrackSearchRepository for Arc<dyn DynTrackSearchRepository + '_> {
+    async fn find_by_isrc(
+        &self,
+        isrc: &str,
+    ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        DynTrackSearchRepository::find_by_isrc(self.as_ref(), isrc).await
+    }
+
+    async fn search(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+        DynTrackSearchRepository::search(self.as_ref(), track).await
+    }
+}
+
+impl AccountProfileRepository for Arc<dyn DynAccountProfileRepository + '_> {
+    async fn get_profile(&self) -> AccountProfileRepositoryResult<AccountProfile> {
+        DynAccountProfileRepository::get_profile(self.as_ref()).await
+    }
+}
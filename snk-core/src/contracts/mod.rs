@@ -1 +1,2 @@
+pub mod dynamic;
 pub mod repositories;
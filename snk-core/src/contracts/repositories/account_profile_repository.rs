@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+use crate::entities::account_profile::AccountProfile;
+
+#[derive(Debug, Error)]
+pub enum AccountProfileRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type AccountProfileRepositoryResult<T> = Result<T, AccountProfileRepositoryError>;
+
+/// Repository reading the profile of the account the credentials belong to
+pub trait AccountProfileRepository {
+    /// Get the profile of the connected account
+    ///
+    /// Returns:
+    /// - [`AccountProfile`] or [`AccountProfileRepositoryError`]
+    async fn get_profile(&self) -> AccountProfileRepositoryResult<AccountProfile>;
+}
@@ -0,0 +1,45 @@
+use thiserror::Error;
+
+use crate::{
+    contracts::repositories::playlist_repository::ChunkResult, entities::album::AlbumWithArtists,
+};
+
+#[derive(Debug, Error)]
+pub enum AlbumLibraryRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+    /// Write split in several requests, some of them failing
+    #[error(
+        "PartialFailure: {} of {} chunks failed",
+        .0.iter().filter(|chunk| chunk.error.is_some()).count(),
+        .0.len()
+    )]
+    PartialFailure(Vec<ChunkResult>),
+}
+
+pub type AlbumLibraryRepositoryResult<T> = Result<T, AlbumLibraryRepositoryError>;
+
+/// Repository managing the albums saved in the library of a user
+pub trait AlbumLibraryRepository {
+    /// Get the saved albums
+    ///
+    /// Returns:
+    /// List of [`AlbumWithArtists`], most recently saved first,
+    /// or [`AlbumLibraryRepositoryError`]
+    async fn get_all(&self) -> AlbumLibraryRepositoryResult<Vec<AlbumWithArtists>>;
+
+    /// Save albums in the library
+    ///
+    /// Requests are split to the limits of the provider, every chunk is attempted and
+    /// `PartialFailure` tells which ones failed
+    ///
+    /// Arguments:
+    /// - ids: ids of the albums for the provider
+    async fn save(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()>;
+
+    /// Remove albums from the library
+    ///
+    /// Arguments:
+    /// - ids: ids of the albums for the provider
+    async fn remove(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()>;
+}
@@ -0,0 +1,42 @@
+use thiserror::Error;
+
+use crate::{contracts::repositories::playlist_repository::ChunkResult, entities::artist::Artist};
+
+#[derive(Debug, Error)]
+pub enum ArtistLibraryRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+    /// Write split in several requests, some of them failing
+    #[error(
+        "PartialFailure: {} of {} chunks failed",
+        .0.iter().filter(|chunk| chunk.error.is_some()).count(),
+        .0.len()
+    )]
+    PartialFailure(Vec<ChunkResult>),
+}
+
+pub type ArtistLibraryRepositoryResult<T> = Result<T, ArtistLibraryRepositoryError>;
+
+/// Repository managing the artists followed by a user
+pub trait ArtistLibraryRepository {
+    /// Get the followed artists
+    ///
+    /// Returns:
+    /// List of [`Artist`] or [`ArtistLibraryRepositoryError`]
+    async fn get_all(&self) -> ArtistLibraryRepositoryResult<Vec<Artist>>;
+
+    /// Follow artists
+    ///
+    /// Requests are split to the limits of the provider, every chunk is attempted and
+    /// `PartialFailure` tells which ones failed
+    ///
+    /// Arguments:
+    /// - ids: ids of the artists for the provider
+    async fn save(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()>;
+
+    /// Unfollow artists
+    ///
+    /// Arguments:
+    /// - ids: ids of the artists for the provider
+    async fn remove(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()>;
+}
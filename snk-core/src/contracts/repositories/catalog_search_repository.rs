@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+use crate::entities::{album::AlbumWithArtists, artist::Artist};
+
+#[derive(Debug, Error)]
+pub enum CatalogSearchRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type CatalogSearchRepositoryResult<T> = Result<T, CatalogSearchRepositoryError>;
+
+/// Repository looking up albums & artists in the catalog of a provider,
+/// used to find the equivalent of a library coming from another provider
+pub trait CatalogSearchRepository {
+    /// Find the album of the catalog having this UPC
+    ///
+    /// Arguments:
+    /// - upc: Universal Product Code of the album
+    ///
+    /// Returns:
+    /// - [`Option<AlbumWithArtists>`] or [`CatalogSearchRepositoryError`]
+    async fn find_album_by_upc(
+        &self,
+        upc: &str,
+    ) -> CatalogSearchRepositoryResult<Option<AlbumWithArtists>>;
+
+    /// Search albums of the catalog looking like the given album (title, artists)
+    ///
+    /// Returns:
+    /// List of candidates [`AlbumWithArtists`], best results first,
+    /// or [`CatalogSearchRepositoryError`]
+    async fn search_albums(
+        &self,
+        album: &AlbumWithArtists,
+    ) -> CatalogSearchRepositoryResult<Vec<AlbumWithArtists>>;
+
+    /// Search artists of the catalog having a name close to the given artist
+    ///
+    /// Returns:
+    /// List of candidates [`Artist`], best results first, or [`CatalogSearchRepositoryError`]
+    async fn search_artists(&self, artist: &Artist) -> CatalogSearchRepositoryResult<Vec<Artist>>;
+}
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::identity::{Identity, IdentityKind},
+    value_objects::product_id::ProductId,
+};
+
+#[derive(Debug, Error)]
+pub enum IdentityRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type IdentityRepositoryResult<T> = Result<T, IdentityRepositoryError>;
+
+/// Repository of the canonical identities of a kind (recordings, releases, artists)
+///
+/// An id belongs to at most one identity of a kind.
+pub trait IdentityRepository<K: IdentityKind> {
+    async fn get(&self, id: Uuid) -> IdentityRepositoryResult<Option<Identity<K>>>;
+
+    /// Get the identities having any of the ids
+    async fn find_by_ids(
+        &self,
+        ids: &HashSet<ProductId>,
+    ) -> IdentityRepositoryResult<Vec<Identity<K>>>;
+
+    /// Insert or replace the identity along with its ids
+    async fn save(&self, identity: &Identity<K>) -> IdentityRepositoryResult<()>;
+
+    /// Delete the identities merged into another one & save it along with their ids,
+    /// as a whole or not at all
+    async fn merge(&self, identity: &Identity<K>, merged: &[Uuid]) -> IdentityRepositoryResult<()>;
+
+    /// Save an identity split in two, the ids it released going to the new identity,
+    /// as a whole or not at all
+    async fn split(
+        &self,
+        identity: &Identity<K>,
+        split_identity: &Identity<K>,
+    ) -> IdentityRepositoryResult<()>;
+
+    async fn delete(&self, id: Uuid) -> IdentityRepositoryResult<()>;
+}
+
+/// Repository knowing no identity, for working without identity graph
+pub struct NoIdentityRepository;
+
+impl<K: IdentityKind> IdentityRepository<K> for NoIdentityRepository {
+    async fn get(&self, _id: Uuid) -> IdentityRepositoryResult<Option<Identity<K>>> {
+        Ok(None)
+    }
+
+    async fn find_by_ids(
+        &self,
+        _ids: &HashSet<ProductId>,
+    ) -> IdentityRepositoryResult<Vec<Identity<K>>> {
+        Ok(vec![])
+    }
+
+    async fn save(&self, _identity: &Identity<K>) -> IdentityRepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn merge(
+        &self,
+        _identity: &Identity<K>,
+        _merged: &[Uuid],
+    ) -> IdentityRepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn split(
+        &self,
+        _identity: &Identity<K>,
+        _split_identity: &Identity<K>,
+    ) -> IdentityRepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn delete(&self, _id: Uuid) -> IdentityRepositoryResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use thiserror::Error;
+
+use crate::value_objects::{product_id::ProductId, provider::provider_id::ProviderId};
+
+#[derive(Debug, Error)]
+pub enum MatchCacheRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type MatchCacheRepositoryResult<T> = Result<T, MatchCacheRepositoryError>;
+
+/// Known outcome of the lookup of a product in the catalog of a provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CachedMatch {
+    /// Track of the provider, with whether it is playable in the market of the lookup
+    Found { id: String, playable: Option<bool> },
+    /// Missing from the catalog of the provider
+    NotFound,
+}
+
+/// Repository caching the ids of products (ISRC, ids of other providers...) in the catalog
+/// of each provider, shared across users
+///
+/// Outcomes are kept per market (ISO 3166-1 alpha-2 country), the playable version of a track
+/// differing from a country to another. `None` is the market of the lookups made without one.
+pub trait MatchCacheRepository {
+    /// Get the unexpired outcome of a lookup
+    ///
+    /// Returns:
+    /// - [`Option<CachedMatch>`], `None` when unknown or expired, or [`MatchCacheRepositoryError`]
+    async fn get(
+        &self,
+        product_id: &ProductId,
+        provider_id: &ProviderId,
+        market: Option<&str>,
+    ) -> MatchCacheRepositoryResult<Option<CachedMatch>>;
+
+    /// Insert or refresh the outcome of a lookup, expiring after a TTL chosen by the repository
+    async fn set(
+        &self,
+        product_id: &ProductId,
+        provider_id: &ProviderId,
+        market: Option<&str>,
+        cached_match: &CachedMatch,
+    ) -> MatchCacheRepositoryResult<()>;
+}
+
+/// Cache remembering nothing, for matching without cache
+pub struct NoMatchCache;
+
+impl MatchCacheRepository for NoMatchCache {
+    async fn get(
+        &self,
+        _product_id: &ProductId,
+        _provider_id: &ProviderId,
+        _market: Option<&str>,
+    ) -> MatchCacheRepositoryResult<Option<CachedMatch>> {
+        Ok(None)
+    }
+
+    async fn set(
+        &self,
+        _product_id: &ProductId,
+        _provider_id: &ProviderId,
+        _market: Option<&str>,
+        _cached_match: &CachedMatch,
+    ) -> MatchCacheRepositoryResult<()> {
+        Ok(())
+    }
+}
@@ -1,3 +1,13 @@
+pub mod account_profile_repository;
+pub mod album_library_repository;
+pub mod artist_library_repository;
+pub mod catalog_search_repository;
+pub mod identity_repository;
+pub mod match_cache_repository;
 pub mod music_account_provider_repository;
 pub mod playlist_repository;
+pub mod smart_playlist_repository;
+pub mod track_metadata_repository;
+pub mod track_search_repository;
+pub mod transfer_job_repository;
 pub mod user_repository;
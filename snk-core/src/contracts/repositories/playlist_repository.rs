@@ -1,7 +1,10 @@
 use thiserror::Error;
 
 use crate::{
-    entities::{playlist::Playlist, track::TrackWithAlbumAndArtists},
+    entities::{
+        playlist::{Playlist, PlaylistSpec},
+        track::TrackWithAlbumAndArtists,
+    },
     value_objects::playlist_id::PlaylistId,
 };
 
@@ -9,6 +12,21 @@ use crate::{
 pub enum PlaylistRepositoryError {
     #[error("ServiceError: {0}")]
     ServiceError(String),
+    /// Write split in several requests, some of them failing
+    #[error(
+        "PartialFailure: {} of {} chunks failed",
+        .0.iter().filter(|chunk| chunk.error.is_some()).count(),
+        .0.len()
+    )]
+    PartialFailure(Vec<ChunkResult>),
+}
+
+/// Outcome of one of the requests of a write split to the limits of the provider
+#[derive(Debug, Clone)]
+pub struct ChunkResult {
+    pub ids: Vec<String>,
+    /// `None` when the chunk was applied
+    pub error: Option<String>,
 }
 
 pub type PlaylistRepositoryResult<T> = Result<T, PlaylistRepositoryError>;
@@ -20,11 +38,26 @@ pub trait PlaylistRepository {
 
     async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>>;
 
-    async fn create(&self, name: &str) -> PlaylistRepositoryResult<Playlist>;
+    /// Create a playlist with the metadata & cover of the spec, the ones the provider supports
+    ///
+    /// Once the playlist is created, a cover the provider refuses (too large...) is left out
+    /// rather than failing, so that the caller always learns about the playlist.
+    async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist>;
+
+    /// Set the name, and the other metadata given by the spec (description, visibility, cover...)
+    async fn update_metadata(
+        &self,
+        id: &PlaylistId,
+        spec: &PlaylistSpec,
+    ) -> PlaylistRepositoryResult<()>;
 
     async fn delete(&self, id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>>;
 
     // Tracks related
+    //
+    // Writes to providers are split to their limits, every chunk is attempted and
+    // `PartialFailure` tells which ones failed. Local playlist files (M3U, XSPF) are
+    // written at once, a failure leaving them untouched
 
     async fn add_tracks(
         &self,
@@ -0,0 +1,24 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::smart_playlist::SmartPlaylist;
+
+#[derive(Debug, Error)]
+pub enum SmartPlaylistRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type SmartPlaylistRepositoryResult<T> = Result<T, SmartPlaylistRepositoryError>;
+
+/// Repository of the smart playlists, read by the periodic refresh of the due ones
+pub trait SmartPlaylistRepository {
+    async fn get(&self, id: Uuid) -> SmartPlaylistRepositoryResult<Option<SmartPlaylist>>;
+
+    async fn get_all(&self) -> SmartPlaylistRepositoryResult<Vec<SmartPlaylist>>;
+
+    /// Insert or replace the smart playlist
+    async fn save(&self, smart_playlist: &SmartPlaylist) -> SmartPlaylistRepositoryResult<()>;
+
+    async fn delete(&self, id: Uuid) -> SmartPlaylistRepositoryResult<()>;
+}
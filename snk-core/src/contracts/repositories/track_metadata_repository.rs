@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+use crate::entities::track::TrackWithAlbumAndArtists;
+
+#[derive(Debug, Error)]
+pub enum TrackMetadataRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type TrackMetadataRepositoryResult<T> = Result<T, TrackMetadataRepositoryError>;
+
+/// Repository looking up tracks in a metadata database (MusicBrainz...),
+/// used to complete the identifiers of tracks before matching them
+pub trait TrackMetadataRepository {
+    /// Find the recording corresponding to a track, using its title, artists & duration
+    ///
+    /// Arguments:
+    /// - track: [`TrackWithAlbumAndArtists`] to look for
+    ///
+    /// Returns:
+    /// - [`Option<TrackWithAlbumAndArtists>`] holding the known ids of the recording (ISRC)
+    ///   and of its release (UPC, EAN), or [`TrackMetadataRepositoryError`]
+    async fn find_recording(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>>;
+}
+
+/// Repository knowing no recording, for matching without metadata database
+pub struct NoTrackMetadataRepository;
+
+impl TrackMetadataRepository for NoTrackMetadataRepository {
+    async fn find_recording(
+        &self,
+        _track: &TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        Ok(None)
+    }
+}
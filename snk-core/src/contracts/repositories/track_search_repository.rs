@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+use crate::entities::track::TrackWithAlbumAndArtists;
+
+#[derive(Debug, Error)]
+pub enum TrackSearchRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type TrackSearchRepositoryResult<T> = Result<T, TrackSearchRepositoryError>;
+
+/// Repository looking up tracks in the catalog of a provider,
+/// used to find the equivalent of a track coming from another provider
+pub trait TrackSearchRepository {
+    /// Find the track of the catalog having this ISRC
+    ///
+    /// Arguments:
+    /// - isrc: International Standard Recording Code of the track
+    ///
+    /// Returns:
+    /// - [`Option<TrackWithAlbumAndArtists>`] or [`TrackSearchRepositoryError`]
+    async fn find_by_isrc(
+        &self,
+        isrc: &str,
+    ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>>;
+
+    /// Search tracks of the catalog looking like the given track (title, artists, album)
+    ///
+    /// Arguments:
+    /// - track: [`TrackWithAlbumAndArtists`] to look for, usually from another provider
+    ///
+    /// Returns:
+    /// List of candidates [`TrackWithAlbumAndArtists`], best results first,
+    /// or [`TrackSearchRepositoryError`]
+    async fn search(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>>;
+}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::transfer_job::TransferJob;
+
+#[derive(Debug, Error)]
+pub enum TransferJobRepositoryError {
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type TransferJobRepositoryResult<T> = Result<T, TransferJobRepositoryError>;
+
+/// Repository keeping the progress of transfers
+pub trait TransferJobRepository {
+    async fn get(&self, id: Uuid) -> TransferJobRepositoryResult<Option<TransferJob>>;
+
+    /// Insert or replace the job
+    async fn save(&self, job: &TransferJob) -> TransferJobRepositoryResult<()>;
+
+    async fn delete(&self, id: Uuid) -> TransferJobRepositoryResult<()>;
+}
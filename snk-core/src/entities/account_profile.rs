@@ -0,0 +1,28 @@
+/// Profile of a user account on a provider
+pub struct AccountProfile {
+    id: String,                   // Id of the account on the provider
+    name: String,                 // Display name of the account
+    country_code: Option<String>, // ISO 3166-1 alpha-2 country of the account, `None` if not shared
+}
+
+impl AccountProfile {
+    pub fn new(id: String, name: String, country_code: Option<String>) -> Self {
+        Self {
+            id,
+            name,
+            country_code,
+        }
+    }
+
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn country_code(&self) -> Option<&String> {
+        self.country_code.as_ref()
+    }
+}
@@ -7,6 +7,13 @@ use crate::value_objects::{
     image_cover::ImageCover, product_id::ProductId, provider::provider_id::ProviderId,
 };
 
+use super::artist::Artist;
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Album {
     ids: HashSet<ProductId>,
     name: String,
@@ -36,6 +43,11 @@ impl Album {
         &self.ids
     }
 
+    /// Add ids found in external databases, known ids are kept
+    pub fn extend_ids(&mut self, ids: impl IntoIterator<Item = ProductId>) {
+        self.ids.extend(ids);
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -52,3 +64,27 @@ impl Album {
         &self.provider_urls
     }
 }
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AlbumWithArtists {
+    pub album: Album,         // Album saved or released
+    pub artists: Vec<Artist>, // Main artists of the album
+}
+
+impl AlbumWithArtists {
+    pub fn new(album: Album, artists: Vec<Artist>) -> Self {
+        Self { album, artists }
+    }
+
+    pub fn album(&self) -> &Album {
+        &self.album
+    }
+
+    pub fn artists(&self) -> &Vec<Artist> {
+        &self.artists
+    }
+}
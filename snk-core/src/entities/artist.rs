@@ -5,6 +5,11 @@ use url::Url;
 use crate::value_objects::provider::provider_id::ProviderId;
 
 #[derive(PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Artist {
     ids: HashMap<ProviderId, String>,
     name: String,
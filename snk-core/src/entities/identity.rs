@@ -0,0 +1,111 @@
+use std::{collections::HashSet, marker::PhantomData};
+
+use uuid::Uuid;
+
+use crate::value_objects::product_id::ProductId;
+
+/// Kind of canonical identity, deciding which ids identify an entity by themselves
+pub trait IdentityKind {
+    /// Name of the kind, as persisted
+    const NAME: &'static str;
+
+    /// Whether the id is a standard identifier (ISRC, UPC...) rather than the id of a provider
+    ///
+    /// Two identities having only different strong ids are never merged.
+    fn is_strong_id(id: &ProductId) -> bool;
+}
+
+/// Kind of the recordings, identified by their ISRC
+pub struct RecordingKind;
+
+impl IdentityKind for RecordingKind {
+    const NAME: &'static str = "recording";
+
+    fn is_strong_id(id: &ProductId) -> bool {
+        matches!(id, ProductId::ISRC(_))
+    }
+}
+
+/// Kind of the releases, identified by their barcode (UPC or EAN)
+pub struct ReleaseKind;
+
+impl IdentityKind for ReleaseKind {
+    const NAME: &'static str = "release";
+
+    fn is_strong_id(id: &ProductId) -> bool {
+        matches!(id, ProductId::UPC(_) | ProductId::EAN(_))
+    }
+}
+
+/// Kind of the artists, identified by their MusicBrainz id
+pub struct ArtistKind;
+
+impl IdentityKind for ArtistKind {
+    const NAME: &'static str = "artist";
+
+    fn is_strong_id(id: &ProductId) -> bool {
+        matches!(id, ProductId::Provider((provider_id, _)) if provider_id.as_str() == "musicbrainz")
+    }
+}
+
+/// Canonical entity linking every id known for the same recording, release or artist
+pub struct Identity<K: IdentityKind> {
+    id: Uuid,
+    name: String,
+    ids: HashSet<ProductId>, // ISRC/UPC/EAN & ids of every provider seen
+    kind: PhantomData<K>,
+}
+
+pub type Recording = Identity<RecordingKind>;
+pub type Release = Identity<ReleaseKind>;
+pub type ArtistIdentity = Identity<ArtistKind>;
+
+impl<K: IdentityKind> Identity<K> {
+    pub fn new(id: Uuid, name: String, ids: HashSet<ProductId>) -> Self {
+        Self {
+            id,
+            name,
+            ids,
+            kind: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn ids(&self) -> &HashSet<ProductId> {
+        &self.ids
+    }
+
+    pub fn extend_ids(&mut self, ids: impl IntoIterator<Item = ProductId>) {
+        self.ids.extend(ids);
+    }
+
+    /// Remove ids, returning the ones the identity had
+    pub fn remove_ids(&mut self, ids: &HashSet<ProductId>) -> HashSet<ProductId> {
+        let removed = self.ids.intersection(ids).cloned().collect();
+
+        self.ids.retain(|id| !ids.contains(id));
+
+        removed
+    }
+
+    pub fn strong_ids(&self) -> HashSet<&ProductId> {
+        self.ids.iter().filter(|id| K::is_strong_id(id)).collect()
+    }
+}
+
+impl<K: IdentityKind> Clone for Identity<K> {
+    fn clone(&self) -> Self {
+        Self::new(self.id, self.name.clone(), self.ids.clone())
+    }
+}
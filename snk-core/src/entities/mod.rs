@@ -1,6 +1,11 @@
+pub mod account_profile;
 pub mod album;
 pub mod artist;
+pub mod identity;
 pub mod music_account_provider;
 pub mod playlist;
+pub mod provider_credentials;
+pub mod smart_playlist;
 pub mod track;
+pub mod transfer_job;
 pub mod user;
@@ -2,16 +2,27 @@ use std::collections::HashSet;
 
 use url::Url;
 
-use crate::value_objects::{image_cover::ImageCover, playlist_id::PlaylistId};
+use crate::value_objects::{
+    cover_image::CoverImage, image_cover::ImageCover, playlist_id::PlaylistId,
+};
 
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Playlist {
     id: PlaylistId,
     name: String,
     covers: HashSet<ImageCover>,
-    owner: String, // Name of the owner (We won't use other metadata for now)
+    owner: String,            // Name of the owner (We won't use other metadata for now)
+    owner_id: Option<String>, // Id of the owner account on the provider, `None` if unknown
     provider_url: Url,
     total_songs: u32,
+    description: Option<String>,
+    public: Option<bool>,        // Visible by anyone, `None` if unknown
+    collaborative: Option<bool>, // Editable by other users, `None` if unknown
 }
 
 impl Playlist {
@@ -28,8 +39,12 @@ impl Playlist {
             name,
             covers,
             owner,
+            owner_id: None,
             total_songs,
             provider_url,
+            description: None,
+            public: None,
+            collaborative: None,
         }
     }
 
@@ -49,6 +64,14 @@ impl Playlist {
         &self.owner
     }
 
+    pub fn owner_id(&self) -> Option<&String> {
+        self.owner_id.as_ref()
+    }
+
+    pub fn set_owner_id(&mut self, owner_id: Option<String>) {
+        self.owner_id = owner_id;
+    }
+
     pub fn total_songs(&self) -> u32 {
         self.total_songs
     }
@@ -56,4 +79,95 @@ impl Playlist {
     pub fn provider_url(&self) -> &Url {
         &self.provider_url
     }
+
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description.filter(|description| !description.is_empty());
+    }
+
+    pub fn public(&self) -> Option<bool> {
+        self.public
+    }
+
+    pub fn set_public(&mut self, public: Option<bool>) {
+        self.public = public;
+    }
+
+    pub fn collaborative(&self) -> Option<bool> {
+        self.collaborative
+    }
+
+    pub fn set_collaborative(&mut self, collaborative: Option<bool>) {
+        self.collaborative = collaborative;
+    }
+
+    /// Largest cover of the playlist
+    pub fn largest_cover(&self) -> Option<&ImageCover> {
+        self.covers.iter().max_by_key(|cover| match cover {
+            ImageCover::Sm(_) => 0,
+            ImageCover::Other(_) => 1,
+            ImageCover::Md(_) => 2,
+            ImageCover::Default(_) => 3,
+            ImageCover::Lg(_) => 4,
+        })
+    }
+}
+
+/// Metadata of a playlist to create, or to set on an existing one
+///
+/// Fields left to `None` keep the default of the provider on creation, and the current value on update.
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistSpec {
+    pub name: String,
+    pub description: Option<String>,
+    pub public: Option<bool>,
+    pub collaborative: Option<bool>,
+    pub cover: Option<CoverImage>,
+}
+
+impl PlaylistSpec {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn with_public(mut self, public: Option<bool>) -> Self {
+        self.public = public;
+        self
+    }
+
+    pub fn with_collaborative(mut self, collaborative: Option<bool>) -> Self {
+        self.collaborative = collaborative;
+        self
+    }
+
+    pub fn with_cover(mut self, cover: Option<CoverImage>) -> Self {
+        self.cover = cover;
+        self
+    }
+}
+
+/// Spec of a copy of the playlist, with its metadata & largest cover
+impl From<&Playlist> for PlaylistSpec {
+    fn from(playlist: &Playlist) -> Self {
+        PlaylistSpec::new(playlist.name().clone())
+            .with_description(playlist.description().cloned())
+            .with_public(playlist.public())
+            .with_collaborative(playlist.collaborative())
+            .with_cover(
+                playlist
+                    .largest_cover()
+                    .map(|cover| CoverImage::Url(cover.url().clone())),
+            )
+    }
 }
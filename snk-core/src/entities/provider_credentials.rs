@@ -0,0 +1,41 @@
+use crate::value_objects::provider::provider_id::ProviderId;
+
+/// Credentials granted by a user to access one of their provider accounts
+pub struct ProviderCredentials {
+    provider_id: ProviderId,
+    access_token: String,         // OAuth2 access token (API key for Last.fm)
+    account_id: Option<String>, // Id or name of the account, needed by some providers (Spotify, Tidal, Last.fm)
+    country_code: Option<String>, // ISO 3166-1 alpha-2 country of the account (Tidal)
+}
+
+impl ProviderCredentials {
+    pub fn new(
+        provider_id: ProviderId,
+        access_token: String,
+        account_id: Option<String>,
+        country_code: Option<String>,
+    ) -> Self {
+        Self {
+            provider_id,
+            access_token,
+            account_id,
+            country_code,
+        }
+    }
+
+    pub fn provider_id(&self) -> &ProviderId {
+        &self.provider_id
+    }
+
+    pub fn access_token(&self) -> &String {
+        &self.access_token
+    }
+
+    pub fn account_id(&self) -> Option<&String> {
+        self.account_id.as_ref()
+    }
+
+    pub fn country_code(&self) -> Option<&String> {
+        self.country_code.as_ref()
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId};
+
+/// Playlist built from the library of the user by a rule, evaluated again periodically
+#[derive(Debug, Clone)]
+pub struct SmartPlaylist {
+    id: Uuid,
+    name: String,
+    rule: String, // Rule in the smart playlist language (ex: "from spotify:liked where year between 1990 and 1999")
+    provider_id: ProviderId, // Provider of the destination playlist
+    playlist_id: Option<PlaylistId>, // Destination playlist, known once created
+    refresh_interval: Duration, // Minimum time between two evaluations
+    evaluated_at: Option<DateTime<Utc>>, // Last evaluation, if any
+}
+
+impl SmartPlaylist {
+    pub fn new(
+        id: Uuid,
+        name: String,
+        rule: String,
+        provider_id: ProviderId,
+        playlist_id: Option<PlaylistId>,
+        refresh_interval: Duration,
+        evaluated_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            rule,
+            provider_id,
+            playlist_id,
+            refresh_interval,
+            evaluated_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn rule(&self) -> &String {
+        &self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: String) {
+        self.rule = rule;
+        // Evaluate the new rule at the next refresh
+        self.evaluated_at = None;
+    }
+
+    pub fn provider_id(&self) -> &ProviderId {
+        &self.provider_id
+    }
+
+    pub fn playlist_id(&self) -> Option<&PlaylistId> {
+        self.playlist_id.as_ref()
+    }
+
+    pub fn set_playlist_id(&mut self, playlist_id: PlaylistId) {
+        self.playlist_id = Some(playlist_id);
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    pub fn evaluated_at(&self) -> Option<&DateTime<Utc>> {
+        self.evaluated_at.as_ref()
+    }
+
+    pub fn set_evaluated_at(&mut self, evaluated_at: DateTime<Utc>) {
+        self.evaluated_at = Some(evaluated_at);
+    }
+
+    /// Whether the playlist was never evaluated, or its refresh interval has elapsed
+    pub fn is_due(&self, now: &DateTime<Utc>) -> bool {
+        self.evaluated_at
+            .is_none_or(|evaluated_at| evaluated_at + self.refresh_interval <= *now)
+    }
+}
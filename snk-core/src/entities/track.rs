@@ -6,11 +6,22 @@ use crate::value_objects::{product_id::ProductId, provider::provider_id::Provide
 
 use super::{album::Album, artist::Artist};
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Track {
     ids: HashSet<ProductId>, // Track ids from external databases & providers (ISRC, UPC, EAN, Provider IDs...)
     name: String,            // Name of the track
     duration_ms: u32,        // Duration of the track
     provider_urls: HashMap<ProviderId, Url>, // External Provider Music URLs
+    explicit: Option<bool>,  // Explicit lyrics, `None` if unknown
+    disc_number: Option<u32>, // Disc of the album the track is on
+    track_number: Option<u32>, // Position of the track on its disc
+    preview_url: Option<Url>, // Short excerpt of the track (usually 30 seconds)
+    available_markets: Option<HashSet<String>>, // ISO 3166-1 alpha-2 countries the track can be played in, `None` if unknown
+    playable: Option<bool>, // Playable in the market of the account it was read with, `None` if unknown
 }
 
 impl Track {
@@ -25,6 +36,12 @@ impl Track {
             name,
             duration_ms,
             provider_urls,
+            explicit: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            available_markets: None,
+            playable: None,
         }
     }
 
@@ -43,13 +60,75 @@ impl Track {
     pub fn provider_urls(&self) -> &HashMap<ProviderId, Url> {
         &self.provider_urls
     }
+
+    pub fn explicit(&self) -> Option<bool> {
+        self.explicit
+    }
+
+    pub fn set_explicit(&mut self, explicit: Option<bool>) {
+        self.explicit = explicit;
+    }
+
+    pub fn disc_number(&self) -> Option<u32> {
+        self.disc_number
+    }
+
+    pub fn track_number(&self) -> Option<u32> {
+        self.track_number
+    }
+
+    /// Position of the track in its album, as disc & track numbers
+    pub fn set_position(&mut self, disc_number: Option<u32>, track_number: Option<u32>) {
+        self.disc_number = disc_number;
+        self.track_number = track_number;
+    }
+
+    pub fn preview_url(&self) -> Option<&Url> {
+        self.preview_url.as_ref()
+    }
+
+    pub fn set_preview_url(&mut self, preview_url: Option<Url>) {
+        self.preview_url = preview_url;
+    }
+
+    pub fn available_markets(&self) -> Option<&HashSet<String>> {
+        self.available_markets.as_ref()
+    }
+
+    pub fn set_available_markets(&mut self, available_markets: Option<HashSet<String>>) {
+        self.available_markets = available_markets;
+    }
+
+    /// Whether the track can be played in the country, `None` if unknown
+    pub fn is_available_in(&self, market: &str) -> Option<bool> {
+        is_available_in(self.available_markets.as_ref(), market)
+    }
+
+    pub fn playable(&self) -> Option<bool> {
+        self.playable
+    }
+
+    pub fn set_playable(&mut self, playable: Option<bool>) {
+        self.playable = playable;
+    }
 }
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct TrackWithAlbumAndArtists {
     pub ids: HashSet<ProductId>, // Track ids from external databases & providers (ISRC, UPC, EAP, Provider IDs...)
     pub name: String,            // Name of the track
     pub duration_ms: u32,        // Duration of the track
     pub provider_urls: HashMap<ProviderId, Url>, // External Provider Music URLs
+    pub explicit: Option<bool>,  // Explicit lyrics, `None` if unknown
+    pub disc_number: Option<u32>, // Disc of the album the track is on
+    pub track_number: Option<u32>, // Position of the track on its disc
+    pub preview_url: Option<Url>, // Short excerpt of the track (usually 30 seconds)
+    pub available_markets: Option<HashSet<String>>, // ISO 3166-1 alpha-2 countries the track can be played in, `None` if unknown
+    pub playable: Option<bool>, // Playable in the market of the account it was read with, `None` if unknown
 
     pub album: Album,         // Album which the track is part of
     pub artists: Vec<Artist>, // Artists present in the music
@@ -69,6 +148,12 @@ impl TrackWithAlbumAndArtists {
             name,
             duration_ms,
             provider_urls,
+            explicit: None,
+            disc_number: None,
+            track_number: None,
+            preview_url: None,
+            available_markets: None,
+            playable: None,
             album,
             artists,
         }
@@ -90,6 +175,42 @@ impl TrackWithAlbumAndArtists {
         &self.provider_urls
     }
 
+    pub fn explicit(&self) -> Option<bool> {
+        self.explicit
+    }
+
+    pub fn disc_number(&self) -> Option<u32> {
+        self.disc_number
+    }
+
+    pub fn track_number(&self) -> Option<u32> {
+        self.track_number
+    }
+
+    pub fn preview_url(&self) -> Option<&Url> {
+        self.preview_url.as_ref()
+    }
+
+    pub fn available_markets(&self) -> Option<&HashSet<String>> {
+        self.available_markets.as_ref()
+    }
+
+    /// Whether the track can be played in the country, `None` if unknown
+    pub fn is_available_in(&self, market: &str) -> Option<bool> {
+        is_available_in(self.available_markets.as_ref(), market)
+    }
+
+    pub fn playable(&self) -> Option<bool> {
+        self.playable
+    }
+
+    /// Whether the track can be played by the account, from its playability, else from the markets
+    /// it's available in. `None` if unknown
+    pub fn is_playable_in(&self, market: Option<&str>) -> Option<bool> {
+        self.playable
+            .or_else(|| market.and_then(|market| self.is_available_in(market)))
+    }
+
     pub fn album(&self) -> &Album {
         &self.album
     }
@@ -98,3 +219,11 @@ impl TrackWithAlbumAndArtists {
         &self.artists
     }
 }
+
+fn is_available_in(available_markets: Option<&HashSet<String>>, market: &str) -> Option<bool> {
+    available_markets.map(|markets| {
+        markets
+            .iter()
+            .any(|available| available.eq_ignore_ascii_case(market))
+    })
+}
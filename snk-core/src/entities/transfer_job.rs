@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::{entities::playlist::PlaylistSpec, value_objects::playlist_id::PlaylistId};
+
+/// Progress of a transfer, saved along the way so that a retry continues where it stopped
+#[derive(Debug, Clone)]
+pub struct TransferJob {
+    id: Uuid,
+    playlist_spec: Option<PlaylistSpec>, // Metadata & cover of the playlist to create, if not transferring into an existing one
+    destination_playlist_id: Option<PlaylistId>, // Known once the playlist is created
+    checkpoints: HashMap<String, Option<String>>, // Key of a source track => id of its match at the destination, if any
+}
+
+impl TransferJob {
+    pub fn new(
+        id: Uuid,
+        playlist_spec: Option<PlaylistSpec>,
+        destination_playlist_id: Option<PlaylistId>,
+        checkpoints: HashMap<String, Option<String>>,
+    ) -> Self {
+        Self {
+            id,
+            playlist_spec,
+            destination_playlist_id,
+            checkpoints,
+        }
+    }
+
+    /// Job transferring tracks into an existing playlist
+    pub fn into_playlist(id: Uuid, playlist_id: PlaylistId) -> Self {
+        Self::new(id, None, Some(playlist_id), HashMap::new())
+    }
+
+    /// Job transferring tracks into a playlist to create
+    pub fn into_new_playlist(id: Uuid, spec: PlaylistSpec) -> Self {
+        Self::new(id, Some(spec), None, HashMap::new())
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn playlist_spec(&self) -> Option<&PlaylistSpec> {
+        self.playlist_spec.as_ref()
+    }
+
+    pub fn destination_playlist_id(&self) -> Option<&PlaylistId> {
+        self.destination_playlist_id.as_ref()
+    }
+
+    pub fn set_destination_playlist_id(&mut self, playlist_id: PlaylistId) {
+        self.destination_playlist_id = Some(playlist_id);
+    }
+
+    pub fn checkpoints(&self) -> &HashMap<String, Option<String>> {
+        &self.checkpoints
+    }
+
+    /// Outcome of the matching of a source track, `Some(None)` when no match was found
+    pub fn checkpoint(&self, key: &str) -> Option<Option<&String>> {
+        self.checkpoints.get(key).map(Option::as_ref)
+    }
+
+    pub fn set_checkpoint(&mut self, key: String, destination_id: Option<String>) {
+        self.checkpoints.insert(key, destination_id);
+    }
+}
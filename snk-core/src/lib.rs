@@ -2,4 +2,8 @@
 
 pub mod contracts;
 pub mod entities;
+pub mod services;
+pub mod use_cases;
 pub mod value_objects;
+#[cfg(feature = "serde")]
+pub mod wire;
@@ -0,0 +1,668 @@
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    contracts::repositories::{
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        playlist_repository::{PlaylistRepository, PlaylistRepositoryError},
+    },
+    entities::{identity::RecordingKind, track::TrackWithAlbumAndArtists},
+    value_objects::{
+        isrc::Isrc, playlist_id::PlaylistId, product_id::ProductId,
+        provider::provider_id::ProviderId,
+    },
+};
+
+use super::{
+    identity_graph::{IdentityGraph, IdentityGraphError},
+    track_matcher::{is_same_recording, provider_track_id},
+};
+
+#[derive(Debug, Error)]
+pub enum DeduplicatorError {
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("IdentityGraph: {0}")]
+    IdentityGraphError(#[from] IdentityGraphError),
+}
+
+pub type DeduplicatorResult<T> = Result<T, DeduplicatorError>;
+
+/// Which tracks are considered the same
+#[derive(Debug, Clone, Copy)]
+pub struct Equivalence {
+    /// Same id at the provider of the playlist
+    pub provider_id: bool,
+    /// Same ISRC
+    pub isrc: bool,
+    /// Same title, artists & duration, like the single & album versions of a song
+    pub metadata: bool,
+}
+
+impl Default for Equivalence {
+    /// Exact duplicates only
+    fn default() -> Self {
+        Self {
+            provider_id: true,
+            isrc: true,
+            metadata: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    ProviderId,
+    Isrc,
+    /// Same recording in the identity graph
+    Identity,
+    Metadata,
+}
+
+/// Track of a playlist, by its position
+#[derive(Debug, Clone)]
+pub struct PlaylistTrack {
+    pub position: usize,
+    /// Id at the provider of the playlist, `None` if unknown (the track cannot be removed)
+    pub id: Option<String>,
+    pub name: String,
+    pub artists: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    pub track: PlaylistTrack,
+    pub reason: DuplicateReason,
+}
+
+/// Tracks considered the same, the first one of the playlist being kept
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub kept: PlaylistTrack,
+    pub duplicates: Vec<Duplicate>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DedupReport {
+    /// Duplicates removed from the playlist
+    pub removed: usize,
+    /// Duplicates left, their id at the provider being unknown
+    pub skipped: usize,
+}
+
+/// Find & remove the duplicates of a playlist
+///
+/// Tracks of the same recording in the identity graph are always the same.
+pub struct Deduplicator<
+    'a,
+    P: PlaylistRepository,
+    I: IdentityRepository<RecordingKind> = NoIdentityRepository,
+> {
+    provider_id: ProviderId,
+    playlist_repository: &'a P,
+    identity_graph: IdentityGraph<'a, RecordingKind, I>,
+    equivalence: Equivalence,
+}
+
+impl<'a, P: PlaylistRepository> Deduplicator<'a, P> {
+    /// Arguments:
+    /// - provider_id: provider of the playlists
+    /// - playlist_repository: playlists of the provider
+    /// - equivalence: which tracks are considered the same
+    pub fn new(
+        provider_id: ProviderId,
+        playlist_repository: &'a P,
+        equivalence: Equivalence,
+    ) -> Self {
+        Self {
+            provider_id,
+            playlist_repository,
+            identity_graph: IdentityGraph::new(&NoIdentityRepository),
+            equivalence,
+        }
+    }
+}
+
+impl<'a, P: PlaylistRepository, I: IdentityRepository<RecordingKind>> Deduplicator<'a, P, I> {
+    /// Recordings shared with the other services, telling apart the tracks of the same recording
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> Deduplicator<'a, P, J> {
+        Deduplicator {
+            provider_id: self.provider_id,
+            playlist_repository: self.playlist_repository,
+            identity_graph,
+            equivalence: self.equivalence,
+        }
+    }
+
+    /// Duplicates of the playlist, without changing it
+    pub async fn preview(
+        &self,
+        playlist_id: &PlaylistId,
+    ) -> DeduplicatorResult<Vec<DuplicateGroup>> {
+        let tracks = self.playlist_repository.get_tracks(playlist_id).await?;
+
+        self.find_duplicates(&tracks).await
+    }
+
+    /// Remove the duplicates of a preview (possibly edited) from the playlist
+    ///
+    /// Removing an id removes all of its occurrences, so the duplicates of a kept id
+    /// are removed with it and the kept track is added back once, at the end of the playlist.
+    /// When only some chunks of the removal fail, the kept tracks removed by the other chunks
+    /// are still added back before reporting the failure.
+    pub async fn apply(
+        &self,
+        playlist_id: &PlaylistId,
+        groups: &[DuplicateGroup],
+    ) -> DeduplicatorResult<DedupReport> {
+        let mut report = DedupReport::default();
+        let mut removed_ids = vec![];
+        let mut added_back_ids = vec![];
+
+        for group in groups {
+            for duplicate in &group.duplicates {
+                let Some(id) = &duplicate.track.id else {
+                    report.skipped += 1;
+                    continue;
+                };
+
+                if group.kept.id.as_ref() == Some(id) && !added_back_ids.contains(id) {
+                    added_back_ids.push(id.clone());
+                }
+
+                if !removed_ids.contains(id) {
+                    removed_ids.push(id.clone());
+                }
+
+                report.removed += 1;
+            }
+        }
+
+        let removal = match removed_ids.is_empty() {
+            true => Ok(()),
+            false => {
+                self.playlist_repository
+                    .delete_tracks(playlist_id, &removed_ids, None)
+                    .await
+            }
+        };
+
+        let failure = match removal {
+            Ok(()) => None,
+            Err(PlaylistRepositoryError::PartialFailure(chunks)) => {
+                added_back_ids.retain(|id| {
+                    chunks
+                        .iter()
+                        .any(|chunk| chunk.error.is_none() && chunk.ids.contains(id))
+                });
+
+                Some(PlaylistRepositoryError::PartialFailure(chunks))
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if !added_back_ids.is_empty() {
+            self.playlist_repository
+                .add_tracks(playlist_id, &added_back_ids, None)
+                .await?;
+        }
+
+        if let Some(err) = failure {
+            return Err(err.into());
+        }
+
+        Ok(report)
+    }
+
+    /// Group the tracks being the same, in the order of the playlist
+    pub async fn find_duplicates(
+        &self,
+        tracks: &[TrackWithAlbumAndArtists],
+    ) -> DeduplicatorResult<Vec<DuplicateGroup>> {
+        let recordings = self.identity_graph.recordings(tracks).await?;
+
+        Ok(find_duplicates(
+            tracks,
+            &recordings,
+            &self.provider_id,
+            &self.equivalence,
+        ))
+    }
+}
+
+/// Group the tracks being the same, the first one of each group being kept
+///
+/// Arguments:
+/// - recordings: identity of each track in the recordings graph, if known (can be empty)
+/// - provider_id: provider of the ids reported, and compared by the provider id equivalence
+pub fn find_duplicates(
+    tracks: &[TrackWithAlbumAndArtists],
+    recordings: &[Option<Uuid>],
+    provider_id: &ProviderId,
+    equivalence: &Equivalence,
+) -> Vec<DuplicateGroup> {
+    // Index of the group of each key, for the exact equivalences
+    let mut groups_by_id: HashMap<&String, usize> = HashMap::new();
+    let mut groups_by_isrc: HashMap<&Isrc, usize> = HashMap::new();
+    let mut groups_by_recording: HashMap<&Uuid, usize> = HashMap::new();
+    // Group & first track of each group, for the fuzzy one
+    let mut kept_tracks: Vec<&TrackWithAlbumAndArtists> = vec![];
+    let mut groups: Vec<DuplicateGroup> = vec![];
+
+    for (position, track) in tracks.iter().enumerate() {
+        let id = provider_track_id(track, provider_id);
+        let recording = recordings.get(position).and_then(Option::as_ref);
+        let isrcs = track
+            .ids()
+            .iter()
+            .filter_map(|id| match id {
+                ProductId::ISRC(isrc) => Some(isrc),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let playlist_track = PlaylistTrack {
+            position,
+            id: id.cloned(),
+            name: track.name().clone(),
+            artists: track
+                .artists()
+                .iter()
+                .map(|artist| artist.name().clone())
+                .collect(),
+        };
+
+        let found = equivalence
+            .provider_id
+            .then(|| id.and_then(|id| groups_by_id.get(id)))
+            .flatten()
+            .map(|group| (*group, DuplicateReason::ProviderId))
+            .or_else(|| {
+                equivalence
+                    .isrc
+                    .then(|| isrcs.iter().find_map(|isrc| groups_by_isrc.get(isrc)))
+                    .flatten()
+                    .map(|group| (*group, DuplicateReason::Isrc))
+            })
+            .or_else(|| {
+                recording
+                    .and_then(|recording| groups_by_recording.get(recording))
+                    .map(|group| (*group, DuplicateReason::Identity))
+            })
+            .or_else(|| {
+                equivalence
+                    .metadata
+                    .then(|| {
+                        kept_tracks
+                            .iter()
+                            .position(|kept| is_same_recording(kept, track))
+                    })
+                    .flatten()
+                    .map(|group| (group, DuplicateReason::Metadata))
+            });
+
+        let group = match found {
+            Some((group, reason)) => {
+                groups[group].duplicates.push(Duplicate {
+                    track: playlist_track,
+                    reason,
+                });
+
+                group
+            }
+            None => {
+                groups.push(DuplicateGroup {
+                    kept: playlist_track,
+                    duplicates: vec![],
+                });
+                kept_tracks.push(track);
+
+                groups.len() - 1
+            }
+        };
+
+        if let Some(id) = id {
+            groups_by_id.entry(id).or_insert(group);
+        }
+
+        for isrc in isrcs {
+            groups_by_isrc.entry(isrc).or_insert(group);
+        }
+
+        if let Some(recording) = recording {
+            groups_by_recording.entry(recording).or_insert(group);
+        }
+    }
+
+    groups.retain(|group| !group.duplicates.is_empty());
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::playlist_repository::{
+            ChunkResult, PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+        },
+        entities::{
+            album::Album,
+            artist::Artist,
+            playlist::{Playlist, PlaylistSpec},
+            track::TrackWithAlbumAndArtists,
+        },
+        value_objects::{
+            isrc::Isrc, playlist_id::PlaylistId, product_id::ProductId,
+            provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::{find_duplicates, Deduplicator, DeduplicatorError, DuplicateReason, Equivalence};
+
+    fn track(id: &str, isrc: &str, name: &str, duration_ms: u32) -> TrackWithAlbumAndArtists {
+        let mut ids = HashSet::from_iter([ProductId::Provider((
+            ProviderId::new("stub".to_string()),
+            id.to_string(),
+        ))]);
+
+        if !isrc.is_empty() {
+            ids.insert(ProductId::ISRC(Isrc::parse(isrc).unwrap()));
+        }
+
+        TrackWithAlbumAndArtists::new(
+            ids,
+            name.to_string(),
+            duration_ms,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                "NewJeans".to_string(),
+                HashMap::new(),
+            )],
+        )
+    }
+
+    /// Id, ISRC, title & duration of a track
+    type StubTrack = (String, String, String, u32);
+
+    /// Playlist removing every occurrence of the deleted ids, like the providers do
+    #[derive(Default)]
+    struct StubPlaylistRepository {
+        tracks: RefCell<Vec<StubTrack>>,
+        /// Id the deletions fail for, each id being deleted by its own chunk
+        rejected_id: RefCell<Option<String>>,
+    }
+
+    impl PlaylistRepository for StubPlaylistRepository {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![])
+        }
+
+        async fn create(&self, _spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            Err(PlaylistRepositoryError::ServiceError(
+                "deduplicating never creates a playlist".to_string(),
+            ))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            _spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.tracks.borrow_mut().extend(
+                ids.iter()
+                    .map(|id| (id.clone(), String::new(), id.clone(), 0)),
+            );
+
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            let rejected_id = self.rejected_id.borrow().clone();
+
+            self.tracks
+                .borrow_mut()
+                .retain(|(id, ..)| !ids.contains(id) || rejected_id.as_ref() == Some(id));
+
+            match rejected_id {
+                None => Ok(()),
+                Some(rejected_id) => Err(PlaylistRepositoryError::PartialFailure(
+                    ids.iter()
+                        .map(|id| ChunkResult {
+                            ids: vec![id.clone()],
+                            error: (*id == rejected_id).then(|| "rejected".to_string()),
+                        })
+                        .collect(),
+                )),
+            }
+        }
+
+        async fn get_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(self
+                .tracks
+                .borrow()
+                .iter()
+                .map(|(id, isrc, name, duration_ms)| track(id, isrc, name, *duration_ms))
+                .collect())
+        }
+    }
+
+    fn playlist() -> Vec<StubTrack> {
+        [
+            ("1", "USA2P2414843", "How Sweet", 219_000),
+            ("2", "", "Supernatural", 191_000),
+            ("1", "USA2P2414843", "How Sweet", 219_000),
+            // Album version of the same recording
+            ("3", "USA2P2414843", "How Sweet", 219_000),
+            // Single version, without ISRC
+            ("4", "", "Supernatural (Radio Edit)", 193_000),
+        ]
+        .into_iter()
+        .map(|(id, isrc, name, duration_ms)| {
+            (
+                id.to_string(),
+                isrc.to_string(),
+                name.to_string(),
+                duration_ms,
+            )
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates() {
+        let repository = StubPlaylistRepository::default();
+        let provider_id = ProviderId::new("stub".to_string());
+        let tracks = playlist()
+            .iter()
+            .map(|(id, isrc, name, duration_ms)| track(id, isrc, name, *duration_ms))
+            .collect::<Vec<_>>();
+
+        let exact = Deduplicator::new(
+            provider_id.clone(),
+            &repository,
+            Equivalence {
+                provider_id: true,
+                isrc: false,
+                metadata: false,
+            },
+        )
+        .find_duplicates(&tracks)
+        .await
+        .expect("found");
+
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].kept.position, 0);
+        assert_eq!(exact[0].duplicates.len(), 1);
+        assert_eq!(exact[0].duplicates[0].track.position, 2);
+
+        let groups = Deduplicator::new(provider_id.clone(), &repository, Equivalence::default())
+            .find_duplicates(&tracks)
+            .await
+            .expect("found");
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0]
+                .duplicates
+                .iter()
+                .map(|duplicate| duplicate.reason)
+                .collect::<Vec<_>>(),
+            vec![DuplicateReason::ProviderId, DuplicateReason::Isrc]
+        );
+
+        let fuzzy = Deduplicator::new(
+            provider_id,
+            &repository,
+            Equivalence {
+                metadata: true,
+                ..Equivalence::default()
+            },
+        )
+        .find_duplicates(&tracks)
+        .await
+        .expect("found");
+
+        assert_eq!(fuzzy.len(), 2);
+        assert_eq!(fuzzy[1].kept.position, 1);
+        assert_eq!(fuzzy[1].duplicates[0].track.position, 4);
+        assert_eq!(fuzzy[1].duplicates[0].reason, DuplicateReason::Metadata);
+    }
+
+    #[test]
+    fn test_find_duplicates_of_recording() {
+        let tracks = [
+            track("1", "", "How Sweet", 219_000),
+            track("2", "", "Supernatural", 191_000),
+            track("3", "", "How Sweet (Radio Edit)", 180_000),
+        ];
+        let recording = Uuid::new_v4();
+
+        // Different ids & metadata, linked in the identity graph
+        let groups = find_duplicates(
+            &tracks,
+            &[Some(recording), None, Some(recording)],
+            &ProviderId::new("stub".to_string()),
+            &Equivalence::default(),
+        );
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].kept.position, 0);
+        assert_eq!(groups[0].duplicates[0].track.position, 2);
+        assert_eq!(groups[0].duplicates[0].reason, DuplicateReason::Identity);
+    }
+
+    #[tokio::test]
+    async fn test_apply() {
+        let repository = StubPlaylistRepository::default();
+        let playlist_id = PlaylistId::Owned("playlist".to_string());
+        let deduplicator = Deduplicator::new(
+            ProviderId::new("stub".to_string()),
+            &repository,
+            Equivalence::default(),
+        );
+
+        repository.tracks.replace(playlist());
+
+        let groups = deduplicator.preview(&playlist_id).await.expect("previewed");
+        let report = deduplicator
+            .apply(&playlist_id, &groups)
+            .await
+            .expect("applied");
+
+        assert_eq!(report.removed, 2);
+        assert_eq!(report.skipped, 0);
+
+        let ids = repository
+            .tracks
+            .borrow()
+            .iter()
+            .map(|(id, ..)| id.clone())
+            .collect::<Vec<_>>();
+
+        // The kept track is added back once, at the end
+        assert_eq!(ids, vec!["2", "4", "1"]);
+        assert!(deduplicator
+            .preview(&playlist_id)
+            .await
+            .expect("previewed")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_partial_failure() {
+        let repository = StubPlaylistRepository::default();
+        let playlist_id = PlaylistId::Owned("playlist".to_string());
+        let deduplicator = Deduplicator::new(
+            ProviderId::new("stub".to_string()),
+            &repository,
+            Equivalence::default(),
+        );
+
+        repository.tracks.replace(playlist());
+
+        let groups = deduplicator.preview(&playlist_id).await.expect("previewed");
+
+        repository.rejected_id.replace(Some("3".to_string()));
+
+        assert!(matches!(
+            deduplicator.apply(&playlist_id, &groups).await,
+            Err(DeduplicatorError::PlaylistRepositoryError(
+                PlaylistRepositoryError::PartialFailure(_)
+            ))
+        ));
+
+        let ids = repository
+            .tracks
+            .borrow()
+            .iter()
+            .map(|(id, ..)| id.clone())
+            .collect::<Vec<_>>();
+
+        // The kept track removed by a successful chunk is still added back
+        assert_eq!(ids, vec!["2", "3", "4", "1"]);
+    }
+}
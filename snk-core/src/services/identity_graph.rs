@@ -0,0 +1,487 @@
+use std::{cmp::Reverse, collections::HashSet, marker::PhantomData};
+
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    contracts::repositories::identity_repository::{IdentityRepository, IdentityRepositoryError},
+    entities::{
+        album::Album,
+        artist::Artist,
+        identity::{ArtistKind, Identity, IdentityKind, RecordingKind, ReleaseKind},
+        track::TrackWithAlbumAndArtists,
+    },
+    value_objects::product_id::ProductId,
+};
+
+#[derive(Debug, Error)]
+pub enum IdentityGraphError {
+    #[error("IdentityRepository: {0}")]
+    IdentityRepositoryError(#[from] IdentityRepositoryError),
+    #[error("Identity {0} not found")]
+    NotFound(Uuid),
+    #[error("Nothing to observe, no id given")]
+    NoIds,
+    #[error("Invalid split: {0}")]
+    InvalidSplit(String),
+}
+
+pub type IdentityGraphResult<T> = Result<T, IdentityGraphError>;
+
+/// How an observation changed the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// No identity had any of the ids
+    Created,
+    /// The ids were added to the only identity sharing some of them
+    Extended,
+    /// The observation linked several identities, merged into one (their ids are removed)
+    Merged(Vec<Uuid>),
+}
+
+pub struct Observation<K: IdentityKind> {
+    /// Identity now holding the observed ids
+    pub identity: Identity<K>,
+    pub resolution: Resolution,
+    /// Identities sharing ids with the observation but having other strong ids,
+    /// the ids they hold are left to them
+    pub conflicts: Vec<Uuid>,
+}
+
+/// Canonical identities of a kind, built from the ids seen together on tracks, albums & artists
+///
+/// Rules:
+/// - ids seen together belong to the same identity, identities linked by an observation are merged
+/// - identities with disjoint strong ids (ISRC for recordings, barcodes for releases...) are
+///   never merged, the ids they share stay with the first identity holding them
+/// - an identity wrongly merged can be split, moving some of its ids to a new identity
+pub struct IdentityGraph<'a, K: IdentityKind, R: IdentityRepository<K>> {
+    identity_repository: &'a R,
+    kind: PhantomData<K>,
+}
+
+/// Graph sharing the repository, without requiring the repository to be `Clone`
+impl<K: IdentityKind, R: IdentityRepository<K>> Clone for IdentityGraph<'_, K, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.identity_repository)
+    }
+}
+
+impl<'a, K: IdentityKind, R: IdentityRepository<K>> IdentityGraph<'a, K, R> {
+    pub fn new(identity_repository: &'a R) -> Self {
+        Self {
+            identity_repository,
+            kind: PhantomData,
+        }
+    }
+
+    /// Identity sharing the most ids with the given ones, if any
+    pub async fn find(
+        &self,
+        ids: impl IntoIterator<Item = ProductId>,
+    ) -> IdentityGraphResult<Option<Identity<K>>> {
+        let ids = ids.into_iter().map(normalize_id).collect::<HashSet<_>>();
+
+        Ok(self
+            .identity_repository
+            .find_by_ids(&ids)
+            .await?
+            .into_iter()
+            .max_by_key(|identity| identity.ids().intersection(&ids).count()))
+    }
+
+    /// Record ids seen together, under the given name if the identity is new
+    pub async fn observe(
+        &self,
+        name: &str,
+        ids: impl IntoIterator<Item = ProductId>,
+    ) -> IdentityGraphResult<Observation<K>> {
+        let ids = ids.into_iter().map(normalize_id).collect::<HashSet<_>>();
+
+        if ids.is_empty() {
+            return Err(IdentityGraphError::NoIds);
+        }
+
+        let mut candidates = self.identity_repository.find_by_ids(&ids).await?;
+
+        candidates.sort_by_key(|candidate| Reverse(candidate.ids().intersection(&ids).count()));
+
+        // Strong ids of the identity being built, growing as candidates are accepted
+        let mut strong_ids = ids
+            .iter()
+            .filter(|id| K::is_strong_id(id))
+            .cloned()
+            .collect::<HashSet<_>>();
+        let mut accepted = vec![];
+        let mut conflicts = vec![];
+
+        for candidate in candidates {
+            let candidate_strong_ids = candidate
+                .strong_ids()
+                .into_iter()
+                .cloned()
+                .collect::<HashSet<_>>();
+
+            if strong_ids.is_empty()
+                || candidate_strong_ids.is_empty()
+                || !strong_ids.is_disjoint(&candidate_strong_ids)
+            {
+                strong_ids.extend(candidate_strong_ids);
+                accepted.push(candidate);
+            } else {
+                conflicts.push(candidate);
+            }
+        }
+
+        let new_ids = ids
+            .into_iter()
+            .filter(|id| !conflicts.iter().any(|conflict| conflict.ids().contains(id)));
+        let mut accepted = accepted.into_iter();
+
+        let (identity, resolution) = match accepted.next() {
+            None => (
+                Identity::new(Uuid::new_v4(), name.to_string(), new_ids.collect()),
+                Resolution::Created,
+            ),
+            Some(mut identity) => {
+                let mut merged = vec![];
+
+                for other in accepted {
+                    identity.extend_ids(other.ids().iter().cloned());
+                    merged.push(other.id());
+                }
+
+                identity.extend_ids(new_ids);
+
+                if identity.name().is_empty() {
+                    identity.set_name(name.to_string());
+                }
+
+                match merged.is_empty() {
+                    true => (identity, Resolution::Extended),
+                    false => (identity, Resolution::Merged(merged)),
+                }
+            }
+        };
+
+        match &resolution {
+            Resolution::Merged(merged) => self.identity_repository.merge(&identity, merged).await?,
+            Resolution::Created | Resolution::Extended => {
+                self.identity_repository.save(&identity).await?
+            }
+        }
+
+        Ok(Observation {
+            identity,
+            resolution,
+            conflicts: conflicts.iter().map(Identity::id).collect(),
+        })
+    }
+
+    /// Move some ids of an identity to a new identity
+    ///
+    /// Returns:
+    /// The identity left & the new one
+    pub async fn split(
+        &self,
+        id: Uuid,
+        ids: impl IntoIterator<Item = ProductId>,
+        name: &str,
+    ) -> IdentityGraphResult<(Identity<K>, Identity<K>)> {
+        let ids = ids.into_iter().map(normalize_id).collect::<HashSet<_>>();
+        let mut identity = self
+            .identity_repository
+            .get(id)
+            .await?
+            .ok_or(IdentityGraphError::NotFound(id))?;
+
+        let moved_ids = identity.remove_ids(&ids);
+
+        if moved_ids.is_empty() {
+            return Err(IdentityGraphError::InvalidSplit(
+                "none of the ids belongs to the identity".to_string(),
+            ));
+        }
+
+        if identity.ids().is_empty() {
+            return Err(IdentityGraphError::InvalidSplit(
+                "no id would be left to the identity".to_string(),
+            ));
+        }
+
+        let split_identity = Identity::new(Uuid::new_v4(), name.to_string(), moved_ids);
+
+        self.identity_repository
+            .split(&identity, &split_identity)
+            .await?;
+
+        Ok((identity, split_identity))
+    }
+}
+
+impl<R: IdentityRepository<RecordingKind>> IdentityGraph<'_, RecordingKind, R> {
+    /// Recording of each track, if known
+    pub async fn recordings(
+        &self,
+        tracks: &[TrackWithAlbumAndArtists],
+    ) -> IdentityGraphResult<Vec<Option<Uuid>>> {
+        let mut recordings = vec![];
+
+        for track in tracks {
+            let recording = self.find(track.ids().iter().cloned()).await?;
+
+            recordings.push(recording.map(|recording| recording.id()));
+        }
+
+        Ok(recordings)
+    }
+
+    pub async fn observe_track(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> IdentityGraphResult<Observation<RecordingKind>> {
+        self.observe(track.name(), track.ids().iter().cloned())
+            .await
+    }
+}
+
+impl<R: IdentityRepository<ReleaseKind>> IdentityGraph<'_, ReleaseKind, R> {
+    pub async fn observe_album(
+        &self,
+        album: &Album,
+    ) -> IdentityGraphResult<Observation<ReleaseKind>> {
+        self.observe(album.name(), album.ids().iter().cloned())
+            .await
+    }
+}
+
+impl<R: IdentityRepository<ArtistKind>> IdentityGraph<'_, ArtistKind, R> {
+    pub async fn observe_artist(
+        &self,
+        artist: &Artist,
+    ) -> IdentityGraphResult<Observation<ArtistKind>> {
+        self.observe(
+            artist.name(),
+            artist
+                .ids()
+                .iter()
+                .map(|(provider_id, id)| ProductId::Provider((provider_id.clone(), id.clone()))),
+        )
+        .await
+    }
+}
+
+/// Single form of equivalent ids: UPC rather than EAN prefixed with 0
+pub fn normalize_id(id: ProductId) -> ProductId {
+    match id {
+        ProductId::EAN(ean) => ean.to_upc().map_or(ProductId::EAN(ean), ProductId::UPC),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::identity_repository::{
+            IdentityRepository, IdentityRepositoryResult,
+        },
+        entities::identity::{Identity, RecordingKind},
+        value_objects::{
+            barcode::{Ean, Upc},
+            isrc::Isrc,
+            product_id::ProductId,
+            provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::{normalize_id, IdentityGraph, Resolution};
+
+    #[derive(Default)]
+    struct StubIdentityRepository {
+        identities: RefCell<HashMap<Uuid, Identity<RecordingKind>>>,
+    }
+
+    impl IdentityRepository<RecordingKind> for StubIdentityRepository {
+        async fn get(&self, id: Uuid) -> IdentityRepositoryResult<Option<Identity<RecordingKind>>> {
+            Ok(self.identities.borrow().get(&id).cloned())
+        }
+
+        async fn find_by_ids(
+            &self,
+            ids: &HashSet<ProductId>,
+        ) -> IdentityRepositoryResult<Vec<Identity<RecordingKind>>> {
+            Ok(self
+                .identities
+                .borrow()
+                .values()
+                .filter(|identity| !identity.ids().is_disjoint(ids))
+                .cloned()
+                .collect())
+        }
+
+        async fn save(&self, identity: &Identity<RecordingKind>) -> IdentityRepositoryResult<()> {
+            let mut identities = self.identities.borrow_mut();
+
+            assert!(
+                identities
+                    .values()
+                    .filter(|other| other.id() != identity.id())
+                    .all(|other| other.ids().is_disjoint(identity.ids())),
+                "an id belongs to one identity"
+            );
+
+            identities.insert(identity.id(), identity.clone());
+
+            Ok(())
+        }
+
+        async fn merge(
+            &self,
+            identity: &Identity<RecordingKind>,
+            merged: &[Uuid],
+        ) -> IdentityRepositoryResult<()> {
+            for id in merged {
+                self.delete(*id).await?;
+            }
+
+            self.save(identity).await
+        }
+
+        async fn split(
+            &self,
+            identity: &Identity<RecordingKind>,
+            split_identity: &Identity<RecordingKind>,
+        ) -> IdentityRepositoryResult<()> {
+            self.save(identity).await?;
+            self.save(split_identity).await
+        }
+
+        async fn delete(&self, id: Uuid) -> IdentityRepositoryResult<()> {
+            self.identities.borrow_mut().remove(&id);
+
+            Ok(())
+        }
+    }
+
+    fn isrc(isrc: &str) -> ProductId {
+        ProductId::ISRC(Isrc::parse(isrc).unwrap())
+    }
+
+    fn provider(provider_id: &str, id: &str) -> ProductId {
+        ProductId::Provider((ProviderId::new(provider_id.to_string()), id.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_observe_merges_linked_identities() {
+        let repository = StubIdentityRepository::default();
+        let graph = IdentityGraph::new(&repository);
+
+        let spotify = graph
+            .observe(
+                "How Sweet",
+                [isrc("USA2P2414843"), provider("spotify", "1")],
+            )
+            .await
+            .expect("observed");
+        let deezer = graph
+            .observe("How Sweet", [provider("deezer", "2")])
+            .await
+            .expect("observed");
+
+        assert_eq!(spotify.resolution, Resolution::Created);
+        assert_eq!(deezer.resolution, Resolution::Created);
+
+        // A track known by both providers links the two identities
+        let linked = graph
+            .observe(
+                "How Sweet",
+                [provider("deezer", "2"), provider("spotify", "1")],
+            )
+            .await
+            .expect("observed");
+
+        assert!(matches!(linked.resolution, Resolution::Merged(merged) if merged.len() == 1));
+        assert_eq!(linked.identity.ids().len(), 3);
+        assert_eq!(repository.identities.borrow().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_observe_keeps_conflicting_identities() {
+        let repository = StubIdentityRepository::default();
+        let graph = IdentityGraph::new(&repository);
+
+        let original = graph
+            .observe(
+                "How Sweet",
+                [isrc("usa2p2414843"), provider("spotify", "1")],
+            )
+            .await
+            .expect("observed");
+
+        // A provider attaching its id to another recording
+        let other = graph
+            .observe(
+                "How Sweet (Instrumental)",
+                [isrc("USA2P2414851"), provider("spotify", "1")],
+            )
+            .await
+            .expect("observed");
+
+        assert_eq!(other.resolution, Resolution::Created);
+        assert_eq!(other.conflicts, vec![original.identity.id()]);
+        assert!(!other.identity.ids().contains(&provider("spotify", "1")));
+        assert!(original.identity.ids().contains(&isrc("USA2P2414843")));
+        assert_eq!(repository.identities.borrow().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_split() {
+        let repository = StubIdentityRepository::default();
+        let graph = IdentityGraph::new(&repository);
+
+        let merged = graph
+            .observe(
+                "How Sweet",
+                [
+                    isrc("USA2P2414843"),
+                    provider("spotify", "1"),
+                    provider("deezer", "2"),
+                ],
+            )
+            .await
+            .expect("observed");
+
+        let (left, split) = graph
+            .split(
+                merged.identity.id(),
+                [provider("deezer", "2")],
+                "How Sweet (Live)",
+            )
+            .await
+            .expect("split");
+
+        assert_eq!(left.ids().len(), 2);
+        assert_eq!(split.name(), "How Sweet (Live)");
+        assert!(split.ids().contains(&provider("deezer", "2")));
+        assert!(graph
+            .split(merged.identity.id(), [provider("tidal", "3")], "Other")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_normalize_id() {
+        assert!(
+            normalize_id(ProductId::EAN(Ean::parse("0196922889738").unwrap()))
+                == ProductId::UPC(Upc::parse("196922889738").unwrap())
+        );
+        assert!(normalize_id(isrc("usa2p2414843")) == isrc("USA2P2414843"));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    contracts::repositories::catalog_search_repository::{
+        CatalogSearchRepository, CatalogSearchRepositoryResult,
+    },
+    entities::{album::AlbumWithArtists, artist::Artist},
+    value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+};
+
+use super::track_matcher::normalize;
+
+/// Find the equivalent of saved albums & followed artists in the catalog of a provider
+///
+/// Albums are matched by UPC first, then by searching title & artists.
+pub struct LibraryMatcher<'a, C: CatalogSearchRepository> {
+    provider_id: ProviderId,
+    catalog_search_repository: &'a C,
+}
+
+impl<'a, C: CatalogSearchRepository> LibraryMatcher<'a, C> {
+    pub fn new(provider_id: ProviderId, catalog_search_repository: &'a C) -> Self {
+        Self {
+            provider_id,
+            catalog_search_repository,
+        }
+    }
+
+    /// Id of the album in the catalog of the provider, if any
+    pub async fn find_album(
+        &self,
+        album: &AlbumWithArtists,
+    ) -> CatalogSearchRepositoryResult<Option<String>> {
+        if let Some(id) = provider_album_id(album, &self.provider_id) {
+            return Ok(Some(id.clone()));
+        }
+
+        for id in album.album().ids() {
+            // An EAN starting with 0 is the UPC prefixed
+            let upc = match id {
+                ProductId::UPC(upc) => upc.clone(),
+                ProductId::EAN(ean) => match ean.to_upc() {
+                    Some(upc) => upc,
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            if let Some(found) = self
+                .catalog_search_repository
+                .find_album_by_upc(&upc)
+                .await?
+            {
+                if let Some(id) = provider_album_id(&found, &self.provider_id) {
+                    return Ok(Some(id.clone()));
+                }
+            }
+        }
+
+        let candidates = self.catalog_search_repository.search_albums(album).await?;
+
+        Ok(candidates
+            .iter()
+            .find(|candidate| is_same_album(album, candidate))
+            .and_then(|candidate| provider_album_id(candidate, &self.provider_id))
+            .cloned())
+    }
+
+    /// Id of the artist in the catalog of the provider, if any
+    pub async fn find_artist(
+        &self,
+        artist: &Artist,
+    ) -> CatalogSearchRepositoryResult<Option<String>> {
+        if let Some(id) = artist.ids().get(&self.provider_id) {
+            return Ok(Some(id.clone()));
+        }
+
+        let name = normalize(artist.name());
+        let candidates = self
+            .catalog_search_repository
+            .search_artists(artist)
+            .await?;
+
+        Ok(candidates
+            .iter()
+            .find(|candidate| !name.is_empty() && normalize(candidate.name()) == name)
+            .and_then(|candidate| candidate.ids().get(&self.provider_id))
+            .cloned())
+    }
+}
+
+/// Id of the album for a provider
+pub fn provider_album_id<'t>(
+    album: &'t AlbumWithArtists,
+    provider_id: &ProviderId,
+) -> Option<&'t String> {
+    album.album().ids().iter().find_map(|id| match id {
+        ProductId::Provider((id_provider, id)) if id_provider == provider_id => Some(id),
+        _ => None,
+    })
+}
+
+/// Whether two albums are likely the same release, based on their title & artists
+pub fn is_same_album(album: &AlbumWithArtists, other: &AlbumWithArtists) -> bool {
+    let title = normalize(album.album().name());
+
+    if title.is_empty() || title != normalize(other.album().name()) {
+        return false;
+    }
+
+    let artists = album
+        .artists()
+        .iter()
+        .map(|artist| normalize(artist.name()))
+        .collect::<Vec<_>>();
+
+    artists.is_empty()
+        || other.artists().is_empty()
+        || other
+            .artists()
+            .iter()
+            .any(|artist| artists.contains(&normalize(artist.name())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use chrono::DateTime;
+
+    use crate::entities::{
+        album::{Album, AlbumWithArtists},
+        artist::Artist,
+    };
+
+    use super::is_same_album;
+
+    fn album(name: &str, artist: &str) -> AlbumWithArtists {
+        AlbumWithArtists::new(
+            Album::new(
+                HashSet::new(),
+                name.to_string(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                artist.to_string(),
+                HashMap::new(),
+            )],
+        )
+    }
+
+    #[test]
+    fn test_same_album() {
+        let original = album("Get Up (Deluxe)", "NewJeans");
+
+        assert!(is_same_album(&original, &album("Get Up", "NEWJEANS")));
+        assert!(!is_same_album(&original, &album("Get Up", "Someone else")));
+        assert!(!is_same_album(&original, &album("OMG", "NewJeans")));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    contracts::repositories::track_metadata_repository::{
+        TrackMetadataRepository, TrackMetadataRepositoryResult,
+    },
+    entities::track::TrackWithAlbumAndArtists,
+    value_objects::product_id::ProductId,
+};
+
+use super::track_matcher::is_same_recording;
+
+/// Complete the ids of tracks (ISRC, release UPC/EAN) from a metadata database,
+/// so that they can be matched by ISRC rather than by search
+pub struct MetadataEnricher<'a, M: TrackMetadataRepository> {
+    track_metadata_repository: &'a M,
+}
+
+impl<'a, M: TrackMetadataRepository> MetadataEnricher<'a, M> {
+    pub fn new(track_metadata_repository: &'a M) -> Self {
+        Self {
+            track_metadata_repository,
+        }
+    }
+
+    /// Add the ids of the recording to the track, tracks already having an ISRC are skipped
+    ///
+    /// Returns whether the track got new ids
+    pub async fn enrich(
+        &self,
+        track: &mut TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<bool> {
+        let Some(recording) = self.recording(track).await? else {
+            return Ok(false);
+        };
+
+        let known_ids = track.ids.len() + track.album.ids().len();
+
+        track.album.extend_ids(
+            recording
+                .album()
+                .ids()
+                .iter()
+                .filter(|id| matches!(id, ProductId::UPC(_) | ProductId::EAN(_)))
+                .cloned(),
+        );
+        track.ids.extend(recording.ids);
+
+        Ok(track.ids.len() + track.album.ids().len() > known_ids)
+    }
+
+    /// Recording of the track holding the ids of the track too, for matching it by ISRC
+    /// without changing it
+    ///
+    /// Returns `None` for tracks already having an ISRC, or when no ISRC is known for the recording
+    pub async fn enriched(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        let Some(mut recording) = self.recording(track).await? else {
+            return Ok(None);
+        };
+
+        if !has_isrc(&recording) {
+            return Ok(None);
+        }
+
+        recording.ids.extend(track.ids().iter().cloned());
+
+        Ok(Some(recording))
+    }
+
+    /// Recording of a track without ISRC, if found & the same as the track
+    async fn recording(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+        if has_isrc(track) {
+            return Ok(None);
+        }
+
+        Ok(self
+            .track_metadata_repository
+            .find_recording(track)
+            .await?
+            .filter(|recording| is_same_recording(track, recording)))
+    }
+
+    /// Enrich a list of tracks, one after the other
+    ///
+    /// Returns the number of tracks which got new ids
+    pub async fn enrich_all(
+        &self,
+        tracks: &mut [TrackWithAlbumAndArtists],
+    ) -> TrackMetadataRepositoryResult<usize> {
+        let mut enriched = 0;
+
+        for track in tracks.iter_mut() {
+            if self.enrich(track).await? {
+                enriched += 1;
+            }
+        }
+
+        Ok(enriched)
+    }
+}
+
+fn has_isrc(track: &TrackWithAlbumAndArtists) -> bool {
+    track
+        .ids()
+        .iter()
+        .any(|id| matches!(id, ProductId::ISRC(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use chrono::DateTime;
+
+    use crate::{
+        contracts::repositories::track_metadata_repository::{
+            TrackMetadataRepository, TrackMetadataRepositoryResult,
+        },
+        entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+        value_objects::{
+            barcode::Upc, isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::MetadataEnricher;
+
+    fn track(
+        name: &str,
+        ids: HashSet<ProductId>,
+        album_ids: HashSet<ProductId>,
+    ) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            ids,
+            name.to_string(),
+            219_000,
+            HashMap::new(),
+            Album::new(
+                album_ids,
+                "How Sweet".to_string(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                "NewJeans".to_string(),
+                HashMap::new(),
+            )],
+        )
+    }
+
+    struct StubMetadataRepository;
+
+    impl TrackMetadataRepository for StubMetadataRepository {
+        async fn find_recording(
+            &self,
+            track: &TrackWithAlbumAndArtists,
+        ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            Ok(Some(self::track(
+                track.name(),
+                HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())]),
+                HashSet::from_iter([ProductId::UPC(Upc::parse("196922995354").unwrap())]),
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enrich() {
+        let enricher = MetadataEnricher::new(&StubMetadataRepository);
+        let mut tracks = vec![
+            track("How Sweet", HashSet::new(), HashSet::new()),
+            track(
+                "Bubble Gum",
+                HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2414844").unwrap())]),
+                HashSet::new(),
+            ),
+        ];
+
+        assert_eq!(enricher.enrich_all(&mut tracks).await.expect("enriched"), 1);
+        assert!(tracks[0]
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert!(tracks[0]
+            .album()
+            .ids()
+            .contains(&ProductId::UPC(Upc::parse("196922995354").unwrap())));
+        assert!(tracks[1].album().ids().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_enriched() {
+        let enricher = MetadataEnricher::new(&StubMetadataRepository);
+        let id = ProductId::Provider((
+            ProviderId::new("deezer".to_string()),
+            "2801558052".to_string(),
+        ));
+        let source = track(
+            "How Sweet",
+            HashSet::from_iter([id.clone()]),
+            HashSet::new(),
+        );
+
+        let recording = enricher
+            .enriched(&source)
+            .await
+            .expect("enriched")
+            .expect("recording");
+
+        assert!(recording.ids().contains(&id));
+        assert!(recording
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())));
+        assert_eq!(source.ids().len(), 1);
+
+        let with_isrc = track(
+            "Bubble Gum",
+            HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2414844").unwrap())]),
+            HashSet::new(),
+        );
+
+        assert!(enricher
+            .enriched(&with_isrc)
+            .await
+            .expect("enriched")
+            .is_none());
+    }
+}
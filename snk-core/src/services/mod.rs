@@ -0,0 +1,7 @@
+pub mod deduplicator;
+pub mod identity_graph;
+pub mod library_matcher;
+pub mod metadata_enricher;
+pub mod provider_registry;
+pub mod smart_playlist_rule;
+pub mod track_matcher;
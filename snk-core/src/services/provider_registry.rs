@@ -0,0 +1,95 @@
+use std::{collections::HashMap, sync::Arc};
+
+use thiserror::Error;
+
+use crate::{
+    contracts::dynamic::{
+        DynAccountProfileRepository, DynPlaylistRepository, DynTrackSearchRepository,
+    },
+    entities::{
+        music_account_provider::MusicAccountProvider, provider_credentials::ProviderCredentials,
+    },
+    value_objects::provider::provider_id::ProviderId,
+};
+
+#[derive(Debug, Error)]
+pub enum ProviderRegistryError {
+    #[error("UnknownProvider: {0}")]
+    UnknownProvider(String),
+    #[error("MissingCredentials: {0}")]
+    MissingCredentials(String),
+    #[error("ServiceError: {0}")]
+    ServiceError(String),
+}
+
+pub type ProviderRegistryResult<T> = Result<T, ProviderRegistryError>;
+
+/// Repositories of a provider account, usually backed by a single shared repository
+pub struct ProviderRepositories<'a> {
+    pub playlists: Arc<dyn DynPlaylistRepository + 'a>,
+    /// Catalog search, for providers tracks can be transferred to
+    pub track_search: Option<Arc<dyn DynTrackSearchRepository + 'a>>,
+    /// Profile of the account, for providers sharing it (country used as market)
+    pub profile: Option<Arc<dyn DynAccountProfileRepository + 'a>>,
+}
+
+/// Build the repositories of a provider from the credentials of an account
+pub type ProviderRepositoriesFactory =
+    for<'a> fn(
+        &'a MusicAccountProvider,
+        &ProviderCredentials,
+    ) -> ProviderRegistryResult<ProviderRepositories<'a>>;
+
+/// Providers available at runtime, by id
+#[derive(Default)]
+pub struct ProviderRegistry {
+    factories: HashMap<ProviderId, ProviderRepositoriesFactory>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider, replacing any factory previously registered for it
+    pub fn register(
+        &mut self,
+        provider_id: ProviderId,
+        factory: ProviderRepositoriesFactory,
+    ) -> &mut Self {
+        self.factories.insert(provider_id, factory);
+        self
+    }
+
+    pub fn is_registered(&self, provider_id: &ProviderId) -> bool {
+        self.factories.contains_key(provider_id)
+    }
+
+    pub fn provider_ids(&self) -> impl Iterator<Item = &ProviderId> {
+        self.factories.keys()
+    }
+
+    /// Repositories of the account the credentials belong to
+    pub fn repositories<'a>(
+        &self,
+        provider: &'a MusicAccountProvider,
+        credentials: &ProviderCredentials,
+    ) -> ProviderRegistryResult<ProviderRepositories<'a>> {
+        let factory =
+            self.factories
+                .get(provider.id())
+                .ok_or(ProviderRegistryError::UnknownProvider(
+                    provider.id().value(),
+                ))?;
+
+        if credentials.provider_id() != provider.id() {
+            return Err(ProviderRegistryError::MissingCredentials(format!(
+                "credentials of {} given for {}",
+                credentials.provider_id().value(),
+                provider.id().value()
+            )));
+        }
+
+        factory(provider, credentials)
+    }
+}
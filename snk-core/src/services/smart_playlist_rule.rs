@@ -0,0 +1,680 @@
+//! Rules of the smart playlists.
+//!
+//! A rule names the playlists to read tracks from, then the conditions the tracks must meet:
+//!
+//! ```text
+//! from spotify:liked, deezer:liked where year between 1990 and 1999 and duration < 4m
+//! from deezer:1234567 where (artist = "NewJeans" or artist ~ "jeans") and not explicit
+//! from spotify:liked, deezer:liked where provider in (deezer) and year >= 2020
+//! ```
+//!
+//! Sources are `<provider>:liked` for the liked songs, else `<provider>:<playlist id>`.
+//! Conditions are combined with `and`, `or`, `not` & brackets, and compare:
+//! - `artist`: `=` any artist named so, `~` any artist whose name contains the text (case insensitive)
+//! - `year`: release year of the album, with `<`, `<=`, `>`, `>=`, `=` or `between <min> and <max>`
+//! - `duration`: same comparisons, in seconds (`240`), minutes & seconds (`4m`, `3m30s`, `3:30`)
+//! - `explicit`: tracks known to be explicit
+//! - `provider`: provider the track was read from, `= <provider>` or `in (<provider>, ...)`
+//!
+//! Unknown values (release date, duration, explicitness) never meet a condition on them.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Utc};
+use thiserror::Error;
+
+use crate::{
+    entities::track::TrackWithAlbumAndArtists,
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+
+use super::track_matcher::normalize;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SmartPlaylistRuleError {
+    #[error("UnexpectedEnd: expected {0}")]
+    UnexpectedEnd(String),
+    #[error("UnexpectedToken: found {0}, expected {1}")]
+    UnexpectedToken(String, String),
+    #[error("InvalidValue: {0}")]
+    InvalidValue(String),
+    #[error("TooDeep: more than {0} nested conditions")]
+    TooDeep(usize),
+}
+
+pub type SmartPlaylistRuleResult<T> = Result<T, SmartPlaylistRuleError>;
+
+/// Maximum nesting of `not` & brackets, conditions being parsed & evaluated recursively
+static MAX_DEPTH: usize = 32;
+
+/// Playlist the tracks are read from
+#[derive(Debug, Clone)]
+pub struct RuleSource {
+    pub provider_id: ProviderId,
+    pub playlist_id: PlaylistId,
+}
+
+/// Inclusive bounds of a value, unbounded when `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl Bounds {
+    pub fn contains(&self, value: i64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    /// Normalized name of one of the artists
+    Artist(String),
+    /// Normalized text in the name of one of the artists
+    ArtistContains(String),
+    /// Release year of the album
+    Year(Bounds),
+    DurationMs(Bounds),
+    Explicit,
+    /// Provider the track was read from
+    Provider(Vec<ProviderId>),
+}
+
+impl Condition {
+    /// Whether the track, read from the provider, meets the condition
+    pub fn matches(&self, track: &TrackWithAlbumAndArtists, provider_id: &ProviderId) -> bool {
+        match self {
+            Condition::All(conditions) => conditions
+                .iter()
+                .all(|condition| condition.matches(track, provider_id)),
+            Condition::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.matches(track, provider_id)),
+            Condition::Not(condition) => !condition.matches(track, provider_id),
+            Condition::Artist(name) => track
+                .artists()
+                .iter()
+                .any(|artist| normalize(artist.name()) == *name),
+            Condition::ArtistContains(text) => track
+                .artists()
+                .iter()
+                .any(|artist| normalize(artist.name()).contains(text.as_str())),
+            Condition::Year(bounds) => {
+                let release_date = track.album().release_date();
+
+                // Unknown dates default to the epoch
+                *release_date != DateTime::<Utc>::default()
+                    && bounds.contains(release_date.year() as i64)
+            }
+            Condition::DurationMs(bounds) => {
+                track.duration_ms() > 0 && bounds.contains(track.duration_ms() as i64)
+            }
+            Condition::Explicit => track.explicit() == Some(true),
+            Condition::Provider(provider_ids) => provider_ids.contains(provider_id),
+        }
+    }
+}
+
+/// Parsed rule of a smart playlist
+#[derive(Debug, Clone)]
+pub struct SmartPlaylistRule {
+    sources: Vec<RuleSource>,
+    condition: Option<Condition>,
+}
+
+impl SmartPlaylistRule {
+    pub fn sources(&self) -> &Vec<RuleSource> {
+        &self.sources
+    }
+
+    /// `None` when every track of the sources is kept
+    pub fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    /// Whether the track, read from the provider, is part of the smart playlist
+    pub fn matches(&self, track: &TrackWithAlbumAndArtists, provider_id: &ProviderId) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.matches(track, provider_id))
+    }
+}
+
+impl FromStr for SmartPlaylistRule {
+    type Err = SmartPlaylistRuleError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(rule)?,
+            position: 0,
+            depth: 0,
+        };
+
+        parser.expect_keyword("from")?;
+
+        let mut sources = vec![parser.source()?];
+
+        while parser.next_if_symbol(",") {
+            sources.push(parser.source()?);
+        }
+
+        let condition = match parser.next_if_keyword("where") {
+            true => Some(parser.any()?),
+            false => None,
+        };
+
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(SmartPlaylistRuleError::UnexpectedToken(
+                token.to_string(),
+                "end of the rule".to_string(),
+            ));
+        }
+
+        Ok(Self { sources, condition })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+/// Longest symbols first
+static SYMBOLS: [&str; 9] = ["<=", ">=", "<", ">", "=", "~", "(", ")", ","];
+
+fn tokenize(rule: &str) -> SmartPlaylistRuleResult<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut rest = rule.trim_start();
+
+    while !rest.is_empty() {
+        if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else if let Some(text) = rest.strip_prefix('"') {
+            let end = text
+                .find('"')
+                .ok_or(SmartPlaylistRuleError::UnexpectedEnd("\"".to_string()))?;
+
+            tokens.push(Token::Text(text[..end].to_string()));
+            rest = &text[end + 1..];
+        } else {
+            let end = rest
+                .find(|character: char| {
+                    character.is_whitespace() || "\"<>=~(),".contains(character)
+                })
+                .unwrap_or(rest.len());
+
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// Nesting of the condition being parsed
+    depth: usize,
+}
+
+impl Parser {
+    fn next(&mut self, expected: &str) -> SmartPlaylistRuleResult<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(SmartPlaylistRuleError::UnexpectedEnd(expected.to_string()))?;
+
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.position), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+
+        if found {
+            self.position += 1;
+        }
+
+        found
+    }
+
+    fn next_if_symbol(&mut self, symbol: &'static str) -> bool {
+        let found = self.tokens.get(self.position) == Some(&Token::Symbol(symbol));
+
+        if found {
+            self.position += 1;
+        }
+
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> SmartPlaylistRuleResult<()> {
+        match self.next(keyword)? {
+            Token::Word(word) if word.eq_ignore_ascii_case(keyword) => Ok(()),
+            token => Err(unexpected(token, keyword)),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> SmartPlaylistRuleResult<()> {
+        match self.next(symbol)? {
+            Token::Symbol(found) if found == symbol => Ok(()),
+            token => Err(unexpected(token, symbol)),
+        }
+    }
+
+    fn word(&mut self, expected: &str) -> SmartPlaylistRuleResult<String> {
+        match self.next(expected)? {
+            Token::Word(word) => Ok(word),
+            token => Err(unexpected(token, expected)),
+        }
+    }
+
+    /// `<provider>:liked` or `<provider>:<playlist id>`
+    fn source(&mut self) -> SmartPlaylistRuleResult<RuleSource> {
+        let source = self.word("a source")?;
+        let (provider, playlist) = source
+            .split_once(':')
+            .filter(|(provider, playlist)| !provider.is_empty() && !playlist.is_empty())
+            .ok_or(SmartPlaylistRuleError::InvalidValue(format!(
+                "source {} is not <provider>:<playlist>",
+                source
+            )))?;
+
+        Ok(RuleSource {
+            provider_id: parse_provider_id(provider)?,
+            playlist_id: match playlist.eq_ignore_ascii_case("liked") {
+                true => PlaylistId::LikedSongs,
+                false => PlaylistId::Owned(playlist.to_string()),
+            },
+        })
+    }
+
+    fn any(&mut self) -> SmartPlaylistRuleResult<Condition> {
+        let mut conditions = vec![self.all()?];
+
+        while self.next_if_keyword("or") {
+            conditions.push(self.all()?);
+        }
+
+        Ok(match conditions.len() {
+            1 => conditions.remove(0),
+            _ => Condition::Any(conditions),
+        })
+    }
+
+    fn all(&mut self) -> SmartPlaylistRuleResult<Condition> {
+        let mut conditions = vec![self.not()?];
+
+        while self.next_if_keyword("and") {
+            conditions.push(self.not()?);
+        }
+
+        Ok(match conditions.len() {
+            1 => conditions.remove(0),
+            _ => Condition::All(conditions),
+        })
+    }
+
+    fn not(&mut self) -> SmartPlaylistRuleResult<Condition> {
+        match self.next_if_keyword("not") {
+            true => Ok(Condition::Not(Box::new(self.nested(Self::not)?))),
+            false => self.condition(),
+        }
+    }
+
+    /// Condition one level deeper, up to [`MAX_DEPTH`]
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> SmartPlaylistRuleResult<Condition>,
+    ) -> SmartPlaylistRuleResult<Condition> {
+        if self.depth >= MAX_DEPTH {
+            return Err(SmartPlaylistRuleError::TooDeep(MAX_DEPTH));
+        }
+
+        self.depth += 1;
+
+        let condition = parse(self);
+
+        self.depth -= 1;
+        condition
+    }
+
+    fn condition(&mut self) -> SmartPlaylistRuleResult<Condition> {
+        if self.next_if_symbol("(") {
+            let condition = self.nested(Self::any)?;
+
+            self.expect_symbol(")")?;
+
+            return Ok(condition);
+        }
+
+        let field = self.word("a condition")?;
+
+        match field.to_lowercase().as_str() {
+            "artist" => {
+                let contains = match self.next("= or ~")? {
+                    Token::Symbol("=") => false,
+                    Token::Symbol("~") => true,
+                    token => return Err(unexpected(token, "= or ~")),
+                };
+                let name = match self.next("an artist")? {
+                    Token::Word(name) | Token::Text(name) => normalize(&name),
+                    token => return Err(unexpected(token, "an artist")),
+                };
+
+                Ok(match contains {
+                    true => Condition::ArtistContains(name),
+                    false => Condition::Artist(name),
+                })
+            }
+            "year" => Ok(Condition::Year(self.bounds(parse_year)?)),
+            "duration" => Ok(Condition::DurationMs(self.bounds(parse_duration_ms)?)),
+            "explicit" => Ok(Condition::Explicit),
+            "provider" => {
+                let mut provider_ids = vec![];
+
+                if self.next_if_keyword("in") {
+                    self.expect_symbol("(")?;
+
+                    loop {
+                        provider_ids.push(parse_provider_id(&self.word("a provider")?)?);
+
+                        if !self.next_if_symbol(",") {
+                            break;
+                        }
+                    }
+
+                    self.expect_symbol(")")?;
+                } else {
+                    self.expect_symbol("=")?;
+                    provider_ids.push(parse_provider_id(&self.word("a provider")?)?);
+                }
+
+                Ok(Condition::Provider(provider_ids))
+            }
+            _ => Err(SmartPlaylistRuleError::UnexpectedToken(
+                field,
+                "artist, year, duration, explicit or provider".to_string(),
+            )),
+        }
+    }
+
+    /// `between <min> and <max>` or a comparison with a value
+    fn bounds(&mut self, parse: fn(&str) -> Option<i64>) -> SmartPlaylistRuleResult<Bounds> {
+        let value = |parser: &mut Parser| {
+            let word = parser.word("a value")?;
+
+            parse(&word).ok_or(SmartPlaylistRuleError::InvalidValue(word))
+        };
+
+        if self.next_if_keyword("between") {
+            let min = value(self)?;
+
+            self.expect_keyword("and")?;
+
+            let max = value(self)?;
+
+            return Ok(Bounds {
+                min: Some(min),
+                max: Some(max),
+            });
+        }
+
+        let operator = match self.next("a comparison")? {
+            Token::Symbol(operator) => operator,
+            token => return Err(unexpected(token, "a comparison")),
+        };
+        let value = value(self)?;
+        let overflow = || SmartPlaylistRuleError::InvalidValue(format!("{} {}", operator, value));
+
+        Ok(match operator {
+            "<" => Bounds {
+                min: None,
+                max: Some(value.checked_sub(1).ok_or_else(overflow)?),
+            },
+            "<=" => Bounds {
+                min: None,
+                max: Some(value),
+            },
+            ">" => Bounds {
+                min: Some(value.checked_add(1).ok_or_else(overflow)?),
+                max: None,
+            },
+            ">=" => Bounds {
+                min: Some(value),
+                max: None,
+            },
+            "=" => Bounds {
+                min: Some(value),
+                max: Some(value),
+            },
+            _ => return Err(unexpected(Token::Symbol(operator), "a comparison")),
+        })
+    }
+}
+
+fn unexpected(token: Token, expected: &str) -> SmartPlaylistRuleError {
+    SmartPlaylistRuleError::UnexpectedToken(token.to_string(), expected.to_string())
+}
+
+fn parse_provider_id(value: &str) -> SmartPlaylistRuleResult<ProviderId> {
+    ProviderId::parse(value).map_err(|err| SmartPlaylistRuleError::InvalidValue(err.to_string()))
+}
+
+fn parse_year(value: &str) -> Option<i64> {
+    value.parse().ok()
+}
+
+/// Seconds (`240`, `240s`), minutes & seconds (`4m`, `3m30s`, `3:30`)
+fn parse_duration_ms(value: &str) -> Option<i64> {
+    let value = value.to_lowercase();
+    let seconds = if let Some((minutes, seconds)) = value.split_once(':') {
+        minutes
+            .parse::<i64>()
+            .ok()?
+            .checked_mul(60)?
+            .checked_add(seconds.parse::<i64>().ok()?)?
+    } else if let Some((minutes, seconds)) = value.split_once('m') {
+        let seconds = match seconds.strip_suffix('s') {
+            Some(seconds) => seconds.parse::<i64>().ok()?,
+            None if seconds.is_empty() => 0,
+            None => return None,
+        };
+
+        minutes
+            .parse::<i64>()
+            .ok()?
+            .checked_mul(60)?
+            .checked_add(seconds)?
+    } else {
+        value.strip_suffix('s').unwrap_or(&value).parse().ok()?
+    };
+
+    seconds.checked_mul(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use chrono::NaiveDate;
+
+    use crate::{
+        entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+        value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+    };
+
+    use super::{Bounds, Condition, SmartPlaylistRule, SmartPlaylistRuleError};
+
+    fn track(
+        artist: &str,
+        year: i32,
+        duration_ms: u32,
+        explicit: bool,
+    ) -> TrackWithAlbumAndArtists {
+        let mut track = TrackWithAlbumAndArtists::new(
+            HashSet::new(),
+            "Track".to_string(),
+            duration_ms,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                NaiveDate::from_ymd_opt(year, 6, 1)
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .unwrap()
+                    .and_utc(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                artist.to_string(),
+                HashMap::new(),
+            )],
+        );
+
+        track.explicit = Some(explicit);
+        track
+    }
+
+    #[test]
+    fn test_parse() {
+        let rule =
+            "FROM spotify:liked, deezer:123 where year between 1990 and 1999 and duration < 4m"
+                .parse::<SmartPlaylistRule>()
+                .expect("parsed");
+
+        assert_eq!(rule.sources().len(), 2);
+        assert!(matches!(
+            rule.sources()[0].playlist_id,
+            PlaylistId::LikedSongs
+        ));
+        assert_eq!(rule.sources()[1].provider_id.as_str(), "deezer");
+        assert_eq!(
+            rule.condition(),
+            Some(&Condition::All(vec![
+                Condition::Year(Bounds {
+                    min: Some(1990),
+                    max: Some(1999)
+                }),
+                Condition::DurationMs(Bounds {
+                    min: None,
+                    max: Some(239_999)
+                }),
+            ]))
+        );
+
+        let rule =
+            "from deezer:liked where not (artist = \"New Jeans\" or provider in (spotify, tidal))"
+                .parse::<SmartPlaylistRule>()
+                .expect("parsed");
+
+        assert_eq!(
+            rule.condition(),
+            Some(&Condition::Not(Box::new(Condition::Any(vec![
+                Condition::Artist("new jeans".to_string()),
+                Condition::Provider(vec![ProviderId::SPOTIFY, ProviderId::TIDAL]),
+            ]))))
+        );
+
+        assert_eq!(
+            "from spotify:liked where year"
+                .parse::<SmartPlaylistRule>()
+                .err(),
+            Some(SmartPlaylistRuleError::UnexpectedEnd(
+                "a comparison".to_string()
+            ))
+        );
+        assert_eq!(
+            "from spotify:liked where duration > 3h"
+                .parse::<SmartPlaylistRule>()
+                .err(),
+            Some(SmartPlaylistRuleError::InvalidValue("3h".to_string()))
+        );
+        assert!("spotify:liked".parse::<SmartPlaylistRule>().is_err());
+
+        for overflowing in [
+            "year < -9223372036854775808",
+            "year > 9223372036854775807",
+            "duration < 999999999999999999",
+            "duration < 99999999999999999m",
+            "duration < 99999999999999999:00",
+        ] {
+            assert!(matches!(
+                format!("from spotify:liked where {}", overflowing).parse::<SmartPlaylistRule>(),
+                Err(SmartPlaylistRuleError::InvalidValue(_))
+            ));
+        }
+
+        assert_eq!(
+            format!(
+                "from spotify:liked where {}explicit",
+                "not ".repeat(200_000)
+            )
+            .parse::<SmartPlaylistRule>()
+            .err(),
+            Some(SmartPlaylistRuleError::TooDeep(32))
+        );
+        assert_eq!(
+            format!(
+                "from spotify:liked where {}explicit{}",
+                "(".repeat(100),
+                ")".repeat(100)
+            )
+            .parse::<SmartPlaylistRule>()
+            .err(),
+            Some(SmartPlaylistRuleError::TooDeep(32))
+        );
+        assert!("from spotify where explicit"
+            .parse::<SmartPlaylistRule>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let rule = "from spotify:liked where year between 1990 and 1999 and duration <= 3:30 and not explicit and artist ~ aaliyah"
+            .parse::<SmartPlaylistRule>()
+            .expect("parsed");
+        let spotify = ProviderId::SPOTIFY;
+
+        assert!(rule.matches(&track("Aaliyah", 1994, 210_000, false), &spotify));
+        assert!(!rule.matches(&track("Aaliyah", 2001, 210_000, false), &spotify));
+        assert!(!rule.matches(&track("Aaliyah", 1994, 211_000, false), &spotify));
+        assert!(!rule.matches(&track("Aaliyah", 1994, 210_000, true), &spotify));
+        assert!(!rule.matches(&track("Brandy", 1994, 210_000, false), &spotify));
+
+        let rule = "from spotify:liked, deezer:liked where provider = deezer"
+            .parse::<SmartPlaylistRule>()
+            .expect("parsed");
+
+        assert!(!rule.matches(&track("Aaliyah", 1994, 210_000, false), &spotify));
+        assert!(rule.matches(&track("Aaliyah", 1994, 210_000, false), &ProviderId::DEEZER));
+    }
+}
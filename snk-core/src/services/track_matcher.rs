@@ -0,0 +1,820 @@
+use std::collections::HashSet;
+
+use crate::{
+    contracts::repositories::{
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        match_cache_repository::{CachedMatch, MatchCacheRepository, NoMatchCache},
+        track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+    },
+    entities::{identity::RecordingKind, track::TrackWithAlbumAndArtists},
+    value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+};
+
+use super::identity_graph::IdentityGraph;
+
+/// Maximum gap between the durations of two versions of the same recording
+static DURATION_TOLERANCE_MS: u32 = 10_000;
+
+/// Track of the catalog of a provider equivalent to another track
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackMatch {
+    pub id: String,
+    /// Whether the account can play the track in its market, `None` if unknown (ex: no market)
+    pub playable: Option<bool>,
+}
+
+/// Find the equivalent of tracks in the catalog of a provider
+///
+/// Tracks are matched by ISRC first, then by searching title & artists,
+/// preferring the version with the same explicitness (explicit or clean) when known.
+/// Versions playable in the market of the account are preferred over any other,
+/// an unplayable match being kept only when no playable alternative exists.
+/// Outcomes are kept in the match cache, keyed by each id of the track & the market,
+/// and reused before any lookup.
+/// The ids of the recording in the identity graph are used along with the ids of the track,
+/// and the matches found are recorded in it.
+pub struct TrackMatcher<
+    'a,
+    S: TrackSearchRepository,
+    C: MatchCacheRepository = NoMatchCache,
+    I: IdentityRepository<RecordingKind> = NoIdentityRepository,
+> {
+    provider_id: ProviderId,
+    track_search_repository: &'a S,
+    match_cache: &'a C,
+    identity_graph: IdentityGraph<'a, RecordingKind, I>,
+    market: Option<String>,
+}
+
+impl<'a, S: TrackSearchRepository> TrackMatcher<'a, S> {
+    pub fn new(provider_id: ProviderId, track_search_repository: &'a S) -> Self {
+        Self {
+            provider_id,
+            track_search_repository,
+            match_cache: &NoMatchCache,
+            identity_graph: IdentityGraph::new(&NoIdentityRepository),
+            market: None,
+        }
+    }
+}
+
+impl<
+        'a,
+        S: TrackSearchRepository,
+        C: MatchCacheRepository,
+        I: IdentityRepository<RecordingKind>,
+    > TrackMatcher<'a, S, C, I>
+{
+    pub fn with_match_cache<M: MatchCacheRepository>(
+        self,
+        match_cache: &'a M,
+    ) -> TrackMatcher<'a, S, M, I> {
+        TrackMatcher {
+            provider_id: self.provider_id,
+            track_search_repository: self.track_search_repository,
+            match_cache,
+            identity_graph: self.identity_graph,
+            market: self.market,
+        }
+    }
+
+    /// Recordings shared with the other services, completing the ids of the tracks matched
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> TrackMatcher<'a, S, C, J> {
+        TrackMatcher {
+            provider_id: self.provider_id,
+            track_search_repository: self.track_search_repository,
+            match_cache: self.match_cache,
+            identity_graph,
+            market: self.market,
+        }
+    }
+
+    /// ISO 3166-1 alpha-2 country of the account, to tell the playable versions apart
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.market = market;
+        self
+    }
+
+    pub fn provider_id(&self) -> &ProviderId {
+        &self.provider_id
+    }
+
+    /// Id of the track in the catalog of the provider, if any
+    ///
+    /// The cache & identity graph are best effort, their failures are ignored.
+    pub async fn find_match(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Option<String>> {
+        Ok(self
+            .find_playable_match(track)
+            .await?
+            .map(|track_match| track_match.id))
+    }
+
+    /// Track of the catalog of the provider, if any, with whether the account can play it
+    ///
+    /// The cache & identity graph are best effort, their failures are ignored.
+    pub async fn find_playable_match(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+    ) -> TrackSearchRepositoryResult<Option<TrackMatch>> {
+        // Track already coming from the provider
+        if let Some(id) = provider_track_id(track, &self.provider_id) {
+            return Ok(Some(TrackMatch {
+                id: id.clone(),
+                playable: track.is_playable_in(self.market.as_deref()),
+            }));
+        }
+
+        let ids = self.resolve(track).await;
+
+        // Id of the provider known by the graph, its availability in a market being unknown
+        if self.market.is_none() {
+            if let Some(id) = provider_id_among(&ids, &self.provider_id) {
+                return Ok(Some(TrackMatch {
+                    id: id.clone(),
+                    playable: None,
+                }));
+            }
+        }
+
+        let market = self.market.as_deref();
+        let mut not_found = false;
+
+        // A track found under any of its ids is found, whatever the order of the ids
+        for id in &ids {
+            match self.match_cache.get(id, &self.provider_id, market).await {
+                Ok(Some(CachedMatch::Found { id, playable })) => {
+                    return Ok(Some(TrackMatch { id, playable }))
+                }
+                Ok(Some(CachedMatch::NotFound)) => not_found = true,
+                Ok(None) | Err(_) => {}
+            }
+        }
+
+        if not_found {
+            return Ok(None);
+        }
+
+        let found = self.lookup(track, &ids).await?;
+        let cached_match = match &found {
+            Some(track_match) => {
+                let _ = self
+                    .identity_graph
+                    .observe(
+                        track.name(),
+                        ids.iter().cloned().chain([ProductId::Provider((
+                            self.provider_id.clone(),
+                            track_match.id.clone(),
+                        ))]),
+                    )
+                    .await;
+
+                CachedMatch::Found {
+                    id: track_match.id.clone(),
+                    playable: track_match.playable,
+                }
+            }
+            None => CachedMatch::NotFound,
+        };
+
+        for id in &ids {
+            let _ = self
+                .match_cache
+                .set(id, &self.provider_id, market, &cached_match)
+                .await;
+        }
+
+        Ok(found)
+    }
+
+    /// Ids of the track along with the ids of its recording in the identity graph, if any
+    async fn resolve(&self, track: &TrackWithAlbumAndArtists) -> HashSet<ProductId> {
+        let mut ids = track.ids().clone();
+
+        if let Ok(Some(recording)) = self.identity_graph.find(track.ids().iter().cloned()).await {
+            ids.extend(recording.ids().iter().cloned());
+        }
+
+        ids
+    }
+
+    /// Look the track up in the catalog, by ISRC (among the given ids) then by search
+    ///
+    /// An ISRC match unplayable in the market of the account is only kept
+    /// when the search finds no playable version of the recording.
+    async fn lookup(
+        &self,
+        track: &TrackWithAlbumAndArtists,
+        ids: &HashSet<ProductId>,
+    ) -> TrackSearchRepositoryResult<Option<TrackMatch>> {
+        let mut unplayable = None;
+
+        for id in ids {
+            let ProductId::ISRC(isrc) = id else {
+                continue;
+            };
+
+            let Some(found) = self.track_search_repository.find_by_isrc(isrc).await? else {
+                continue;
+            };
+
+            if let Some(track_match) = self.track_match(&found) {
+                if track_match.playable != Some(false) {
+                    return Ok(Some(track_match));
+                }
+
+                unplayable.get_or_insert(track_match);
+            }
+        }
+
+        let candidates = self.track_search_repository.search(track).await?;
+
+        // Playable versions first, then the same version (explicit or clean), in the order of the results
+        let best = candidates
+            .iter()
+            .filter(|candidate| is_same_recording(track, candidate))
+            .filter_map(|candidate| {
+                let same_version =
+                    track.explicit().is_some() && candidate.explicit() == track.explicit();
+
+                self.track_match(candidate)
+                    .map(|track_match| (track_match, same_version))
+            })
+            .enumerate()
+            .min_by_key(|(position, (track_match, same_version))| {
+                (
+                    track_match.playable == Some(false),
+                    !same_version,
+                    *position,
+                )
+            })
+            .map(|(_, (track_match, _))| track_match);
+
+        Ok(match best {
+            Some(track_match) if track_match.playable != Some(false) => Some(track_match),
+            best => unplayable.or(best),
+        })
+    }
+
+    /// Match of a track of the catalog of the provider
+    fn track_match(&self, found: &TrackWithAlbumAndArtists) -> Option<TrackMatch> {
+        provider_track_id(found, &self.provider_id).map(|id| TrackMatch {
+            id: id.clone(),
+            playable: found.is_playable_in(self.market.as_deref()),
+        })
+    }
+}
+
+/// Id of the track for a provider
+pub fn provider_track_id<'t>(
+    track: &'t TrackWithAlbumAndArtists,
+    provider_id: &ProviderId,
+) -> Option<&'t String> {
+    provider_id_among(track.ids(), provider_id)
+}
+
+/// Id for a provider among ids
+fn provider_id_among<'i>(
+    ids: &'i HashSet<ProductId>,
+    provider_id: &ProviderId,
+) -> Option<&'i String> {
+    ids.iter().find_map(|id| match id {
+        ProductId::Provider((id_provider, id)) if id_provider == provider_id => Some(id),
+        _ => None,
+    })
+}
+
+/// Whether two tracks are likely the same recording, based on their metadata
+pub fn is_same_recording(
+    track: &TrackWithAlbumAndArtists,
+    other: &TrackWithAlbumAndArtists,
+) -> bool {
+    let title = normalize(track.name());
+    let other_title = normalize(other.name());
+
+    if title.is_empty() || (title != other_title && !other_title.starts_with(&title)) {
+        return false;
+    }
+
+    let artists = track
+        .artists()
+        .iter()
+        .map(|artist| normalize(artist.name()))
+        .collect::<Vec<_>>();
+    let share_artist = other
+        .artists()
+        .iter()
+        .any(|artist| artists.contains(&normalize(artist.name())));
+
+    if !artists.is_empty() && !other.artists().is_empty() && !share_artist {
+        return false;
+    }
+
+    // Unknown durations are 0
+    track.duration_ms() == 0
+        || other.duration_ms() == 0
+        || track.duration_ms().abs_diff(other.duration_ms()) <= DURATION_TOLERANCE_MS
+}
+
+/// Lowercase alphanumeric words, without the details in brackets (feat., remaster...)
+pub fn normalize(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    let mut depth = 0;
+
+    for character in value.chars() {
+        match character {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = (depth - 1).max(0),
+            _ if depth > 0 => {}
+            _ if character.is_alphanumeric() => normalized.extend(character.to_lowercase()),
+            _ => normalized.push(' '),
+        }
+    }
+
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::{
+            identity_repository::{IdentityRepository, IdentityRepositoryResult},
+            match_cache_repository::{
+                CachedMatch, MatchCacheRepository, MatchCacheRepositoryResult,
+            },
+            track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+        },
+        entities::{
+            album::Album,
+            artist::Artist,
+            identity::{Identity, RecordingKind},
+            track::TrackWithAlbumAndArtists,
+        },
+        services::identity_graph::IdentityGraph,
+        value_objects::{isrc::Isrc, product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
+    use super::{is_same_recording, normalize, TrackMatch, TrackMatcher};
+
+    fn track(name: &str, artist: &str, duration_ms: u32) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            HashSet::new(),
+            name.to_string(),
+            duration_ms,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                artist.to_string(),
+                HashMap::new(),
+            )],
+        )
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("Nights Like This (feat. Ty Dolla $ign)"),
+            "nights like this"
+        );
+        assert_eq!(normalize("  HOW-Sweet [Remastered] "), "how sweet");
+    }
+
+    #[test]
+    fn test_same_recording() {
+        let original = track("Nights Like This (feat. Ty Dolla $ign)", "Kehlani", 201_000);
+
+        assert!(is_same_recording(
+            &original,
+            &track("Nights Like This", "KEHLANI", 203_000)
+        ));
+        assert!(!is_same_recording(
+            &original,
+            &track("Nights Like This", "Kehlani", 260_000)
+        ));
+        assert!(!is_same_recording(
+            &original,
+            &track("Nights Like This", "Someone else", 201_000)
+        ));
+    }
+
+    /// Catalog knowing a single ISRC, counting the lookups
+    #[derive(Default)]
+    struct StubSearchRepository {
+        lookups: Cell<usize>,
+        /// Id & explicitness of the search results, all named after the searched track
+        candidates: Vec<(&'static str, Option<bool>)>,
+        /// Ids of the tracks unplayable by the account
+        unplayable: Vec<&'static str>,
+    }
+
+    impl TrackSearchRepository for StubSearchRepository {
+        async fn find_by_isrc(
+            &self,
+            isrc: &str,
+        ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            self.lookups.set(self.lookups.get() + 1);
+
+            let mut found = track("How Sweet", "NewJeans", 219_000);
+
+            found.ids.insert(ProductId::Provider((
+                ProviderId::new("stub".to_string()),
+                "1".to_string(),
+            )));
+            found.playable = Some(!self.unplayable.contains(&"1"));
+
+            Ok((isrc == "USA2P2414843").then_some(found))
+        }
+
+        async fn search(
+            &self,
+            track: &TrackWithAlbumAndArtists,
+        ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            self.lookups.set(self.lookups.get() + 1);
+
+            Ok(self
+                .candidates
+                .iter()
+                .map(|(id, explicit)| {
+                    let mut candidate = TrackWithAlbumAndArtists::new(
+                        HashSet::from_iter([ProductId::Provider((
+                            ProviderId::new("stub".to_string()),
+                            id.to_string(),
+                        ))]),
+                        track.name().clone(),
+                        track.duration_ms(),
+                        HashMap::new(),
+                        Album::new(
+                            HashSet::new(),
+                            String::new(),
+                            DateTime::default(),
+                            HashSet::new(),
+                            HashMap::new(),
+                        ),
+                        vec![],
+                    );
+
+                    candidate.explicit = *explicit;
+                    candidate.playable = Some(!self.unplayable.contains(id));
+                    candidate
+                })
+                .collect())
+        }
+    }
+
+    /// Product id, provider id & market
+    type CacheKey = (ProductId, ProviderId, Option<String>);
+
+    #[derive(Default)]
+    struct StubMatchCache {
+        entries: RefCell<HashMap<CacheKey, CachedMatch>>,
+    }
+
+    impl MatchCacheRepository for StubMatchCache {
+        async fn get(
+            &self,
+            product_id: &ProductId,
+            provider_id: &ProviderId,
+            market: Option<&str>,
+        ) -> MatchCacheRepositoryResult<Option<CachedMatch>> {
+            Ok(self
+                .entries
+                .borrow()
+                .get(&(
+                    product_id.clone(),
+                    provider_id.clone(),
+                    market.map(str::to_string),
+                ))
+                .cloned())
+        }
+
+        async fn set(
+            &self,
+            product_id: &ProductId,
+            provider_id: &ProviderId,
+            market: Option<&str>,
+            cached_match: &CachedMatch,
+        ) -> MatchCacheRepositoryResult<()> {
+            self.entries.borrow_mut().insert(
+                (
+                    product_id.clone(),
+                    provider_id.clone(),
+                    market.map(str::to_string),
+                ),
+                cached_match.clone(),
+            );
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_match_cached() {
+        let search_repository = StubSearchRepository::default();
+        let match_cache = StubMatchCache::default();
+        let matcher = TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository)
+            .with_match_cache(&match_cache);
+
+        let mut known = track("How Sweet", "NewJeans", 219_000);
+        let mut unknown = track("Unknown", "Nobody", 180_000);
+
+        known
+            .ids
+            .insert(ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap()));
+        unknown
+            .ids
+            .insert(ProductId::ISRC(Isrc::parse("XXX000000000").unwrap()));
+
+        for _ in 0..2 {
+            assert_eq!(
+                matcher.find_match(&known).await.expect("matched"),
+                Some("1".to_string())
+            );
+            assert_eq!(matcher.find_match(&unknown).await.expect("matched"), None);
+        }
+
+        // ISRC lookup of the known track, ISRC lookup & search of the unknown one
+        assert_eq!(search_repository.lookups.get(), 3);
+        assert!(
+            match_cache.entries.borrow()[&(
+                ProductId::ISRC(Isrc::parse("XXX000000000").unwrap()),
+                ProviderId::new("stub".to_string()),
+                None
+            )] == CachedMatch::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_match_cached_under_any_id() {
+        let search_repository = StubSearchRepository::default();
+        let match_cache = StubMatchCache::default();
+        let matcher = TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository)
+            .with_match_cache(&match_cache);
+
+        let isrc = ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap());
+        let other = ProductId::Provider((ProviderId::new("other".to_string()), "2".to_string()));
+        let mut track = track("How Sweet", "NewJeans", 219_000);
+
+        track.ids.extend([isrc.clone(), other.clone()]);
+
+        // Missing under one id, found under another one
+        for (id, cached_match) in [
+            (other, CachedMatch::NotFound),
+            (
+                isrc,
+                CachedMatch::Found {
+                    id: "1".to_string(),
+                    playable: None,
+                },
+            ),
+        ] {
+            match_cache
+                .set(
+                    &id,
+                    &ProviderId::new("stub".to_string()),
+                    None,
+                    &cached_match,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            matcher.find_match(&track).await.expect("matched"),
+            Some("1".to_string())
+        );
+        assert_eq!(search_repository.lookups.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_find_match_cached_per_market() {
+        let search_repository = StubSearchRepository {
+            unplayable: vec!["1"],
+            ..Default::default()
+        };
+        let match_cache = StubMatchCache::default();
+        let matcher = |market: &str| {
+            TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository)
+                .with_match_cache(&match_cache)
+                .with_market(Some(market.to_string()))
+        };
+
+        let mut track = track("How Sweet", "NewJeans", 219_000);
+
+        track
+            .ids
+            .insert(ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap()));
+
+        let unplayable = Some(TrackMatch {
+            id: "1".to_string(),
+            playable: Some(false),
+        });
+
+        for market in ["FR", "FR", "US"] {
+            assert_eq!(
+                matcher(market)
+                    .find_playable_match(&track)
+                    .await
+                    .expect("matched"),
+                unplayable
+            );
+        }
+
+        // ISRC lookup & search for each market, the availability coming from the cache for FR
+        assert_eq!(search_repository.lookups.get(), 4);
+    }
+
+    #[derive(Default)]
+    struct StubIdentityRepository {
+        identities: RefCell<HashMap<Uuid, Identity<RecordingKind>>>,
+    }
+
+    impl IdentityRepository<RecordingKind> for StubIdentityRepository {
+        async fn get(&self, id: Uuid) -> IdentityRepositoryResult<Option<Identity<RecordingKind>>> {
+            Ok(self.identities.borrow().get(&id).cloned())
+        }
+
+        async fn find_by_ids(
+            &self,
+            ids: &HashSet<ProductId>,
+        ) -> IdentityRepositoryResult<Vec<Identity<RecordingKind>>> {
+            Ok(self
+                .identities
+                .borrow()
+                .values()
+                .filter(|identity| !identity.ids().is_disjoint(ids))
+                .cloned()
+                .collect())
+        }
+
+        async fn save(&self, identity: &Identity<RecordingKind>) -> IdentityRepositoryResult<()> {
+            self.identities
+                .borrow_mut()
+                .insert(identity.id(), identity.clone());
+
+            Ok(())
+        }
+
+        async fn merge(
+            &self,
+            identity: &Identity<RecordingKind>,
+            merged: &[Uuid],
+        ) -> IdentityRepositoryResult<()> {
+            for id in merged {
+                self.delete(*id).await?;
+            }
+
+            self.save(identity).await
+        }
+
+        async fn split(
+            &self,
+            identity: &Identity<RecordingKind>,
+            split_identity: &Identity<RecordingKind>,
+        ) -> IdentityRepositoryResult<()> {
+            self.save(identity).await?;
+            self.save(split_identity).await
+        }
+
+        async fn delete(&self, id: Uuid) -> IdentityRepositoryResult<()> {
+            self.identities.borrow_mut().remove(&id);
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_match_identity_graph() {
+        let search_repository = StubSearchRepository::default();
+        let identity_repository = StubIdentityRepository::default();
+        let graph = IdentityGraph::new(&identity_repository);
+        let matcher = || {
+            TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository)
+                .with_identity_graph(IdentityGraph::new(&identity_repository))
+        };
+
+        let deezer = ProductId::Provider((ProviderId::new("deezer".to_string()), "2".to_string()));
+        let mut track = track("How Sweet", "NewJeans", 219_000);
+
+        track.ids.insert(deezer.clone());
+        graph
+            .observe(
+                "How Sweet",
+                [
+                    deezer.clone(),
+                    ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap()),
+                ],
+            )
+            .await
+            .expect("observed");
+
+        // Looked up by the ISRC of the recording, missing from the track
+        assert_eq!(
+            matcher().find_match(&track).await.expect("matched"),
+            Some("1".to_string())
+        );
+        assert_eq!(search_repository.lookups.get(), 1);
+
+        // The match recorded in the graph is reused
+        assert_eq!(
+            matcher().find_match(&track).await.expect("matched"),
+            Some("1".to_string())
+        );
+        assert_eq!(search_repository.lookups.get(), 1);
+        assert!(graph
+            .find([deezer])
+            .await
+            .expect("found")
+            .expect("recording")
+            .ids()
+            .contains(&ProductId::Provider((
+                ProviderId::new("stub".to_string()),
+                "1".to_string()
+            ))));
+    }
+
+    #[tokio::test]
+    async fn test_find_match_same_version() {
+        let search_repository = StubSearchRepository {
+            candidates: vec![("clean", Some(false)), ("explicit", Some(true))],
+            ..Default::default()
+        };
+        let matcher = TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository);
+
+        let mut explicit = track("Nights Like This", "Kehlani", 201_000);
+        let unknown = track("Nights Like This", "Kehlani", 201_000);
+
+        explicit.explicit = Some(true);
+
+        assert_eq!(
+            matcher.find_match(&explicit).await.expect("matched"),
+            Some("explicit".to_string())
+        );
+        assert_eq!(
+            matcher.find_match(&unknown).await.expect("matched"),
+            Some("clean".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_match_playable() {
+        let search_repository = StubSearchRepository {
+            candidates: vec![("unplayable", Some(true)), ("playable", Some(false))],
+            unplayable: vec!["1", "unplayable"],
+            ..Default::default()
+        };
+        let matcher = TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository)
+            .with_market(Some("FR".to_string()));
+
+        let mut track = track("How Sweet", "NewJeans", 219_000);
+
+        track.explicit = Some(true);
+        track
+            .ids
+            .insert(ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap()));
+
+        // The playable clean version is preferred over the unplayable ISRC match & explicit version
+        assert_eq!(
+            matcher.find_playable_match(&track).await.expect("matched"),
+            Some(TrackMatch {
+                id: "playable".to_string(),
+                playable: Some(true),
+            })
+        );
+
+        let search_repository = StubSearchRepository {
+            candidates: vec![("unplayable", Some(true))],
+            unplayable: vec!["1", "unplayable"],
+            ..Default::default()
+        };
+        let matcher = TrackMatcher::new(ProviderId::new("stub".to_string()), &search_repository);
+
+        // Without playable alternative, the ISRC match is kept
+        assert_eq!(
+            matcher.find_playable_match(&track).await.expect("matched"),
+            Some(TrackMatch {
+                id: "1".to_string(),
+                playable: Some(false),
+            })
+        );
+    }
+}
@@ -0,0 +1,568 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    contracts::repositories::{
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        match_cache_repository::{MatchCacheRepository, NoMatchCache},
+        playlist_repository::{PlaylistRepository, PlaylistRepositoryError},
+        smart_playlist_repository::{SmartPlaylistRepository, SmartPlaylistRepositoryError},
+        track_search_repository::TrackSearchRepository,
+    },
+    entities::{identity::RecordingKind, playlist::PlaylistSpec, smart_playlist::SmartPlaylist},
+    services::{
+        identity_graph::IdentityGraph,
+        smart_playlist_rule::{SmartPlaylistRule, SmartPlaylistRuleError},
+        track_matcher::provider_track_id,
+    },
+    value_objects::provider::provider_id::ProviderId,
+};
+
+use super::transfer_tracks::{TransferReport, TransferTracks, TransferTracksError};
+
+#[derive(Debug, Error)]
+pub enum EvaluateSmartPlaylistError {
+    #[error("SmartPlaylistRule: {0}")]
+    SmartPlaylistRuleError(#[from] SmartPlaylistRuleError),
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("SmartPlaylistRepository: {0}")]
+    SmartPlaylistRepositoryError(#[from] SmartPlaylistRepositoryError),
+    #[error("TransferTracks: {0}")]
+    TransferTracksError(#[from] TransferTracksError),
+    #[error("MissingLibrary: no playlists given for {0}")]
+    MissingLibrary(String),
+}
+
+pub type EvaluateSmartPlaylistResult<T> = Result<T, EvaluateSmartPlaylistError>;
+
+/// Playlists of the user on a provider, the sources of the rules naming it
+pub struct ProviderLibrary<'s, Q: PlaylistRepository> {
+    pub provider_id: ProviderId,
+    pub playlist_repository: &'s Q,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SmartPlaylistReport {
+    /// Tracks of the sources meeting the rule
+    pub matching_tracks: usize,
+    /// Tracks added to the playlist, or found already in it
+    pub transfer: TransferReport,
+    /// Ids of the tracks removed from the playlist, no longer meeting the rule
+    pub removed_ids: Vec<String>,
+}
+
+/// Outcome of the evaluation of a due smart playlist
+#[derive(Debug, Clone)]
+pub struct SmartPlaylistRefresh {
+    pub id: Uuid,
+    pub name: String,
+    pub report: SmartPlaylistReport,
+    /// Failure preventing the smart playlist from being evaluated
+    pub error: Option<String>,
+}
+
+/// Build or refresh the destination playlist of a smart playlist from its rule
+///
+/// The destination playlist belongs to the smart playlist: at each evaluation, matching tracks
+/// missing from it are added and the tracks no longer matching are removed.
+pub struct EvaluateSmartPlaylist<
+    'a,
+    P: PlaylistRepository,
+    S: TrackSearchRepository,
+    C: MatchCacheRepository = NoMatchCache,
+    I: IdentityRepository<RecordingKind> = NoIdentityRepository,
+> {
+    provider_id: ProviderId,
+    playlist_repository: &'a P,
+    transfer_tracks: TransferTracks<'a, P, S, C, I>,
+    dry_run: bool,
+}
+
+impl<'a, P: PlaylistRepository, S: TrackSearchRepository> EvaluateSmartPlaylist<'a, P, S> {
+    /// Arguments:
+    /// - provider_id: destination provider
+    /// - playlist_repository: playlists of the destination provider
+    /// - track_search_repository: catalog of the destination provider
+    pub fn new(
+        provider_id: ProviderId,
+        playlist_repository: &'a P,
+        track_search_repository: &'a S,
+    ) -> Self {
+        Self {
+            provider_id: provider_id.clone(),
+            playlist_repository,
+            transfer_tracks: TransferTracks::new(
+                provider_id,
+                playlist_repository,
+                track_search_repository,
+            ),
+            dry_run: false,
+        }
+    }
+}
+
+impl<
+        'a,
+        P: PlaylistRepository,
+        S: TrackSearchRepository,
+        C: MatchCacheRepository,
+        I: IdentityRepository<RecordingKind>,
+    > EvaluateSmartPlaylist<'a, P, S, C, I>
+{
+    pub fn with_match_cache<M: MatchCacheRepository>(
+        self,
+        match_cache: &'a M,
+    ) -> EvaluateSmartPlaylist<'a, P, S, M, I> {
+        EvaluateSmartPlaylist {
+            provider_id: self.provider_id,
+            playlist_repository: self.playlist_repository,
+            transfer_tracks: self.transfer_tracks.with_match_cache(match_cache),
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Match the tracks of the sources with the ids of their recordings, recording the matches found
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> EvaluateSmartPlaylist<'a, P, S, C, J> {
+        EvaluateSmartPlaylist {
+            provider_id: self.provider_id,
+            playlist_repository: self.playlist_repository,
+            transfer_tracks: self.transfer_tracks.with_identity_graph(identity_graph),
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Evaluate the rule without writing the playlist, nor saving the smart playlist
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.transfer_tracks = self.transfer_tracks.with_dry_run(dry_run);
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Country of the account of the smart playlist, preferring the versions playable there
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.transfer_tracks = self.transfer_tracks.with_market(market);
+        self
+    }
+
+    /// Evaluate the rule against the libraries, then save the smart playlist
+    ///
+    /// Stale tracks are kept when a lookup failed, as their match may be the one that failed.
+    pub async fn execute<Q: PlaylistRepository, R: SmartPlaylistRepository>(
+        &self,
+        smart_playlist: &mut SmartPlaylist,
+        libraries: &[ProviderLibrary<'_, Q>],
+        smart_playlist_repository: &R,
+        now: DateTime<Utc>,
+    ) -> EvaluateSmartPlaylistResult<SmartPlaylistReport> {
+        let rule = smart_playlist.rule().parse::<SmartPlaylistRule>()?;
+        let mut tracks = vec![];
+
+        for source in rule.sources() {
+            let library = libraries
+                .iter()
+                .find(|library| library.provider_id == source.provider_id)
+                .ok_or(EvaluateSmartPlaylistError::MissingLibrary(
+                    source.provider_id.value(),
+                ))?;
+
+            tracks.extend(
+                library
+                    .playlist_repository
+                    .get_tracks(&source.playlist_id)
+                    .await?
+                    .into_iter()
+                    .filter(|track| rule.matches(track, &source.provider_id)),
+            );
+        }
+
+        let mut report = SmartPlaylistReport {
+            matching_tracks: tracks.len(),
+            ..Default::default()
+        };
+
+        match smart_playlist.playlist_id().cloned() {
+            Some(playlist_id) => {
+                report.transfer = self.transfer_tracks.execute(&tracks, &playlist_id).await?;
+
+                if !self.dry_run && report.transfer.failed() == 0 {
+                    let matching_ids = report
+                        .transfer
+                        .transferred_ids()
+                        .into_iter()
+                        .collect::<HashSet<_>>();
+                    let mut removed_ids = vec![];
+
+                    for track in self.playlist_repository.get_tracks(&playlist_id).await? {
+                        if let Some(id) = provider_track_id(&track, &self.provider_id) {
+                            if !matching_ids.contains(id) && !removed_ids.contains(id) {
+                                removed_ids.push(id.clone());
+                            }
+                        }
+                    }
+
+                    if !removed_ids.is_empty() {
+                        self.playlist_repository
+                            .delete_tracks(&playlist_id, &removed_ids, None)
+                            .await?;
+                    }
+
+                    report.removed_ids = removed_ids;
+                }
+            }
+            None => {
+                report.transfer = self
+                    .transfer_tracks
+                    .execute_into_new(&tracks, &PlaylistSpec::new(smart_playlist.name().clone()))
+                    .await?;
+
+                if let Some(playlist_id) = report
+                    .transfer
+                    .created_playlist
+                    .as_ref()
+                    .and_then(|created_playlist| created_playlist.id.clone())
+                {
+                    smart_playlist.set_playlist_id(playlist_id);
+                }
+            }
+        }
+
+        if !self.dry_run {
+            smart_playlist.set_evaluated_at(now);
+            smart_playlist_repository.save(smart_playlist).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Evaluate the smart playlists of the destination provider whose refresh interval has elapsed,
+    /// to be run periodically
+    ///
+    /// Failures are reported rather than stopping the refresh of the other smart playlists.
+    pub async fn refresh<Q: PlaylistRepository, R: SmartPlaylistRepository>(
+        &self,
+        libraries: &[ProviderLibrary<'_, Q>],
+        smart_playlist_repository: &R,
+        now: DateTime<Utc>,
+    ) -> EvaluateSmartPlaylistResult<Vec<SmartPlaylistRefresh>> {
+        let mut refreshes = vec![];
+
+        for mut smart_playlist in smart_playlist_repository.get_all().await? {
+            if smart_playlist.provider_id() != &self.provider_id || !smart_playlist.is_due(&now) {
+                continue;
+            }
+
+            let result = self
+                .execute(
+                    &mut smart_playlist,
+                    libraries,
+                    smart_playlist_repository,
+                    now,
+                )
+                .await;
+
+            refreshes.push(SmartPlaylistRefresh {
+                id: smart_playlist.id(),
+                name: smart_playlist.name().clone(),
+                error: result.as_ref().err().map(ToString::to_string),
+                report: result.unwrap_or_default(),
+            });
+        }
+
+        Ok(refreshes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::{DateTime, Duration, NaiveDate};
+    use url::Url;
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::{
+            playlist_repository::{PlaylistRepository, PlaylistRepositoryResult},
+            smart_playlist_repository::{SmartPlaylistRepository, SmartPlaylistRepositoryResult},
+            track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+        },
+        entities::{
+            album::Album,
+            playlist::{Playlist, PlaylistSpec},
+            smart_playlist::SmartPlaylist,
+            track::TrackWithAlbumAndArtists,
+        },
+        value_objects::{
+            playlist_id::PlaylistId, product_id::ProductId, provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::{EvaluateSmartPlaylist, ProviderLibrary};
+
+    fn track(id: &str, year: i32) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            HashSet::from_iter([ProductId::Provider((
+                ProviderId::new("stub".to_string()),
+                id.to_string(),
+            ))]),
+            id.to_string(),
+            200_000,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                NaiveDate::from_ymd_opt(year, 1, 1)
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .unwrap()
+                    .and_utc(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![],
+        )
+    }
+
+    /// Liked songs released the year of their id, and the destination playlist
+    #[derive(Default)]
+    struct StubPlaylistRepository {
+        liked_songs: Vec<String>,
+        playlist: RefCell<Vec<String>>,
+    }
+
+    impl PlaylistRepository for StubPlaylistRepository {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![])
+        }
+
+        async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            Ok(Playlist::new(
+                PlaylistId::Owned("smart".to_string()),
+                spec.name.clone(),
+                HashSet::new(),
+                String::new(),
+                0,
+                Url::parse("https://example.com/smart").unwrap(),
+            ))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            _spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.playlist.borrow_mut().extend_from_slice(ids);
+
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.playlist.borrow_mut().retain(|id| !ids.contains(id));
+
+            Ok(())
+        }
+
+        async fn get_tracks(
+            &self,
+            playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            let ids = match playlist_id {
+                PlaylistId::LikedSongs => self.liked_songs.clone(),
+                PlaylistId::Owned(_) => self.playlist.borrow().clone(),
+            };
+
+            Ok(ids
+                .iter()
+                .map(|id| track(id, id.parse().unwrap_or(2000)))
+                .collect())
+        }
+    }
+
+    impl TrackSearchRepository for StubPlaylistRepository {
+        async fn find_by_isrc(
+            &self,
+            _isrc: &str,
+        ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            Ok(None)
+        }
+
+        async fn search(
+            &self,
+            _track: &TrackWithAlbumAndArtists,
+        ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Default)]
+    struct StubSmartPlaylistRepository {
+        smart_playlists: Vec<SmartPlaylist>,
+        saved: RefCell<Vec<Uuid>>,
+    }
+
+    impl SmartPlaylistRepository for StubSmartPlaylistRepository {
+        async fn get(&self, _id: Uuid) -> SmartPlaylistRepositoryResult<Option<SmartPlaylist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> SmartPlaylistRepositoryResult<Vec<SmartPlaylist>> {
+            Ok(self.smart_playlists.clone())
+        }
+
+        async fn save(&self, smart_playlist: &SmartPlaylist) -> SmartPlaylistRepositoryResult<()> {
+            self.saved.borrow_mut().push(smart_playlist.id());
+
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> SmartPlaylistRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate() {
+        let provider_id = ProviderId::new("stub".to_string());
+        let repository = StubPlaylistRepository {
+            liked_songs: vec!["1991".to_string(), "2005".to_string(), "1999".to_string()],
+            ..Default::default()
+        };
+        let smart_playlist_repository = StubSmartPlaylistRepository::default();
+        let mut smart_playlist = SmartPlaylist::new(
+            Uuid::new_v4(),
+            "90s".to_string(),
+            "from stub:liked where year between 1990 and 1999".to_string(),
+            provider_id.clone(),
+            None,
+            Duration::hours(1),
+            None,
+        );
+        let libraries = [ProviderLibrary {
+            provider_id: provider_id.clone(),
+            playlist_repository: &repository,
+        }];
+        let evaluate = EvaluateSmartPlaylist::new(provider_id, &repository, &repository);
+        let now = DateTime::default();
+
+        let report = evaluate
+            .execute(
+                &mut smart_playlist,
+                &libraries,
+                &smart_playlist_repository,
+                now,
+            )
+            .await
+            .expect("evaluated");
+
+        assert_eq!(report.matching_tracks, 2);
+        assert_eq!(*repository.playlist.borrow(), vec!["1991", "1999"]);
+        assert!(smart_playlist.playlist_id().is_some());
+        assert!(!smart_playlist.is_due(&(now + Duration::minutes(30))));
+        assert!(smart_playlist.is_due(&(now + Duration::hours(1))));
+
+        // A track added by hand & a track no longer matching are removed
+        repository.playlist.borrow_mut().push("2010".to_string());
+        smart_playlist.set_rule("from stub:liked where year = 1999".to_string());
+
+        let report = evaluate
+            .execute(
+                &mut smart_playlist,
+                &libraries,
+                &smart_playlist_repository,
+                now,
+            )
+            .await
+            .expect("evaluated");
+
+        assert_eq!(report.removed_ids, vec!["1991", "2010"]);
+        assert_eq!(*repository.playlist.borrow(), vec!["1999"]);
+        assert_eq!(smart_playlist_repository.saved.borrow().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refresh() {
+        let provider_id = ProviderId::new("stub".to_string());
+        let repository = StubPlaylistRepository {
+            liked_songs: vec!["1991".to_string(), "2005".to_string()],
+            ..Default::default()
+        };
+        let now = DateTime::default();
+        let smart_playlist = |rule: &str, provider: &str, evaluated_at| {
+            SmartPlaylist::new(
+                Uuid::new_v4(),
+                rule.to_string(),
+                rule.to_string(),
+                ProviderId::new(provider.to_string()),
+                Some(PlaylistId::Owned("smart".to_string())),
+                Duration::hours(1),
+                evaluated_at,
+            )
+        };
+        let due = smart_playlist("from stub:liked where year < 2000", "stub", None);
+        let invalid = smart_playlist(
+            "from stub:liked where",
+            "stub",
+            Some(now - Duration::hours(2)),
+        );
+        let smart_playlist_repository = StubSmartPlaylistRepository {
+            smart_playlists: vec![
+                due.clone(),
+                invalid.clone(),
+                // Not due yet
+                smart_playlist("from stub:liked", "stub", Some(now)),
+                // Of another provider
+                smart_playlist("from stub:liked", "other", None),
+            ],
+            ..Default::default()
+        };
+        let libraries = [ProviderLibrary {
+            provider_id: provider_id.clone(),
+            playlist_repository: &repository,
+        }];
+
+        let refreshes = EvaluateSmartPlaylist::new(provider_id, &repository, &repository)
+            .refresh(&libraries, &smart_playlist_repository, now)
+            .await
+            .expect("refreshed");
+
+        assert_eq!(refreshes.len(), 2);
+        assert_eq!(refreshes[0].id, due.id());
+        assert_eq!(refreshes[0].error, None);
+        assert_eq!(refreshes[0].report.matching_tracks, 1);
+        assert_eq!(refreshes[1].id, invalid.id());
+        assert!(refreshes[1].error.is_some());
+        assert_eq!(*repository.playlist.borrow(), vec!["1991"]);
+        assert_eq!(*smart_playlist_repository.saved.borrow(), vec![due.id()]);
+    }
+}
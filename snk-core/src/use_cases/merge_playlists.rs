@@ -0,0 +1,498 @@
+use thiserror::Error;
+
+use crate::{
+    contracts::repositories::{
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        match_cache_repository::{MatchCacheRepository, NoMatchCache},
+        playlist_repository::{PlaylistRepository, PlaylistRepositoryError},
+        track_search_repository::TrackSearchRepository,
+    },
+    entities::{identity::RecordingKind, playlist::PlaylistSpec, track::TrackWithAlbumAndArtists},
+    services::{
+        deduplicator::{find_duplicates, DuplicateGroup, Equivalence},
+        identity_graph::{IdentityGraph, IdentityGraphError},
+    },
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+
+use super::transfer_tracks::{TransferReport, TransferTracks, TransferTracksError};
+
+#[derive(Debug, Error)]
+pub enum MergePlaylistsError {
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("IdentityGraph: {0}")]
+    IdentityGraphError(#[from] IdentityGraphError),
+    #[error("TransferTracks: {0}")]
+    TransferTracksError(#[from] TransferTracksError),
+}
+
+pub type MergePlaylistsResult<T> = Result<T, MergePlaylistsError>;
+
+/// Playlist to merge, from any provider
+pub struct MergeSource<'s, Q: PlaylistRepository> {
+    pub playlist_repository: &'s Q,
+    pub playlist_id: PlaylistId,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    /// Tracks read from the sources, duplicates included
+    pub source_tracks: usize,
+    /// Duplicates left out of the merge, positions being the ones in the sources put end to end
+    pub duplicates: Vec<DuplicateGroup>,
+    pub transfer: TransferReport,
+}
+
+/// Merge playlists, even from other providers, into a new deduplicated playlist
+///
+/// Sources are deduplicated before matching, then tracks found several times
+/// at the destination are added once. Tracks of the same recording in the identity graph
+/// are always duplicates.
+pub struct MergePlaylists<
+    'a,
+    P: PlaylistRepository,
+    S: TrackSearchRepository,
+    C: MatchCacheRepository = NoMatchCache,
+    I: IdentityRepository<RecordingKind> = NoIdentityRepository,
+> {
+    provider_id: ProviderId,
+    transfer_tracks: TransferTracks<'a, P, S, C, I>,
+    identity_graph: IdentityGraph<'a, RecordingKind, I>,
+    equivalence: Equivalence,
+}
+
+impl<'a, P: PlaylistRepository, S: TrackSearchRepository> MergePlaylists<'a, P, S> {
+    /// Arguments:
+    /// - provider_id: destination provider
+    /// - playlist_repository: playlists of the destination provider
+    /// - track_search_repository: catalog of the destination provider
+    pub fn new(
+        provider_id: ProviderId,
+        playlist_repository: &'a P,
+        track_search_repository: &'a S,
+    ) -> Self {
+        Self {
+            provider_id: provider_id.clone(),
+            transfer_tracks: TransferTracks::new(
+                provider_id,
+                playlist_repository,
+                track_search_repository,
+            ),
+            identity_graph: IdentityGraph::new(&NoIdentityRepository),
+            equivalence: Equivalence::default(),
+        }
+    }
+}
+
+impl<
+        'a,
+        P: PlaylistRepository,
+        S: TrackSearchRepository,
+        C: MatchCacheRepository,
+        I: IdentityRepository<RecordingKind>,
+    > MergePlaylists<'a, P, S, C, I>
+{
+    pub fn with_match_cache<M: MatchCacheRepository>(
+        self,
+        match_cache: &'a M,
+    ) -> MergePlaylists<'a, P, S, M, I> {
+        MergePlaylists {
+            provider_id: self.provider_id,
+            transfer_tracks: self.transfer_tracks.with_match_cache(match_cache),
+            identity_graph: self.identity_graph,
+            equivalence: self.equivalence,
+        }
+    }
+
+    /// Recordings shared with the other services, for the deduplication & matching
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> MergePlaylists<'a, P, S, C, J> {
+        MergePlaylists {
+            provider_id: self.provider_id,
+            transfer_tracks: self
+                .transfer_tracks
+                .with_identity_graph(identity_graph.clone()),
+            identity_graph,
+            equivalence: self.equivalence,
+        }
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.transfer_tracks = self.transfer_tracks.with_dry_run(dry_run);
+        self
+    }
+
+    /// Country of the destination account, preferring the versions playable there
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.transfer_tracks = self.transfer_tracks.with_market(market);
+        self
+    }
+
+    /// Which tracks of the sources are considered the same, exact duplicates by default
+    pub fn with_equivalence(mut self, equivalence: Equivalence) -> Self {
+        self.equivalence = equivalence;
+        self
+    }
+
+    /// Create a playlist from the spec, with the tracks of the sources in their order
+    pub async fn execute<Q: PlaylistRepository>(
+        &self,
+        sources: &[MergeSource<'_, Q>],
+        spec: &PlaylistSpec,
+    ) -> MergePlaylistsResult<MergeReport> {
+        let mut tracks = vec![];
+
+        for source in sources {
+            tracks.extend(
+                source
+                    .playlist_repository
+                    .get_tracks(&source.playlist_id)
+                    .await?,
+            );
+        }
+
+        let recordings = self.identity_graph.recordings(&tracks).await?;
+        let duplicates =
+            find_duplicates(&tracks, &recordings, &self.provider_id, &self.equivalence);
+        let mut duplicate_positions = duplicates
+            .iter()
+            .flat_map(|group| group.duplicates.iter())
+            .map(|duplicate| duplicate.track.position)
+            .collect::<Vec<_>>();
+
+        duplicate_positions.sort_unstable();
+
+        let source_tracks = tracks.len();
+        let unique_tracks = tracks
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| duplicate_positions.binary_search(position).is_err())
+            .map(|(_, track)| track)
+            .collect::<Vec<TrackWithAlbumAndArtists>>();
+
+        let transfer = self
+            .transfer_tracks
+            .execute_into_new(&unique_tracks, spec)
+            .await?;
+
+        Ok(MergeReport {
+            source_tracks,
+            duplicates,
+            transfer,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::DateTime;
+    use url::Url;
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::{
+            identity_repository::{IdentityRepository, IdentityRepositoryResult},
+            playlist_repository::{PlaylistRepository, PlaylistRepositoryResult},
+            track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+        },
+        entities::{
+            album::Album,
+            identity::{Identity, RecordingKind},
+            playlist::{Playlist, PlaylistSpec},
+            track::TrackWithAlbumAndArtists,
+        },
+        value_objects::{
+            isrc::Isrc, playlist_id::PlaylistId, product_id::ProductId,
+            provider::provider_id::ProviderId,
+        },
+    };
+
+    use crate::services::identity_graph::IdentityGraph;
+
+    use super::{MergePlaylists, MergeSource};
+
+    fn track(ids: &[ProductId]) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            HashSet::from_iter(ids.iter().cloned()),
+            "How Sweet".to_string(),
+            219_000,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![],
+        )
+    }
+
+    fn provider_id(provider: &str, id: &str) -> ProductId {
+        ProductId::Provider((ProviderId::new(provider.to_string()), id.to_string()))
+    }
+
+    fn isrc(isrc: &str) -> ProductId {
+        ProductId::ISRC(Isrc::parse(isrc).unwrap())
+    }
+
+    /// Playlists by id, recording the tracks added
+    #[derive(Default)]
+    struct StubPlaylistRepository {
+        playlists: HashMap<String, Vec<Vec<ProductId>>>,
+        added: RefCell<Vec<String>>,
+    }
+
+    impl PlaylistRepository for StubPlaylistRepository {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![])
+        }
+
+        async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            Ok(Playlist::new(
+                PlaylistId::Owned("merged".to_string()),
+                spec.name.clone(),
+                HashSet::new(),
+                String::new(),
+                0,
+                Url::parse("https://example.com/merged").unwrap(),
+            ))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            _spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.added.borrow_mut().extend_from_slice(ids);
+
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            _ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn get_tracks(
+            &self,
+            playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(self
+                .playlists
+                .get(&playlist_id.to_string())
+                .map(|tracks| tracks.iter().map(|ids| track(ids)).collect())
+                .unwrap_or_default())
+        }
+    }
+
+    impl TrackSearchRepository for StubPlaylistRepository {
+        async fn find_by_isrc(
+            &self,
+            isrc: &str,
+        ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            Ok(Some(track(&[provider_id("stub", &isrc.to_lowercase())])))
+        }
+
+        async fn search(
+            &self,
+            _track: &TrackWithAlbumAndArtists,
+        ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(vec![])
+        }
+    }
+
+    /// Recordings known beforehand
+    struct StubIdentityRepository {
+        identities: Vec<Identity<RecordingKind>>,
+    }
+
+    impl IdentityRepository<RecordingKind> for StubIdentityRepository {
+        async fn get(&self, id: Uuid) -> IdentityRepositoryResult<Option<Identity<RecordingKind>>> {
+            Ok(self
+                .identities
+                .iter()
+                .find(|identity| identity.id() == id)
+                .cloned())
+        }
+
+        async fn find_by_ids(
+            &self,
+            ids: &HashSet<ProductId>,
+        ) -> IdentityRepositoryResult<Vec<Identity<RecordingKind>>> {
+            Ok(self
+                .identities
+                .iter()
+                .filter(|identity| !identity.ids().is_disjoint(ids))
+                .cloned()
+                .collect())
+        }
+
+        async fn save(&self, _identity: &Identity<RecordingKind>) -> IdentityRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn merge(
+            &self,
+            _identity: &Identity<RecordingKind>,
+            _merged: &[Uuid],
+        ) -> IdentityRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn split(
+            &self,
+            _identity: &Identity<RecordingKind>,
+            _split_identity: &Identity<RecordingKind>,
+        ) -> IdentityRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> IdentityRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_recordings() {
+        let destination = StubPlaylistRepository {
+            playlists: HashMap::from_iter([(
+                "mine".to_string(),
+                vec![vec![provider_id("stub", "a")]],
+            )]),
+            ..Default::default()
+        };
+        let other = StubPlaylistRepository {
+            playlists: HashMap::from_iter([(
+                "theirs".to_string(),
+                vec![
+                    vec![provider_id("other", "1")],
+                    vec![provider_id("other", "2")],
+                ],
+            )]),
+            ..Default::default()
+        };
+        // Nothing in common but the recording
+        let recordings = StubIdentityRepository {
+            identities: vec![Identity::new(
+                Uuid::new_v4(),
+                "How Sweet".to_string(),
+                HashSet::from_iter([provider_id("stub", "a"), provider_id("other", "2")]),
+            )],
+        };
+
+        let report = MergePlaylists::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        )
+        .with_identity_graph(IdentityGraph::new(&recordings))
+        .with_dry_run(true)
+        .execute(
+            &[
+                MergeSource {
+                    playlist_repository: &destination,
+                    playlist_id: PlaylistId::Owned("mine".to_string()),
+                },
+                MergeSource {
+                    playlist_repository: &other,
+                    playlist_id: PlaylistId::Owned("theirs".to_string()),
+                },
+            ],
+            &PlaylistSpec::new("Merged".to_string()),
+        )
+        .await
+        .expect("merged");
+
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].kept.position, 0);
+        assert_eq!(report.duplicates[0].duplicates[0].track.position, 2);
+        assert_eq!(report.transfer.items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_merge() {
+        let destination = StubPlaylistRepository {
+            playlists: HashMap::from_iter([(
+                "mine".to_string(),
+                vec![
+                    vec![provider_id("stub", "usa2p2414843")],
+                    vec![provider_id("stub", "b")],
+                ],
+            )]),
+            ..Default::default()
+        };
+        let other = StubPlaylistRepository {
+            playlists: HashMap::from_iter([(
+                "theirs".to_string(),
+                vec![
+                    vec![provider_id("other", "1"), isrc("USA2P2414843")],
+                    vec![provider_id("other", "2"), isrc("USA2P2414845")],
+                    // Another version of the same recording
+                    vec![provider_id("other", "3"), isrc("USA2P2414845")],
+                ],
+            )]),
+            ..Default::default()
+        };
+
+        let report = MergePlaylists::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        )
+        .execute(
+            &[
+                MergeSource {
+                    playlist_repository: &destination,
+                    playlist_id: PlaylistId::Owned("mine".to_string()),
+                },
+                MergeSource {
+                    playlist_repository: &other,
+                    playlist_id: PlaylistId::Owned("theirs".to_string()),
+                },
+            ],
+            &PlaylistSpec::new("Merged".to_string()),
+        )
+        .await
+        .expect("merged");
+
+        assert_eq!(report.source_tracks, 5);
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].duplicates[0].track.position, 4);
+        assert_eq!(report.transfer.items.len(), 4);
+        // The track matched to "usa2p2414843" is added once
+        assert_eq!(
+            *destination.added.borrow(),
+            vec!["usa2p2414843", "b", "usa2p2414845"]
+        );
+    }
+}
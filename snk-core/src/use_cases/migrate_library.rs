@@ -0,0 +1,920 @@
+use thiserror::Error;
+
+use crate::{
+    contracts::repositories::{
+        account_profile_repository::{AccountProfileRepository, AccountProfileRepositoryError},
+        album_library_repository::{AlbumLibraryRepository, AlbumLibraryRepositoryError},
+        artist_library_repository::{ArtistLibraryRepository, ArtistLibraryRepositoryError},
+        catalog_search_repository::CatalogSearchRepository,
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        match_cache_repository::{MatchCacheRepository, NoMatchCache},
+        playlist_repository::{ChunkResult, PlaylistRepository, PlaylistRepositoryError},
+        track_search_repository::TrackSearchRepository,
+    },
+    entities::{
+        identity::RecordingKind,
+        playlist::{Playlist, PlaylistSpec},
+    },
+    services::{
+        identity_graph::IdentityGraph,
+        library_matcher::{provider_album_id, LibraryMatcher},
+    },
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+
+use super::transfer_tracks::{
+    TransferReport, TransferReportItem, TransferStatus, TransferTracks, TransferTracksError,
+};
+
+#[derive(Debug, Error)]
+pub enum MigrateLibraryError {
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("AlbumLibraryRepository: {0}")]
+    AlbumLibraryRepositoryError(#[from] AlbumLibraryRepositoryError),
+    #[error("ArtistLibraryRepository: {0}")]
+    ArtistLibraryRepositoryError(#[from] ArtistLibraryRepositoryError),
+    #[error("AccountProfileRepository: {0}")]
+    AccountProfileRepositoryError(#[from] AccountProfileRepositoryError),
+    #[error("TransferTracks: {0}")]
+    TransferTracksError(#[from] TransferTracksError),
+}
+
+pub type MigrateLibraryResult<T> = Result<T, MigrateLibraryError>;
+
+/// Items of a kind to migrate, by their id at the source provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Nothing,
+    Only(Vec<String>),
+}
+
+impl Selection {
+    pub fn includes(&self, id: &str) -> bool {
+        match self {
+            Selection::All => true,
+            Selection::Nothing => false,
+            Selection::Only(ids) => ids.iter().any(|selected| selected == id),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationOptions {
+    pub liked_songs: bool,
+    /// Owned playlists of the source, followed playlists of other users are never migrated
+    pub playlists: Selection,
+    /// Saved albums of the source
+    pub albums: Selection,
+    /// Followed artists of the source
+    pub artists: Selection,
+    /// Run all the reads & matching, without writing anything to the destination
+    pub dry_run: bool,
+}
+
+impl Default for MigrationOptions {
+    /// Everything, for real
+    fn default() -> Self {
+        Self {
+            liked_songs: true,
+            playlists: Selection::All,
+            albums: Selection::All,
+            artists: Selection::All,
+            dry_run: false,
+        }
+    }
+}
+
+/// Outcome of the migration of one playlist
+#[derive(Debug, Clone)]
+pub struct PlaylistMigrationReport {
+    pub source_id: PlaylistId,
+    pub name: String,
+    /// Playlist created at the destination, `None` in dry-run or when the creation failed
+    pub destination_id: Option<PlaylistId>,
+    pub tracks: TransferReport,
+    /// Failure preventing the playlist from being migrated
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    pub liked_songs: Option<TransferReport>,
+    pub playlists: Vec<PlaylistMigrationReport>,
+    pub albums: TransferReport,
+    pub artists: TransferReport,
+}
+
+/// Move a whole account from a provider to another: liked songs, owned playlists,
+/// saved albums and followed artists
+pub struct MigrateLibrary<'a, S, D, C = NoMatchCache, I = NoIdentityRepository>
+where
+    S: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + AccountProfileRepository,
+    D: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + TrackSearchRepository
+        + CatalogSearchRepository,
+    C: MatchCacheRepository,
+    I: IdentityRepository<RecordingKind>,
+{
+    source_provider_id: ProviderId,
+    source: &'a S,
+    destination_provider_id: ProviderId,
+    destination: &'a D,
+    library_matcher: LibraryMatcher<'a, D>,
+    match_cache: &'a C,
+    identity_graph: IdentityGraph<'a, RecordingKind, I>,
+    market: Option<String>,
+}
+
+impl<'a, S, D> MigrateLibrary<'a, S, D>
+where
+    S: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + AccountProfileRepository,
+    D: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + TrackSearchRepository
+        + CatalogSearchRepository,
+{
+    pub fn new(
+        source_provider_id: ProviderId,
+        source: &'a S,
+        destination_provider_id: ProviderId,
+        destination: &'a D,
+    ) -> Self {
+        Self {
+            source_provider_id,
+            source,
+            destination_provider_id: destination_provider_id.clone(),
+            destination,
+            library_matcher: LibraryMatcher::new(destination_provider_id, destination),
+            match_cache: &NoMatchCache,
+            identity_graph: IdentityGraph::new(&NoIdentityRepository),
+            market: None,
+        }
+    }
+}
+
+impl<'a, S, D, C, I> MigrateLibrary<'a, S, D, C, I>
+where
+    S: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + AccountProfileRepository,
+    D: PlaylistRepository
+        + AlbumLibraryRepository
+        + ArtistLibraryRepository
+        + TrackSearchRepository
+        + CatalogSearchRepository,
+    C: MatchCacheRepository,
+    I: IdentityRepository<RecordingKind>,
+{
+    /// Reuse and record the outcomes of the track lookups in a cache shared across transfers
+    pub fn with_match_cache<M: MatchCacheRepository>(
+        self,
+        match_cache: &'a M,
+    ) -> MigrateLibrary<'a, S, D, M, I> {
+        MigrateLibrary {
+            source_provider_id: self.source_provider_id,
+            source: self.source,
+            destination_provider_id: self.destination_provider_id,
+            destination: self.destination,
+            library_matcher: self.library_matcher,
+            match_cache,
+            identity_graph: self.identity_graph,
+            market: self.market,
+        }
+    }
+
+    /// Match the tracks with the ids of their recordings, recording the matches found
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> MigrateLibrary<'a, S, D, C, J> {
+        MigrateLibrary {
+            source_provider_id: self.source_provider_id,
+            source: self.source,
+            destination_provider_id: self.destination_provider_id,
+            destination: self.destination,
+            library_matcher: self.library_matcher,
+            match_cache: self.match_cache,
+            identity_graph,
+            market: self.market,
+        }
+    }
+
+    /// Country of the destination account, preferring the versions of the tracks playable there
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.market = market;
+        self
+    }
+
+    pub async fn execute(
+        &self,
+        options: &MigrationOptions,
+    ) -> MigrateLibraryResult<MigrationReport> {
+        let mut report = MigrationReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        let transfer_tracks = TransferTracks::new(
+            self.destination_provider_id.clone(),
+            self.destination,
+            self.destination,
+        )
+        .with_match_cache(self.match_cache)
+        .with_identity_graph(self.identity_graph.clone())
+        .with_dry_run(options.dry_run)
+        .with_market(self.market.clone());
+
+        if options.liked_songs {
+            let tracks = self.source.get_tracks(&PlaylistId::LikedSongs).await?;
+
+            report.liked_songs = Some(
+                transfer_tracks
+                    .execute(&tracks, &PlaylistId::LikedSongs)
+                    .await?,
+            );
+        }
+
+        if options.playlists != Selection::Nothing {
+            let account_id = self.source.get_profile().await?.id().clone();
+
+            for playlist in PlaylistRepository::get_all(self.source).await? {
+                let owned = playlist.owner_id() == Some(&account_id);
+
+                if owned && options.playlists.includes(&playlist.id().to_string()) {
+                    report
+                        .playlists
+                        .push(self.migrate_playlist(&transfer_tracks, &playlist).await);
+                }
+            }
+        }
+
+        if options.albums != Selection::Nothing {
+            report.albums = self
+                .migrate_albums(&options.albums, options.dry_run)
+                .await?;
+        }
+
+        if options.artists != Selection::Nothing {
+            report.artists = self
+                .migrate_artists(&options.artists, options.dry_run)
+                .await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Copy a playlist with its metadata & cover, failures are reported rather than stopping the migration
+    async fn migrate_playlist(
+        &self,
+        transfer_tracks: &TransferTracks<'a, D, D, C, I>,
+        playlist: &Playlist,
+    ) -> PlaylistMigrationReport {
+        let source_id = playlist.id();
+        let mut playlist_report = PlaylistMigrationReport {
+            source_id: source_id.clone(),
+            name: playlist.name().clone(),
+            destination_id: None,
+            tracks: TransferReport::default(),
+            error: None,
+        };
+
+        let result = match self.source.get_tracks(source_id).await {
+            Ok(tracks) => transfer_tracks
+                .execute_into_new(&tracks, &PlaylistSpec::from(playlist))
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match result {
+            Ok(tracks_report) => {
+                playlist_report.destination_id = tracks_report
+                    .created_playlist
+                    .as_ref()
+                    .and_then(|created_playlist| created_playlist.id.clone());
+                playlist_report.tracks = tracks_report;
+            }
+            Err(err) => playlist_report.error = Some(err),
+        }
+
+        playlist_report
+    }
+
+    async fn migrate_albums(
+        &self,
+        selection: &Selection,
+        dry_run: bool,
+    ) -> MigrateLibraryResult<TransferReport> {
+        let mut report = TransferReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        for album in AlbumLibraryRepository::get_all(self.source).await? {
+            let selected = provider_album_id(&album, &self.source_provider_id)
+                .is_some_and(|id| selection.includes(id));
+
+            if !selected {
+                continue;
+            }
+
+            report.items.push(TransferReportItem {
+                name: album.album().name().clone(),
+                artists: album
+                    .artists()
+                    .iter()
+                    .map(|artist| artist.name().clone())
+                    .collect(),
+                status: match self.library_matcher.find_album(&album).await {
+                    Ok(Some(id)) => TransferStatus::Transferred(id),
+                    Ok(None) => TransferStatus::NotFound,
+                    Err(err) => TransferStatus::Failed(err.to_string()),
+                },
+                unavailable: false,
+            });
+        }
+
+        let ids = report.transferred_ids();
+
+        if !dry_run && !ids.is_empty() {
+            match AlbumLibraryRepository::save(self.destination, &ids).await {
+                Ok(()) => {}
+                Err(AlbumLibraryRepositoryError::PartialFailure(chunks)) => {
+                    mark_failed(&mut report, &chunks)
+                }
+                Err(err) => mark_failed(&mut report, &[failed_chunk(ids, &err.to_string())]),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn migrate_artists(
+        &self,
+        selection: &Selection,
+        dry_run: bool,
+    ) -> MigrateLibraryResult<TransferReport> {
+        let mut report = TransferReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        for artist in ArtistLibraryRepository::get_all(self.source).await? {
+            let selected = artist
+                .ids()
+                .get(&self.source_provider_id)
+                .is_some_and(|id| selection.includes(id));
+
+            if !selected {
+                continue;
+            }
+
+            report.items.push(TransferReportItem {
+                name: artist.name().clone(),
+                artists: vec![],
+                status: match self.library_matcher.find_artist(&artist).await {
+                    Ok(Some(id)) => TransferStatus::Transferred(id),
+                    Ok(None) => TransferStatus::NotFound,
+                    Err(err) => TransferStatus::Failed(err.to_string()),
+                },
+                unavailable: false,
+            });
+        }
+
+        let ids = report.transferred_ids();
+
+        if !dry_run && !ids.is_empty() {
+            match ArtistLibraryRepository::save(self.destination, &ids).await {
+                Ok(()) => {}
+                Err(ArtistLibraryRepositoryError::PartialFailure(chunks)) => {
+                    mark_failed(&mut report, &chunks)
+                }
+                Err(err) => mark_failed(&mut report, &[failed_chunk(ids, &err.to_string())]),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Save failing before any chunk was sent
+fn failed_chunk(ids: Vec<String>, error: &str) -> ChunkResult {
+    ChunkResult {
+        ids,
+        error: Some(error.to_string()),
+    }
+}
+
+/// Items matched but not saved, their chunk having failed
+fn mark_failed(report: &mut TransferReport, chunks: &[ChunkResult]) {
+    for chunk in chunks {
+        let Some(error) = &chunk.error else {
+            continue;
+        };
+
+        for item in report.items.iter_mut() {
+            if matches!(&item.status, TransferStatus::Transferred(id) if chunk.ids.contains(id)) {
+                item.status = TransferStatus::Failed(error.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::DateTime;
+    use url::Url;
+
+    use crate::{
+        contracts::repositories::{
+            account_profile_repository::{
+                AccountProfileRepository, AccountProfileRepositoryResult,
+            },
+            album_library_repository::{
+                AlbumLibraryRepository, AlbumLibraryRepositoryError, AlbumLibraryRepositoryResult,
+            },
+            artist_library_repository::{ArtistLibraryRepository, ArtistLibraryRepositoryResult},
+            catalog_search_repository::{CatalogSearchRepository, CatalogSearchRepositoryResult},
+            match_cache_repository::{
+                CachedMatch, MatchCacheRepository, MatchCacheRepositoryResult,
+            },
+            playlist_repository::{ChunkResult, PlaylistRepository, PlaylistRepositoryResult},
+            track_search_repository::{TrackSearchRepository, TrackSearchRepositoryResult},
+        },
+        entities::{
+            account_profile::AccountProfile,
+            album::{Album, AlbumWithArtists},
+            artist::Artist,
+            playlist::{Playlist, PlaylistSpec},
+            track::TrackWithAlbumAndArtists,
+        },
+        use_cases::transfer_tracks::TransferStatus,
+        value_objects::{
+            isrc::Isrc, playlist_id::PlaylistId, product_id::ProductId,
+            provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::{MigrateLibrary, MigrationOptions, Selection};
+
+    fn source_id() -> ProviderId {
+        ProviderId::new("source".to_string())
+    }
+
+    fn destination_id() -> ProviderId {
+        ProviderId::new("stub".to_string())
+    }
+
+    fn album(name: &str, ids: HashSet<ProductId>) -> Album {
+        Album::new(
+            ids,
+            name.to_string(),
+            DateTime::default(),
+            HashSet::new(),
+            HashMap::new(),
+        )
+    }
+
+    fn track(name: &str, id: ProductId) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            HashSet::from_iter([id]),
+            name.to_string(),
+            219_000,
+            HashMap::new(),
+            album("", HashSet::new()),
+            vec![],
+        )
+    }
+
+    fn playlist(id: &str, owner_id: &str) -> Playlist {
+        let mut playlist = Playlist::new(
+            PlaylistId::Owned(id.to_string()),
+            id.to_string(),
+            HashSet::new(),
+            owner_id.to_string(),
+            1,
+            Url::parse("https://example.com/playlist").unwrap(),
+        );
+
+        playlist.set_owner_id(Some(owner_id.to_string()));
+        playlist
+    }
+
+    /// Library of an album & an artist known by both providers, per name
+    fn saved_album(name: &str) -> AlbumWithArtists {
+        AlbumWithArtists::new(
+            album(
+                name,
+                HashSet::from_iter([
+                    ProductId::Provider((source_id(), format!("source-{}", name))),
+                    ProductId::Provider((destination_id(), name.to_string())),
+                ]),
+            ),
+            vec![],
+        )
+    }
+
+    fn followed_artist(name: &str) -> Artist {
+        Artist::new(
+            HashMap::from_iter([
+                (source_id(), format!("source-{}", name)),
+                (destination_id(), name.to_string()),
+            ]),
+            name.to_string(),
+            HashMap::new(),
+        )
+    }
+
+    /// Account "me", following a playlist of another user
+    struct StubSource;
+
+    impl PlaylistRepository for StubSource {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![
+                playlist("mine", "me"),
+                playlist("followed", "someone"),
+            ])
+        }
+
+        async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            Ok(playlist(&spec.name, "me"))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            _spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            _ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            _ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn get_tracks(
+            &self,
+            playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(match playlist_id {
+                PlaylistId::LikedSongs => vec![track(
+                    "How Sweet",
+                    ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap()),
+                )],
+                PlaylistId::Owned(_) => vec![track(
+                    "Supernatural",
+                    ProductId::ISRC(Isrc::parse("USA2P2425323").unwrap()),
+                )],
+            })
+        }
+    }
+
+    impl AlbumLibraryRepository for StubSource {
+        async fn get_all(&self) -> AlbumLibraryRepositoryResult<Vec<AlbumWithArtists>> {
+            Ok(vec![saved_album("get-up"), saved_album("how-sweet")])
+        }
+
+        async fn save(&self, _ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn remove(&self, _ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    impl ArtistLibraryRepository for StubSource {
+        async fn get_all(&self) -> ArtistLibraryRepositoryResult<Vec<Artist>> {
+            Ok(vec![followed_artist("newjeans")])
+        }
+
+        async fn save(&self, _ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn remove(&self, _ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    impl AccountProfileRepository for StubSource {
+        async fn get_profile(&self) -> AccountProfileRepositoryResult<AccountProfile> {
+            Ok(AccountProfile::new(
+                "me".to_string(),
+                "Me".to_string(),
+                None,
+            ))
+        }
+    }
+
+    /// Catalog knowing tracks by ISRC only, recording every write
+    #[derive(Default)]
+    struct StubDestination {
+        writes: RefCell<Vec<String>>,
+        /// Album saves fail for the chunk (of one album) of this id
+        rejected_album: Option<String>,
+    }
+
+    impl PlaylistRepository for StubDestination {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![])
+        }
+
+        async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            self.writes
+                .borrow_mut()
+                .push(format!("create {}", spec.name));
+
+            Ok(playlist(&format!("new-{}", spec.name), "me"))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            _spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.writes
+                .borrow_mut()
+                .push(format!("add {} {}", playlist_id, ids.join(",")));
+
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            _ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn get_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(vec![])
+        }
+    }
+
+    impl TrackSearchRepository for StubDestination {
+        async fn find_by_isrc(
+            &self,
+            isrc: &str,
+        ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            Ok(Some(track(
+                isrc,
+                ProductId::Provider((destination_id(), isrc.to_lowercase())),
+            )))
+        }
+
+        async fn search(
+            &self,
+            _track: &TrackWithAlbumAndArtists,
+        ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(vec![])
+        }
+    }
+
+    impl CatalogSearchRepository for StubDestination {
+        async fn find_album_by_upc(
+            &self,
+            _upc: &str,
+        ) -> CatalogSearchRepositoryResult<Option<AlbumWithArtists>> {
+            Ok(None)
+        }
+
+        async fn search_albums(
+            &self,
+            _album: &AlbumWithArtists,
+        ) -> CatalogSearchRepositoryResult<Vec<AlbumWithArtists>> {
+            Ok(vec![])
+        }
+
+        async fn search_artists(
+            &self,
+            _artist: &Artist,
+        ) -> CatalogSearchRepositoryResult<Vec<Artist>> {
+            Ok(vec![])
+        }
+    }
+
+    impl AlbumLibraryRepository for StubDestination {
+        async fn get_all(&self) -> AlbumLibraryRepositoryResult<Vec<AlbumWithArtists>> {
+            Ok(vec![])
+        }
+
+        async fn save(&self, ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+            let Some(rejected_album) = &self.rejected_album else {
+                self.writes
+                    .borrow_mut()
+                    .push(format!("save albums {}", ids.join(",")));
+
+                return Ok(());
+            };
+
+            Err(AlbumLibraryRepositoryError::PartialFailure(
+                ids.iter()
+                    .map(|id| ChunkResult {
+                        ids: vec![id.clone()],
+                        error: (id == rejected_album).then(|| "rejected".to_string()),
+                    })
+                    .collect(),
+            ))
+        }
+
+        async fn remove(&self, _ids: &[String]) -> AlbumLibraryRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    impl ArtistLibraryRepository for StubDestination {
+        async fn get_all(&self) -> ArtistLibraryRepositoryResult<Vec<Artist>> {
+            Ok(vec![])
+        }
+
+        async fn save(&self, ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+            self.writes
+                .borrow_mut()
+                .push(format!("follow {}", ids.join(",")));
+
+            Ok(())
+        }
+
+        async fn remove(&self, _ids: &[String]) -> ArtistLibraryRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute() {
+        let destination = StubDestination::default();
+        let migrate_library =
+            MigrateLibrary::new(source_id(), &StubSource, destination_id(), &destination);
+
+        let report = migrate_library
+            .execute(&MigrationOptions::default())
+            .await
+            .expect("migrated");
+
+        assert_eq!(report.liked_songs.expect("liked songs").transferred(), 1);
+        // The followed playlist of another user is left out
+        assert_eq!(report.playlists.len(), 1);
+        assert_eq!(report.playlists[0].name, "mine");
+        assert_eq!(report.albums.transferred(), 2);
+        assert_eq!(report.artists.transferred(), 1);
+        assert_eq!(
+            *destination.writes.borrow(),
+            vec![
+                "add favourites usa2p2414843",
+                "create mine",
+                "add new-mine usa2p2425323",
+                "save albums get-up,how-sweet",
+                "follow newjeans"
+            ]
+        );
+    }
+
+    /// Cache recording the lookups stored, by product id & market
+    #[derive(Default)]
+    struct StubMatchCache {
+        stored: RefCell<Vec<(String, Option<String>)>>,
+    }
+
+    impl MatchCacheRepository for StubMatchCache {
+        async fn get(
+            &self,
+            _product_id: &ProductId,
+            _provider_id: &ProviderId,
+            _market: Option<&str>,
+        ) -> MatchCacheRepositoryResult<Option<CachedMatch>> {
+            Ok(None)
+        }
+
+        async fn set(
+            &self,
+            product_id: &ProductId,
+            _provider_id: &ProviderId,
+            market: Option<&str>,
+            _cached_match: &CachedMatch,
+        ) -> MatchCacheRepositoryResult<()> {
+            self.stored
+                .borrow_mut()
+                .push((product_id.id().clone(), market.map(str::to_string)));
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_match_cache() {
+        let destination = StubDestination::default();
+        let match_cache = StubMatchCache::default();
+
+        MigrateLibrary::new(source_id(), &StubSource, destination_id(), &destination)
+            .with_match_cache(&match_cache)
+            .with_market(Some("FR".to_string()))
+            .execute(&MigrationOptions {
+                playlists: Selection::Nothing,
+                albums: Selection::Nothing,
+                artists: Selection::Nothing,
+                ..Default::default()
+            })
+            .await
+            .expect("migrated");
+
+        assert!(!match_cache.stored.borrow().is_empty());
+        assert!(match_cache
+            .stored
+            .borrow()
+            .iter()
+            .all(|(_, market)| market.as_deref() == Some("FR")));
+    }
+
+    #[tokio::test]
+    async fn test_execute_partial_save_failure() {
+        let destination = StubDestination {
+            rejected_album: Some("how-sweet".to_string()),
+            ..Default::default()
+        };
+        let migrate_library =
+            MigrateLibrary::new(source_id(), &StubSource, destination_id(), &destination);
+
+        let report = migrate_library
+            .execute(&MigrationOptions {
+                liked_songs: false,
+                playlists: Selection::Nothing,
+                artists: Selection::Nothing,
+                ..Default::default()
+            })
+            .await
+            .expect("migrated");
+
+        // Only the album of the failed chunk is reported as failed
+        assert_eq!(
+            report.albums.items[0].status,
+            TransferStatus::Transferred("get-up".to_string())
+        );
+        assert_eq!(
+            report.albums.items[1].status,
+            TransferStatus::Failed("rejected".to_string())
+        );
+        assert!(report.artists.items.is_empty());
+    }
+}
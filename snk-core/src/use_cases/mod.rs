@@ -0,0 +1,5 @@
+pub mod evaluate_smart_playlist;
+pub mod merge_playlists;
+pub mod migrate_library;
+pub mod split_playlist;
+pub mod transfer_tracks;
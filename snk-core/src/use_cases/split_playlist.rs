@@ -0,0 +1,315 @@
+use chrono::{DateTime, Datelike, Utc};
+use thiserror::Error;
+
+use crate::{
+    contracts::repositories::playlist_repository::{PlaylistRepository, PlaylistRepositoryError},
+    entities::{playlist::PlaylistSpec, track::TrackWithAlbumAndArtists},
+    services::track_matcher::{normalize, provider_track_id},
+    value_objects::{playlist_id::PlaylistId, provider::provider_id::ProviderId},
+};
+
+#[derive(Debug, Error)]
+pub enum SplitPlaylistError {
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("InvalidSize: parts must have at least one track")]
+    InvalidSize,
+}
+
+pub type SplitPlaylistResult<T> = Result<T, SplitPlaylistError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitBy {
+    /// Parts of a number of tracks, in the order of the playlist
+    Size(usize),
+    /// Decade of the release of the album, unknown dates last
+    Decade,
+    /// Main artist, artists with less tracks than the minimum being put together last
+    Artist { min_tracks: usize },
+}
+
+/// Playlist created with a part of the tracks
+#[derive(Debug, Clone)]
+pub struct SplitPart {
+    pub name: String,
+    /// `None` in dry-run, the playlist not being created
+    pub id: Option<PlaylistId>,
+    pub track_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SplitReport {
+    pub dry_run: bool,
+    pub parts: Vec<SplitPart>,
+    /// Tracks without id at the provider, left out of the parts
+    pub skipped: usize,
+}
+
+/// Split a playlist into new playlists of the same provider, the playlist being kept
+pub struct SplitPlaylist<'a, P: PlaylistRepository> {
+    provider_id: ProviderId,
+    playlist_repository: &'a P,
+    dry_run: bool,
+}
+
+impl<'a, P: PlaylistRepository> SplitPlaylist<'a, P> {
+    /// Arguments:
+    /// - provider_id: provider of the playlist
+    /// - playlist_repository: playlists of the provider
+    pub fn new(provider_id: ProviderId, playlist_repository: &'a P) -> Self {
+        Self {
+            provider_id,
+            playlist_repository,
+            dry_run: false,
+        }
+    }
+
+    /// Compute the parts only, without creating the playlists
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Create one playlist per part, named after the playlist & the part (ex: "Mix (1990s)")
+    pub async fn execute(
+        &self,
+        playlist_id: &PlaylistId,
+        name: &str,
+        split_by: &SplitBy,
+    ) -> SplitPlaylistResult<SplitReport> {
+        if *split_by == SplitBy::Size(0) {
+            return Err(SplitPlaylistError::InvalidSize);
+        }
+
+        let tracks = self.playlist_repository.get_tracks(playlist_id).await?;
+        let mut report = SplitReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+
+        let tracks = tracks
+            .iter()
+            .filter_map(|track| {
+                let id = provider_track_id(track, &self.provider_id);
+
+                if id.is_none() {
+                    report.skipped += 1;
+                }
+
+                id.map(|id| (track, id))
+            })
+            .collect::<Vec<_>>();
+
+        for (label, ids) in split(&tracks, split_by) {
+            let name = format!("{} ({})", name, label);
+            let id = match self.dry_run {
+                true => None,
+                false => {
+                    let id = self
+                        .playlist_repository
+                        .create(&PlaylistSpec::new(name.clone()))
+                        .await?
+                        .id()
+                        .clone();
+
+                    self.playlist_repository.add_tracks(&id, &ids, None).await?;
+
+                    Some(id)
+                }
+            };
+
+            report.parts.push(SplitPart {
+                name,
+                id,
+                track_ids: ids,
+            });
+        }
+
+        Ok(report)
+    }
+}
+
+/// Label & ids of each part, without duplicates within a part
+fn split(
+    tracks: &[(&TrackWithAlbumAndArtists, &String)],
+    split_by: &SplitBy,
+) -> Vec<(String, Vec<String>)> {
+    let mut parts: Vec<(String, Vec<String>)> = vec![];
+
+    match split_by {
+        SplitBy::Size(size) => {
+            let mut ids: Vec<String> = vec![];
+
+            for (_, id) in tracks {
+                if !ids.contains(id) {
+                    ids.push((*id).clone());
+                }
+            }
+
+            let count = ids.len().div_ceil(*size);
+
+            for (index, chunk) in ids.chunks(*size).enumerate() {
+                parts.push((format!("{}/{}", index + 1, count), chunk.to_vec()));
+            }
+        }
+        SplitBy::Decade => {
+            let mut decades = tracks
+                .iter()
+                .map(|(track, id)| (decade(track), *id))
+                .collect::<Vec<_>>();
+
+            // Stable, so that tracks keep their order within a decade
+            decades.sort_by_key(|(decade, _)| decade.unwrap_or(i32::MAX));
+
+            for (decade, id) in decades {
+                let label = match decade {
+                    Some(decade) => format!("{}s", decade),
+                    None => "Unknown decade".to_string(),
+                };
+
+                push_id(&mut parts, label, id);
+            }
+        }
+        SplitBy::Artist { min_tracks } => {
+            let mut artists: Vec<(String, String, Vec<String>)> = vec![];
+
+            for (track, id) in tracks {
+                let Some(artist) = track.artists().first() else {
+                    continue;
+                };
+                let key = normalize(artist.name());
+
+                match artists.iter_mut().find(|(other, ..)| *other == key) {
+                    Some((.., ids)) if ids.contains(id) => {}
+                    Some((.., ids)) => ids.push((*id).clone()),
+                    None => artists.push((key, artist.name().clone(), vec![(*id).clone()])),
+                }
+            }
+
+            let mut others = vec![];
+
+            for (_, name, ids) in artists {
+                match ids.len() >= *min_tracks {
+                    true => parts.push((name, ids)),
+                    false => others.extend(ids),
+                }
+            }
+
+            // Tracks without artist
+            for (track, id) in tracks {
+                if track.artists().is_empty() {
+                    others.push((*id).clone());
+                }
+            }
+
+            for id in others {
+                push_id(&mut parts, "Other artists".to_string(), &id);
+            }
+        }
+    }
+
+    parts
+}
+
+/// Add the id to the last part if it has the label, else to a new part
+fn push_id(parts: &mut Vec<(String, Vec<String>)>, label: String, id: &String) {
+    match parts.last_mut() {
+        Some((last_label, ids)) if *last_label == label => {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        _ => parts.push((label, vec![id.clone()])),
+    }
+}
+
+/// First year of the decade the album was released, `None` if the date is unknown
+fn decade(track: &TrackWithAlbumAndArtists) -> Option<i32> {
+    let release_date = track.album().release_date();
+
+    // Unknown dates default to the epoch
+    (*release_date != DateTime::<Utc>::default()).then(|| release_date.year() / 10 * 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use chrono::{DateTime, NaiveDate};
+
+    use crate::{
+        entities::{album::Album, artist::Artist, track::TrackWithAlbumAndArtists},
+        value_objects::{product_id::ProductId, provider::provider_id::ProviderId},
+    };
+
+    use super::{split, SplitBy};
+
+    fn track(id: &str, artist: &str, year: i32) -> TrackWithAlbumAndArtists {
+        let release_date = match year {
+            0 => DateTime::default(),
+            year => NaiveDate::from_ymd_opt(year, 6, 1)
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .unwrap()
+                .and_utc(),
+        };
+
+        TrackWithAlbumAndArtists::new(
+            HashSet::from_iter([ProductId::Provider((
+                ProviderId::new("stub".to_string()),
+                id.to_string(),
+            ))]),
+            id.to_string(),
+            200_000,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                release_date,
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![Artist::new(
+                HashMap::new(),
+                artist.to_string(),
+                HashMap::new(),
+            )],
+        )
+    }
+
+    fn labels(parts: &[(String, Vec<String>)]) -> Vec<String> {
+        parts
+            .iter()
+            .map(|(label, ids)| format!("{}: {}", label, ids.join(",")))
+            .collect()
+    }
+
+    #[test]
+    fn test_split() {
+        let tracks = [
+            track("1", "NewJeans", 2022),
+            track("2", "Kehlani", 2014),
+            track("3", "newjeans", 0),
+            track("4", "Aaliyah", 1994),
+            track("5", "NewJeans", 2023),
+            track("1", "NewJeans", 2022),
+        ];
+        let ids = tracks
+            .iter()
+            .map(|track| track.name().clone())
+            .collect::<Vec<_>>();
+        let tracks = tracks.iter().zip(ids.iter()).collect::<Vec<_>>();
+
+        assert_eq!(
+            labels(&split(&tracks, &SplitBy::Size(2))),
+            vec!["1/3: 1,2", "2/3: 3,4", "3/3: 5"]
+        );
+        assert_eq!(
+            labels(&split(&tracks, &SplitBy::Decade)),
+            vec!["1990s: 4", "2010s: 2", "2020s: 1,5", "Unknown decade: 3"]
+        );
+        assert_eq!(
+            labels(&split(&tracks, &SplitBy::Artist { min_tracks: 2 })),
+            vec!["NewJeans: 1,3,5", "Other artists: 2,4"]
+        );
+    }
+}
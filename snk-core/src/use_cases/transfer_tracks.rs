@@ -0,0 +1,976 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::{
+    contracts::repositories::{
+        identity_repository::{IdentityRepository, NoIdentityRepository},
+        match_cache_repository::{MatchCacheRepository, NoMatchCache},
+        playlist_repository::{PlaylistRepository, PlaylistRepositoryError},
+        track_metadata_repository::{NoTrackMetadataRepository, TrackMetadataRepository},
+        track_search_repository::TrackSearchRepository,
+        transfer_job_repository::{TransferJobRepository, TransferJobRepositoryError},
+    },
+    entities::{
+        identity::RecordingKind, playlist::PlaylistSpec, track::TrackWithAlbumAndArtists,
+        transfer_job::TransferJob,
+    },
+    services::{
+        identity_graph::IdentityGraph,
+        metadata_enricher::MetadataEnricher,
+        track_matcher::{normalize, provider_track_id, TrackMatcher},
+    },
+    value_objects::{
+        playlist_id::PlaylistId, product_id::ProductId, provider::provider_id::ProviderId,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum TransferTracksError {
+    #[error("PlaylistRepository: {0}")]
+    PlaylistRepositoryError(#[from] PlaylistRepositoryError),
+    #[error("TransferJobRepository: {0}")]
+    TransferJobRepositoryError(#[from] TransferJobRepositoryError),
+    #[error("Job {0} has neither a playlist to create nor a destination playlist")]
+    InvalidJob(String),
+}
+
+pub type TransferTracksResult<T> = Result<T, TransferTracksError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferStatus {
+    /// Added to the playlist, with the id of the track for the destination provider
+    Transferred(String),
+    /// No equivalent found in the catalog of the destination provider
+    NotFound,
+    /// The lookup of an equivalent failed
+    Failed(String),
+}
+
+/// Outcome of the transfer of one track
+#[derive(Debug, Clone)]
+pub struct TransferReportItem {
+    pub name: String,
+    pub artists: Vec<String>,
+    pub status: TransferStatus,
+    /// Transferred, yet known to be unplayable in the market of the destination account
+    pub unavailable: bool,
+}
+
+/// Playlist created at the destination to receive the tracks
+#[derive(Debug, Clone)]
+pub struct CreatedPlaylist {
+    pub name: String,
+    /// `None` in dry-run, the playlist not being created
+    pub id: Option<PlaylistId>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TransferReport {
+    /// Nothing was written, transferred items are the ones that would be
+    pub dry_run: bool,
+    pub created_playlist: Option<CreatedPlaylist>,
+    /// One item per source track, in the order of the source
+    pub items: Vec<TransferReportItem>,
+}
+
+impl TransferReport {
+    pub fn transferred(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.status, TransferStatus::Transferred(_)))
+            .count()
+    }
+
+    pub fn not_found(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status == TransferStatus::NotFound)
+            .count()
+    }
+
+    /// Ids of the transferred items, without duplicates (items may appear several times in the source)
+    pub fn transferred_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = vec![];
+
+        for item in &self.items {
+            if let TransferStatus::Transferred(id) = &item.status {
+                if !ids.contains(id) {
+                    ids.push(id.clone());
+                }
+            }
+        }
+
+        ids
+    }
+
+    pub fn failed(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.status, TransferStatus::Failed(_)))
+            .count()
+    }
+
+    /// Transferred items the destination account can't play
+    pub fn unavailable(&self) -> usize {
+        self.items.iter().filter(|item| item.unavailable).count()
+    }
+}
+
+/// Copy tracks coming from any source (provider, file...) into a playlist of a provider
+///
+/// Tracks already in the destination playlist are never added twice.
+pub struct TransferTracks<
+    'a,
+    P: PlaylistRepository,
+    S: TrackSearchRepository,
+    C: MatchCacheRepository = NoMatchCache,
+    I: IdentityRepository<RecordingKind> = NoIdentityRepository,
+    M: TrackMetadataRepository = NoTrackMetadataRepository,
+> {
+    playlist_repository: &'a P,
+    track_matcher: TrackMatcher<'a, S, C, I>,
+    metadata_enricher: MetadataEnricher<'a, M>,
+    dry_run: bool,
+}
+
+impl<'a, P: PlaylistRepository, S: TrackSearchRepository> TransferTracks<'a, P, S> {
+    /// Arguments:
+    /// - provider_id: destination provider
+    /// - playlist_repository: playlists of the destination provider
+    /// - track_search_repository: catalog of the destination provider
+    pub fn new(
+        provider_id: ProviderId,
+        playlist_repository: &'a P,
+        track_search_repository: &'a S,
+    ) -> Self {
+        Self {
+            playlist_repository,
+            track_matcher: TrackMatcher::new(provider_id, track_search_repository),
+            metadata_enricher: MetadataEnricher::new(&NoTrackMetadataRepository),
+            dry_run: false,
+        }
+    }
+}
+
+impl<
+        'a,
+        P: PlaylistRepository,
+        S: TrackSearchRepository,
+        C: MatchCacheRepository,
+        I: IdentityRepository<RecordingKind>,
+        M: TrackMetadataRepository,
+    > TransferTracks<'a, P, S, C, I, M>
+{
+    /// Reuse and record the outcomes of lookups in a cache shared across transfers
+    pub fn with_match_cache<D: MatchCacheRepository>(
+        self,
+        match_cache: &'a D,
+    ) -> TransferTracks<'a, P, S, D, I, M> {
+        TransferTracks {
+            playlist_repository: self.playlist_repository,
+            track_matcher: self.track_matcher.with_match_cache(match_cache),
+            metadata_enricher: self.metadata_enricher,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Match the tracks with the ids of their recordings, recording the matches found
+    pub fn with_identity_graph<J: IdentityRepository<RecordingKind>>(
+        self,
+        identity_graph: IdentityGraph<'a, RecordingKind, J>,
+    ) -> TransferTracks<'a, P, S, C, J, M> {
+        TransferTracks {
+            playlist_repository: self.playlist_repository,
+            track_matcher: self.track_matcher.with_identity_graph(identity_graph),
+            metadata_enricher: self.metadata_enricher,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Look up the ISRC of the tracks without one in a metadata database before matching them,
+    /// the tracks being matched as they are when the lookup fails
+    pub fn with_metadata_enricher<N: TrackMetadataRepository>(
+        self,
+        metadata_enricher: MetadataEnricher<'a, N>,
+    ) -> TransferTracks<'a, P, S, C, I, N> {
+        TransferTracks {
+            playlist_repository: self.playlist_repository,
+            track_matcher: self.track_matcher,
+            metadata_enricher,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Run the reads & matching only, without writing anything to the destination
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// ISO 3166-1 alpha-2 country of the destination account, preferring the versions playable there
+    pub fn with_market(mut self, market: Option<String>) -> Self {
+        self.track_matcher = self.track_matcher.with_market(market);
+        self
+    }
+
+    /// Add the tracks to an existing playlist
+    pub async fn execute(
+        &self,
+        tracks: &[TrackWithAlbumAndArtists],
+        playlist_id: &PlaylistId,
+    ) -> TransferTracksResult<TransferReport> {
+        let mut report = self.match_tracks(tracks).await;
+
+        self.add_tracks(playlist_id, &mut report).await?;
+
+        Ok(report)
+    }
+
+    /// Create a playlist from the spec (metadata & cover) and add the tracks to it
+    pub async fn execute_into_new(
+        &self,
+        tracks: &[TrackWithAlbumAndArtists],
+        spec: &PlaylistSpec,
+    ) -> TransferTracksResult<TransferReport> {
+        let mut report = self.match_tracks(tracks).await;
+
+        let id = match self.dry_run {
+            true => None,
+            false => Some(self.playlist_repository.create(spec).await?.id().clone()),
+        };
+
+        report.created_playlist = Some(CreatedPlaylist {
+            name: spec.name.clone(),
+            id: id.clone(),
+        });
+
+        if let Some(id) = id {
+            self.add_tracks(&id, &mut report).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Run or resume a transfer, saving its progress in the job after each step
+    ///
+    /// The outcome of each match is checkpointed, so that a retry only looks up the remaining
+    /// tracks, and the created playlist is mapped to the job, so that it is created only once.
+    /// Failed lookups are not checkpointed, a retry tries them again.
+    pub async fn execute_job<J: TransferJobRepository>(
+        &self,
+        tracks: &[TrackWithAlbumAndArtists],
+        job: &mut TransferJob,
+        job_repository: &J,
+    ) -> TransferTracksResult<TransferReport> {
+        let mut report = self.new_report();
+
+        for track in tracks {
+            let key = checkpoint_key(track);
+
+            let (status, unavailable) = match job.checkpoint(&key) {
+                Some(Some(id)) => (TransferStatus::Transferred(id.clone()), false),
+                Some(None) => (TransferStatus::NotFound, false),
+                None => {
+                    let (status, unavailable) = self.match_track(track).await;
+                    let checkpoint = match &status {
+                        TransferStatus::Transferred(id) => Some(Some(id.clone())),
+                        TransferStatus::NotFound => Some(None),
+                        TransferStatus::Failed(_) => None,
+                    };
+
+                    if let (false, Some(destination_id)) = (self.dry_run, checkpoint) {
+                        job.set_checkpoint(key, destination_id);
+                        job_repository.save(job).await?;
+                    }
+
+                    (status, unavailable)
+                }
+            };
+
+            report.items.push(report_item(track, status, unavailable));
+        }
+
+        let playlist_id = match (job.destination_playlist_id(), job.playlist_spec()) {
+            (Some(playlist_id), _) => Some(playlist_id.clone()),
+            (None, Some(spec)) => {
+                let name = spec.name.clone();
+                let id = match self.dry_run {
+                    true => None,
+                    false => Some(self.playlist_repository.create(spec).await?.id().clone()),
+                };
+
+                if let Some(id) = &id {
+                    job.set_destination_playlist_id(id.clone());
+                    job_repository.save(job).await?;
+                }
+
+                report.created_playlist = Some(CreatedPlaylist {
+                    name,
+                    id: id.clone(),
+                });
+
+                id
+            }
+            (None, None) => return Err(TransferTracksError::InvalidJob(job.id().to_string())),
+        };
+
+        if let Some(playlist_id) = playlist_id {
+            self.add_tracks(&playlist_id, &mut report).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Add the transferred tracks missing from the playlist
+    ///
+    /// Tracks of the chunks the provider rejected are reported as failed.
+    async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId,
+        report: &mut TransferReport,
+    ) -> TransferTracksResult<()> {
+        let mut ids = report.transferred_ids();
+
+        if self.dry_run || ids.is_empty() {
+            return Ok(());
+        }
+
+        let existing_ids = self
+            .playlist_repository
+            .get_tracks(playlist_id)
+            .await?
+            .iter()
+            .filter_map(|track| provider_track_id(track, self.track_matcher.provider_id()))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        ids.retain(|id| !existing_ids.contains(id));
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        match self
+            .playlist_repository
+            .add_tracks(playlist_id, &ids, None)
+            .await
+        {
+            Err(PlaylistRepositoryError::PartialFailure(chunks)) => {
+                for chunk in chunks {
+                    let Some(error) = chunk.error else {
+                        continue;
+                    };
+
+                    for item in report.items.iter_mut() {
+                        if matches!(&item.status, TransferStatus::Transferred(id) if chunk.ids.contains(id))
+                        {
+                            item.status = TransferStatus::Failed(error.clone());
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// Find the equivalent of each track, without writing anything to the playlist
+    pub async fn match_tracks(&self, tracks: &[TrackWithAlbumAndArtists]) -> TransferReport {
+        let mut report = self.new_report();
+
+        for track in tracks {
+            let (status, unavailable) = self.match_track(track).await;
+
+            report.items.push(report_item(track, status, unavailable));
+        }
+
+        report
+    }
+
+    /// Status of the track, with whether its match is known to be unplayable
+    async fn match_track(&self, track: &TrackWithAlbumAndArtists) -> (TransferStatus, bool) {
+        let enriched = self.metadata_enricher.enriched(track).await.ok().flatten();
+
+        match self
+            .track_matcher
+            .find_playable_match(enriched.as_ref().unwrap_or(track))
+            .await
+        {
+            Ok(Some(track_match)) => (
+                TransferStatus::Transferred(track_match.id),
+                track_match.playable == Some(false),
+            ),
+            Ok(None) => (TransferStatus::NotFound, false),
+            Err(err) => (TransferStatus::Failed(err.to_string()), false),
+        }
+    }
+
+    fn new_report(&self) -> TransferReport {
+        TransferReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        }
+    }
+}
+
+fn report_item(
+    track: &TrackWithAlbumAndArtists,
+    status: TransferStatus,
+    unavailable: bool,
+) -> TransferReportItem {
+    TransferReportItem {
+        name: track.name().clone(),
+        artists: track
+            .artists()
+            .iter()
+            .map(|artist| artist.name().clone())
+            .collect(),
+        status,
+        unavailable,
+    }
+}
+
+/// Stable key of a source track across runs: a provider id, else the ISRC, else its metadata
+pub fn checkpoint_key(track: &TrackWithAlbumAndArtists) -> String {
+    let provider_key = track
+        .ids()
+        .iter()
+        .filter_map(|id| match id {
+            ProductId::Provider((provider_id, id)) => {
+                Some(format!("{}:{}", provider_id.as_str(), id))
+            }
+            _ => None,
+        })
+        .min();
+
+    if let Some(key) = provider_key {
+        return key;
+    }
+
+    let isrc_key = track
+        .ids()
+        .iter()
+        .filter_map(|id| match id {
+            ProductId::ISRC(isrc) => Some(format!("isrc:{}", isrc)),
+            _ => None,
+        })
+        .min();
+
+    isrc_key.unwrap_or_else(|| {
+        let artists = track
+            .artists()
+            .iter()
+            .map(|artist| normalize(artist.name()))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "track:{}|{}|{}",
+            normalize(track.name()),
+            artists,
+            track.duration_ms()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::{HashMap, HashSet},
+    };
+
+    use chrono::DateTime;
+    use url::Url;
+    use uuid::Uuid;
+
+    use crate::{
+        contracts::repositories::{
+            playlist_repository::{
+                ChunkResult, PlaylistRepository, PlaylistRepositoryError, PlaylistRepositoryResult,
+            },
+            track_metadata_repository::{TrackMetadataRepository, TrackMetadataRepositoryResult},
+            track_search_repository::{
+                TrackSearchRepository, TrackSearchRepositoryError, TrackSearchRepositoryResult,
+            },
+            transfer_job_repository::{TransferJobRepository, TransferJobRepositoryResult},
+        },
+        entities::{
+            album::Album,
+            playlist::{Playlist, PlaylistSpec},
+            track::TrackWithAlbumAndArtists,
+            transfer_job::TransferJob,
+        },
+        value_objects::{
+            cover_image::CoverImage, image_cover::ImageCover, isrc::Isrc, playlist_id::PlaylistId,
+            product_id::ProductId, provider::provider_id::ProviderId,
+        },
+    };
+
+    use crate::services::metadata_enricher::MetadataEnricher;
+
+    use super::{TransferStatus, TransferTracks};
+
+    fn track(name: &str, ids: HashSet<ProductId>) -> TrackWithAlbumAndArtists {
+        TrackWithAlbumAndArtists::new(
+            ids,
+            name.to_string(),
+            219_000,
+            HashMap::new(),
+            Album::new(
+                HashSet::new(),
+                String::new(),
+                DateTime::default(),
+                HashSet::new(),
+                HashMap::new(),
+            ),
+            vec![],
+        )
+    }
+
+    fn stub_track(id: &str) -> TrackWithAlbumAndArtists {
+        track(
+            id,
+            HashSet::from_iter([ProductId::Provider((
+                ProviderId::new("stub".to_string()),
+                id.to_string(),
+            ))]),
+        )
+    }
+
+    /// Catalog knowing tracks by ISRC only, recording every write
+    #[derive(Default)]
+    struct StubDestination {
+        writes: RefCell<Vec<String>>,
+        created: RefCell<Option<PlaylistSpec>>,
+        playlist: RefCell<Vec<String>>,
+        lookups: Cell<usize>,
+        failing_isrc: RefCell<Option<String>>,
+        rejected_id: RefCell<Option<String>>,
+    }
+
+    impl PlaylistRepository for StubDestination {
+        async fn get(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            Ok(None)
+        }
+
+        async fn get_all(&self) -> PlaylistRepositoryResult<Vec<Playlist>> {
+            Ok(vec![])
+        }
+
+        async fn create(&self, spec: &PlaylistSpec) -> PlaylistRepositoryResult<Playlist> {
+            self.writes
+                .borrow_mut()
+                .push(format!("create {}", spec.name));
+            self.created.replace(Some(spec.clone()));
+
+            Ok(Playlist::new(
+                PlaylistId::Owned("new".to_string()),
+                spec.name.clone(),
+                HashSet::new(),
+                String::new(),
+                0,
+                Url::parse("https://example.com/new").unwrap(),
+            ))
+        }
+
+        async fn update_metadata(
+            &self,
+            _id: &PlaylistId,
+            spec: &PlaylistSpec,
+        ) -> PlaylistRepositoryResult<()> {
+            self.writes
+                .borrow_mut()
+                .push(format!("update {}", spec.name));
+
+            Ok(())
+        }
+
+        async fn delete(&self, _id: &PlaylistId) -> PlaylistRepositoryResult<Option<Playlist>> {
+            self.writes.borrow_mut().push("delete".to_string());
+
+            Ok(None)
+        }
+
+        async fn add_tracks(
+            &self,
+            playlist_id: &PlaylistId,
+            ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            // One chunk per id
+            if let Some(rejected_id) = self.rejected_id.borrow().as_ref() {
+                return Err(PlaylistRepositoryError::PartialFailure(
+                    ids.iter()
+                        .map(|id| ChunkResult {
+                            ids: vec![id.clone()],
+                            error: (id == rejected_id).then(|| "rejected".to_string()),
+                        })
+                        .collect(),
+                ));
+            }
+
+            self.writes
+                .borrow_mut()
+                .push(format!("add {} {}", playlist_id, ids.join(",")));
+            self.playlist.borrow_mut().extend_from_slice(ids);
+
+            Ok(())
+        }
+
+        async fn delete_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+            _ids: &[String],
+            _snapshot_id: Option<String>,
+        ) -> PlaylistRepositoryResult<()> {
+            self.writes.borrow_mut().push("delete_tracks".to_string());
+
+            Ok(())
+        }
+
+        async fn get_tracks(
+            &self,
+            _playlist_id: &PlaylistId,
+        ) -> PlaylistRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(self
+                .playlist
+                .borrow()
+                .iter()
+                .map(|id| stub_track(id))
+                .collect())
+        }
+    }
+
+    impl TrackSearchRepository for StubDestination {
+        async fn find_by_isrc(
+            &self,
+            isrc: &str,
+        ) -> TrackSearchRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            self.lookups.set(self.lookups.get() + 1);
+
+            if self.failing_isrc.borrow().as_deref() == Some(isrc) {
+                return Err(TrackSearchRepositoryError::ServiceError(
+                    "rate limited".to_string(),
+                ));
+            }
+
+            Ok(Some(stub_track(&isrc.to_lowercase())))
+        }
+
+        async fn search(
+            &self,
+            _track: &TrackWithAlbumAndArtists,
+        ) -> TrackSearchRepositoryResult<Vec<TrackWithAlbumAndArtists>> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Default)]
+    struct StubJobRepository {
+        saves: Cell<usize>,
+    }
+
+    impl TransferJobRepository for StubJobRepository {
+        async fn get(&self, _id: Uuid) -> TransferJobRepositoryResult<Option<TransferJob>> {
+            Ok(None)
+        }
+
+        async fn save(&self, _job: &TransferJob) -> TransferJobRepositoryResult<()> {
+            self.saves.set(self.saves.get() + 1);
+
+            Ok(())
+        }
+
+        async fn delete(&self, _id: Uuid) -> TransferJobRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Metadata database knowing the ISRC of every recording
+    struct StubMetadataRepository;
+
+    impl TrackMetadataRepository for StubMetadataRepository {
+        async fn find_recording(
+            &self,
+            track: &TrackWithAlbumAndArtists,
+        ) -> TrackMetadataRepositoryResult<Option<TrackWithAlbumAndArtists>> {
+            Ok(Some(self::track(
+                track.name(),
+                HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2414845").unwrap())]),
+            )))
+        }
+    }
+
+    fn source_tracks() -> Vec<TrackWithAlbumAndArtists> {
+        vec![
+            track(
+                "How Sweet",
+                HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())]),
+            ),
+            track("Unknown", HashSet::new()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_execute_into_new() {
+        let destination = StubDestination::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        );
+
+        let mut source_playlist = Playlist::new(
+            PlaylistId::Owned("source".to_string()),
+            "Migrated".to_string(),
+            HashSet::from_iter([
+                ImageCover::Sm(Url::parse("https://example.com/sm.jpg").unwrap()),
+                ImageCover::Lg(Url::parse("https://example.com/lg.jpg").unwrap()),
+            ]),
+            String::new(),
+            2,
+            Url::parse("https://example.com/source").unwrap(),
+        );
+        source_playlist.set_description(Some("Road trip".to_string()));
+        source_playlist.set_public(Some(false));
+
+        let report = transfer_tracks
+            .execute_into_new(&source_tracks(), &PlaylistSpec::from(&source_playlist))
+            .await
+            .expect("transferred");
+
+        let created = destination.created.take().expect("playlist created");
+
+        assert_eq!(created.description, Some("Road trip".to_string()));
+        assert_eq!(created.public, Some(false));
+        assert_eq!(created.collaborative, None);
+        assert_eq!(
+            created.cover,
+            Some(CoverImage::Url(
+                Url::parse("https://example.com/lg.jpg").unwrap()
+            ))
+        );
+
+        assert!(!report.dry_run);
+        assert_eq!(
+            report
+                .created_playlist
+                .and_then(|playlist| playlist.id)
+                .map(|id| id.to_string()),
+            Some("new".to_string())
+        );
+        assert_eq!(
+            report.items[0].status,
+            TransferStatus::Transferred("usa2p2414843".to_string())
+        );
+        assert_eq!(report.items[1].status, TransferStatus::NotFound);
+        assert_eq!(
+            *destination.writes.borrow(),
+            vec!["create Migrated", "add new usa2p2414843"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metadata_enricher() {
+        let destination = StubDestination::default();
+        let tracks = source_tracks();
+
+        let report = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        )
+        .with_metadata_enricher(MetadataEnricher::new(&StubMetadataRepository))
+        .match_tracks(&tracks)
+        .await;
+
+        // Tracks having an ISRC are matched as they are
+        assert_eq!(
+            report.items[0].status,
+            TransferStatus::Transferred("usa2p2414843".to_string())
+        );
+        assert_eq!(
+            report.items[1].status,
+            TransferStatus::Transferred("usa2p2414845".to_string())
+        );
+        assert_eq!(report.items[1].name, "Unknown");
+        assert!(tracks[1].ids().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let destination = StubDestination::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        )
+        .with_dry_run(true);
+
+        let report = transfer_tracks
+            .execute_into_new(&source_tracks(), &PlaylistSpec::new("Migrated".to_string()))
+            .await
+            .expect("matched");
+
+        assert!(report.dry_run);
+
+        let created_playlist = report.created_playlist.clone().expect("playlist to create");
+
+        assert_eq!(created_playlist.name, "Migrated");
+        assert!(created_playlist.id.is_none());
+        assert_eq!(report.transferred(), 1);
+        assert_eq!(report.not_found(), 1);
+        assert!(destination.writes.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_job() {
+        let destination = StubDestination::default();
+        let job_repository = StubJobRepository::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        );
+        let mut tracks = source_tracks();
+
+        tracks.push(track(
+            "Supernatural",
+            HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2425323").unwrap())]),
+        ));
+
+        let mut job = TransferJob::into_new_playlist(
+            Uuid::new_v4(),
+            PlaylistSpec::new("Migrated".to_string())
+                .with_description(Some("Road trip".to_string())),
+        );
+
+        // First run interrupted while looking up the last track
+        *destination.failing_isrc.borrow_mut() = Some("USA2P2425323".to_string());
+
+        let report = transfer_tracks
+            .execute_job(&tracks, &mut job, &job_repository)
+            .await
+            .expect("transferred");
+
+        assert_eq!(report.failed(), 1);
+        assert_eq!(job.checkpoints().len(), 2);
+        assert!(job.destination_playlist_id().is_some());
+        assert_eq!(
+            destination
+                .created
+                .take()
+                .and_then(|created| created.description),
+            Some("Road trip".to_string())
+        );
+
+        // Retry: only the failed track is looked up, the playlist is reused
+        *destination.failing_isrc.borrow_mut() = None;
+        destination.lookups.set(0);
+
+        let report = transfer_tracks
+            .execute_job(&tracks, &mut job, &job_repository)
+            .await
+            .expect("transferred");
+
+        assert_eq!(report.transferred(), 2);
+        assert!(report.created_playlist.is_none());
+        assert_eq!(destination.lookups.get(), 1);
+        assert_eq!(
+            *destination.writes.borrow(),
+            vec![
+                "create Migrated",
+                "add new usa2p2414843",
+                "add new usa2p2425323"
+            ]
+        );
+        assert_eq!(job_repository.saves.get(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_execute_skips_present_tracks() {
+        let destination = StubDestination::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        );
+        let playlist_id = PlaylistId::Owned("new".to_string());
+
+        for _ in 0..2 {
+            transfer_tracks
+                .execute(&source_tracks(), &playlist_id)
+                .await
+                .expect("transferred");
+        }
+
+        assert_eq!(*destination.playlist.borrow(), vec!["usa2p2414843"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_skips_present_tracks_beyond_first_page() {
+        let destination = StubDestination::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        );
+        let mut tracks = source_tracks();
+
+        tracks.push(track(
+            "Supernatural",
+            HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2425323").unwrap())]),
+        ));
+
+        // Larger than a page of the providers (100 tracks at most)
+        destination
+            .playlist
+            .borrow_mut()
+            .extend((0..150).map(|index| format!("filler-{}", index)));
+        destination
+            .playlist
+            .borrow_mut()
+            .push("usa2p2414843".to_string());
+
+        let report = transfer_tracks
+            .execute(&tracks, &PlaylistId::Owned("new".to_string()))
+            .await
+            .expect("transferred");
+
+        assert_eq!(report.transferred(), 2);
+        assert_eq!(*destination.writes.borrow(), vec!["add new usa2p2425323"]);
+        assert_eq!(destination.playlist.borrow().len(), 152);
+    }
+
+    #[tokio::test]
+    async fn test_execute_partial_failure() {
+        let destination = StubDestination::default();
+        let transfer_tracks = TransferTracks::new(
+            ProviderId::new("stub".to_string()),
+            &destination,
+            &destination,
+        );
+        let mut tracks = source_tracks();
+
+        tracks.push(track(
+            "Supernatural",
+            HashSet::from_iter([ProductId::ISRC(Isrc::parse("USA2P2425323").unwrap())]),
+        ));
+
+        *destination.rejected_id.borrow_mut() = Some("usa2p2425323".to_string());
+
+        let report = transfer_tracks
+            .execute(&tracks, &PlaylistId::Owned("new".to_string()))
+            .await
+            .expect("partially transferred");
+
+        assert_eq!(report.transferred(), 1);
+        assert_eq!(
+            report.items[2].status,
+            TransferStatus::Failed("rejected".to_string())
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::{fmt::Display, ops::Deref, str::FromStr};
+
+use super::product_id::ProductIdError;
+
+/// Universal Product Code (UPC-A), the 12 digits barcode of releases in North America
+///
+/// Also accepted when padded with leading zeros, as an EAN-13 or GTIN-14.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Upc {
+    code: String,
+}
+
+/// International Article Number (EAN-13), the 13 digits barcode of releases
+///
+/// A UPC is the EAN prefixed with 0, prefer [`Ean::to_upc`] to compare them.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Ean {
+    code: String,
+}
+
+impl Upc {
+    pub fn parse(value: &str) -> Result<Self, ProductIdError> {
+        gtin(value, 12)
+            .map(|code| Self { code })
+            .ok_or(ProductIdError::InvalidUpc(value.to_string()))
+    }
+
+    /// The EAN-13 of the product, the UPC prefixed with 0
+    pub fn to_ean(&self) -> Ean {
+        Ean {
+            code: format!("0{}", self.code),
+        }
+    }
+}
+
+impl Ean {
+    pub fn parse(value: &str) -> Result<Self, ProductIdError> {
+        gtin(value, 13)
+            .map(|code| Self { code })
+            .ok_or(ProductIdError::InvalidEan(value.to_string()))
+    }
+
+    /// The UPC-A of the product, if it was assigned in North America (EAN starting with 0)
+    pub fn to_upc(&self) -> Option<Upc> {
+        self.code.strip_prefix('0').map(|code| Upc {
+            code: code.to_string(),
+        })
+    }
+}
+
+/// Digits of a GTIN of the given length, padded or stripped of leading zeros,
+/// `None` when invalid or when its check digit doesn't match
+fn gtin(value: &str, length: usize) -> Option<String> {
+    let digits = value
+        .chars()
+        .filter(|char| !matches!(char, '-' | ' '))
+        .collect::<String>();
+
+    if digits.is_empty() || !digits.chars().all(|char| char.is_ascii_digit()) {
+        return None;
+    }
+
+    let code = match digits.len() {
+        len if len < length => format!("{}{}", "0".repeat(length - len), digits),
+        len if digits[..len - length].chars().all(|char| char == '0') => {
+            digits[len - length..].to_string()
+        }
+        _ => return None,
+    };
+
+    (check_digit(&code[..length - 1]) == code[length - 1..]).then_some(code)
+}
+
+/// Modulo 10 check digit of GTINs, weighting digits by 3 & 1 from the right
+fn check_digit(digits: &str) -> String {
+    let sum = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| {
+            let digit = (digit - b'0') as u32;
+
+            match index % 2 {
+                0 => digit * 3,
+                _ => digit,
+            }
+        })
+        .sum::<u32>();
+
+    ((10 - sum % 10) % 10).to_string()
+}
+
+impl FromStr for Upc {
+    type Err = ProductIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl FromStr for Ean {
+    type Err = ProductIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for Upc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Display for Ean {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Deref for Upc {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.code
+    }
+}
+
+impl Deref for Ean {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_objects::product_id::{ProductId, ProductIdError};
+
+    use super::{Ean, Upc};
+
+    #[test]
+    fn test_parse_upc() {
+        let upc = Upc::parse("886443671584").expect("valid upc");
+
+        assert_eq!(Upc::parse("0886443671584"), Ok(upc.clone()));
+        assert_eq!(Upc::parse("00886443671584"), Ok(upc.clone()));
+        assert_eq!(Upc::parse("8 86443 67158 4"), Ok(upc.clone()));
+        assert_eq!(upc.to_ean().as_str(), "0886443671584");
+
+        assert_eq!(
+            Upc::parse("886443671585"),
+            Err(ProductIdError::InvalidUpc("886443671585".to_string()))
+        );
+        assert!(Upc::parse("5099749534728").is_err());
+        assert!(Upc::parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_ean() {
+        let ean = Ean::parse("5099749534728").expect("valid ean");
+
+        assert_eq!(ean.to_upc(), None);
+        assert!(Ean::parse("5099749534729").is_err());
+        assert!(Ean::parse("50997495347x8").is_err());
+
+        let ean = Ean::parse("196922889738").expect("valid ean");
+
+        assert_eq!(ean.as_str(), "0196922889738");
+        assert_eq!(ean.to_upc(), Upc::parse("196922889738").ok());
+    }
+
+    #[test]
+    fn test_barcode_product_id() {
+        assert!(
+            ProductId::barcode("0196922889738")
+                == Ok(ProductId::UPC(Upc::parse("196922889738").unwrap()))
+        );
+        assert!(
+            ProductId::barcode("5099749534728")
+                == Ok(ProductId::EAN(Ean::parse("5099749534728").unwrap()))
+        );
+        assert!(
+            ProductId::barcode("5099749534720")
+                == Err(ProductIdError::InvalidEan("5099749534720".to_string()))
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use url::Url;
+
+/// Image to set as the cover of a playlist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverImage {
+    /// Image to download first, ex: the cover of the source playlist
+    Url(Url),
+    /// Content of a JPEG file
+    Jpeg(Vec<u8>),
+}
@@ -1,6 +1,12 @@
 use url::Url;
 
+/// Serialized as `{"size":"lg","url":"https://..."}`
 #[derive(Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "size", content = "url", rename_all = "lowercase")
+)]
 pub enum ImageCover {
     Sm(Url),
     Md(Url),
@@ -0,0 +1,104 @@
+use std::{fmt::Display, ops::Deref, str::FromStr};
+
+use super::product_id::ProductIdError;
+
+/// International Standard Recording Code, e.g. `USA2P2414843`
+///
+/// Normalized to its 12 uppercase characters, without the hyphens or spaces it's often written
+/// with (`US-A2P-24-14843`): country code (2 letters), registrant code (3 alphanumerics),
+/// year of reference (2 digits) & designation code (5 digits).
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct Isrc {
+    code: String,
+}
+
+impl Isrc {
+    pub fn parse(value: &str) -> Result<Self, ProductIdError> {
+        let code = value
+            .chars()
+            .filter(|char| !matches!(char, '-' | ' '))
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let code = code.strip_prefix("ISRC").unwrap_or(&code).to_string();
+
+        // Sliced by bytes, only once known to be ASCII
+        let valid = code.is_ascii()
+            && code.len() == 12
+            && code[0..2].chars().all(|char| char.is_ascii_uppercase())
+            && code[2..5].chars().all(|char| char.is_ascii_alphanumeric())
+            && code[5..12].chars().all(|char| char.is_ascii_digit());
+
+        match valid {
+            true => Ok(Self { code }),
+            false => Err(ProductIdError::InvalidIsrc(value.to_string())),
+        }
+    }
+
+    /// ISO 3166-1 alpha-2 country of the registrant (not of the recording)
+    pub fn country_code(&self) -> &str {
+        &self.code[0..2]
+    }
+
+    pub fn registrant_code(&self) -> &str {
+        &self.code[2..5]
+    }
+
+    /// Last 2 digits of the year the code was assigned
+    pub fn year_of_reference(&self) -> u8 {
+        self.code[5..7].parse().unwrap_or_default()
+    }
+
+    pub fn designation_code(&self) -> u32 {
+        self.code[7..12].parse().unwrap_or_default()
+    }
+}
+
+impl FromStr for Isrc {
+    type Err = ProductIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for Isrc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Deref for Isrc {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_objects::product_id::ProductIdError;
+
+    use super::Isrc;
+
+    #[test]
+    fn test_parse_isrc() {
+        let isrc = Isrc::parse("us-a2p-24-14843").expect("valid isrc");
+
+        assert_eq!(isrc.as_str(), "USA2P2414843");
+        assert_eq!(isrc.country_code(), "US");
+        assert_eq!(isrc.registrant_code(), "A2P");
+        assert_eq!(isrc.year_of_reference(), 24);
+        assert_eq!(isrc.designation_code(), 14843);
+        assert_eq!(Isrc::parse("ISRC USA2P2414843"), Ok(isrc));
+
+        assert_eq!(
+            Isrc::parse("1SA2P2414843"),
+            Err(ProductIdError::InvalidIsrc("1SA2P2414843".to_string()))
+        );
+        assert!(Isrc::parse("USA2P24148").is_err());
+        assert!(Isrc::parse("USA2P24148AB").is_err());
+        assert!(Isrc::parse("AéA2P241484").is_err());
+        assert!(Isrc::parse("USA2P24148é").is_err());
+    }
+}
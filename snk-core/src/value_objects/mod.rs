@@ -1,4 +1,7 @@
+pub mod barcode;
+pub mod cover_image;
 pub mod image_cover;
+pub mod isrc;
 pub mod playlist_id;
 pub mod product_id;
 pub mod provider;
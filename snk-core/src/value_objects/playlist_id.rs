@@ -1,6 +1,12 @@
 use std::fmt::Display;
 
+/// Serialized as `{"type":"likedSongs"}` or `{"type":"owned","value":"..."}`
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "camelCase")
+)]
 pub enum PlaylistId {
     LikedSongs,
     Owned(String),
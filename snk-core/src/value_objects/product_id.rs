@@ -1,20 +1,98 @@
-use super::provider::provider_id::ProviderId;
+use std::ops::Deref;
 
-#[derive(Hash, PartialEq, Eq)]
+use thiserror::Error;
+
+use super::{
+    barcode::{Ean, Upc},
+    isrc::Isrc,
+    provider::provider_id::ProviderId,
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProductIdError {
+    #[error("Invalid ISRC: {0}")]
+    InvalidIsrc(String),
+    #[error("Invalid UPC: {0}")]
+    InvalidUpc(String),
+    #[error("Invalid EAN: {0}")]
+    InvalidEan(String),
+}
+
+/// Serialized as `{"type":"isrc","value":"..."}`,
+/// provider ids as `{"type":"provider","provider":"spotify","value":"..."}`
+#[derive(Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "ProductIdWire", try_from = "ProductIdWire")
+)]
 pub enum ProductId {
-    ISRC(String),
-    UPC(String),
-    EAN(String),
+    ISRC(Isrc),
+    UPC(Upc),
+    EAN(Ean),
     Provider((ProviderId, String)),
 }
 
 impl ProductId {
     pub fn id(&self) -> &String {
         match self {
-            ProductId::ISRC(id) => id,
-            ProductId::UPC(id) => id,
-            ProductId::EAN(id) => id,
+            ProductId::ISRC(id) => id.deref(),
+            ProductId::UPC(id) => id.deref(),
+            ProductId::EAN(id) => id.deref(),
             ProductId::Provider((_, id)) => id,
         }
     }
+
+    /// Id of a barcode (UPC-A, EAN-13...), as a UPC when the product has one
+    pub fn barcode(value: &str) -> Result<Self, ProductIdError> {
+        match Upc::parse(value) {
+            Ok(upc) => Ok(ProductId::UPC(upc)),
+            Err(_) => Ean::parse(value).map(ProductId::EAN),
+        }
+    }
+}
+
+/// Wire representation of [`ProductId`], flattening the provider of provider ids
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ProductIdWire {
+    Isrc { value: String },
+    Upc { value: String },
+    Ean { value: String },
+    Provider { provider: ProviderId, value: String },
+}
+
+#[cfg(feature = "serde")]
+impl From<ProductId> for ProductIdWire {
+    fn from(product_id: ProductId) -> Self {
+        match product_id {
+            ProductId::ISRC(isrc) => ProductIdWire::Isrc {
+                value: isrc.to_string(),
+            },
+            ProductId::UPC(upc) => ProductIdWire::Upc {
+                value: upc.to_string(),
+            },
+            ProductId::EAN(ean) => ProductIdWire::Ean {
+                value: ean.to_string(),
+            },
+            ProductId::Provider((provider, value)) => ProductIdWire::Provider { provider, value },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ProductIdWire> for ProductId {
+    type Error = ProductIdError;
+
+    fn try_from(wire: ProductIdWire) -> Result<Self, Self::Error> {
+        match wire {
+            ProductIdWire::Isrc { value } => Isrc::parse(&value).map(ProductId::ISRC),
+            ProductIdWire::Upc { value } => Upc::parse(&value).map(ProductId::UPC),
+            ProductIdWire::Ean { value } => Ean::parse(&value).map(ProductId::EAN),
+            ProductIdWire::Provider { provider, value } => {
+                Ok(ProductId::Provider((provider, value)))
+            }
+        }
+    }
 }
@@ -0,0 +1,62 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::provider_id::ProviderIdError;
+
+/// Numeric id of a Deezer track, album, artist or playlist, e.g. `3135556`
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct DeezerId {
+    id: u64,
+}
+
+impl DeezerId {
+    pub fn new(id: u64) -> Self {
+        Self { id }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, ProviderIdError> {
+        match value.chars().all(|char| char.is_ascii_digit()) {
+            true => value
+                .parse::<u64>()
+                .map(Self::new)
+                .map_err(|_| ProviderIdError::InvalidDeezerId(value.to_string())),
+            false => Err(ProviderIdError::InvalidDeezerId(value.to_string())),
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.id
+    }
+}
+
+impl FromStr for DeezerId {
+    type Err = ProviderIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for DeezerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_objects::provider::provider_id::ProviderIdError;
+
+    use super::DeezerId;
+
+    #[test]
+    fn test_parse_deezer_id() {
+        assert_eq!(DeezerId::parse("3135556").map(|id| id.value()), Ok(3135556));
+        assert_eq!(
+            DeezerId::parse("+3135556"),
+            Err(ProviderIdError::InvalidDeezerId("+3135556".to_string()))
+        );
+        assert!(DeezerId::parse("").is_err());
+        assert!(DeezerId::parse("6rqhFgbbKwnb9MLmUQDhG6").is_err());
+        assert!(DeezerId::parse("99999999999999999999999").is_err());
+    }
+}
@@ -1 +1,3 @@
+pub mod deezer_id;
 pub mod provider_id;
+pub mod spotify_id;
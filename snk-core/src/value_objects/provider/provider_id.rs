@@ -1,24 +1,130 @@
-use std::ops::Deref;
+use std::{borrow::Cow, fmt::Display, ops::Deref, str::FromStr};
 
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProviderIdError {
+    #[error("Invalid provider id: {0}")]
+    InvalidProviderId(String),
+    #[error("Invalid Spotify id: {0}")]
+    InvalidSpotifyId(String),
+    #[error("Invalid Spotify URI: {0}")]
+    InvalidSpotifyUri(String),
+    #[error("Invalid Deezer id: {0}")]
+    InvalidDeezerId(String),
+}
+
+/// Serialized as its plain string, e.g. `"spotify"`
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct ProviderId {
-    id: String,
+    id: Cow<'static, str>,
 }
 
 impl ProviderId {
+    pub const DEEZER: ProviderId = ProviderId::known("deezer");
+    pub const LASTFM: ProviderId = ProviderId::known("lastfm");
+    /// Local files (M3U, XSPF & CSV playlists)
+    pub const LOCAL: ProviderId = ProviderId::known("local");
+    pub const MUSICBRAINZ: ProviderId = ProviderId::known("musicbrainz");
+    pub const SOUNDCLOUD: ProviderId = ProviderId::known("soundcloud");
+    pub const SPOTIFY: ProviderId = ProviderId::known("spotify");
+    pub const TIDAL: ProviderId = ProviderId::known("tidal");
+    pub const YOUTUBE: ProviderId = ProviderId::known("youtube");
+
+    /// Providers with an integration
+    pub const KNOWN: [ProviderId; 8] = [
+        Self::DEEZER,
+        Self::LASTFM,
+        Self::LOCAL,
+        Self::MUSICBRAINZ,
+        Self::SOUNDCLOUD,
+        Self::SPOTIFY,
+        Self::TIDAL,
+        Self::YOUTUBE,
+    ];
+
     pub fn new(id: String) -> Self {
-        Self { id }
+        Self { id: Cow::Owned(id) }
+    }
+
+    const fn known(id: &'static str) -> Self {
+        Self {
+            id: Cow::Borrowed(id),
+        }
+    }
+
+    /// Provider id written by a user or a client, lowercased:
+    /// letters, digits, `-` & `_` only
+    pub fn parse(value: &str) -> Result<Self, ProviderIdError> {
+        let id = value.trim().to_ascii_lowercase();
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || matches!(char, '-' | '_'));
+
+        match valid {
+            true => Ok(Self::new(id)),
+            false => Err(ProviderIdError::InvalidProviderId(value.to_string())),
+        }
+    }
+
+    /// Whether the provider has an integration
+    pub fn is_known(&self) -> bool {
+        Self::KNOWN.contains(self)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
     }
 
     pub fn value(&self) -> String {
-        self.id.clone()
+        self.id.to_string()
+    }
+}
+
+impl FromStr for ProviderId {
+    type Err = ProviderIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for ProviderId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
     }
 }
 
 impl Deref for ProviderId {
-    type Target = String;
+    type Target = str;
 
     fn deref(&self) -> &Self::Target {
         &self.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ProviderId, ProviderIdError};
+
+    #[test]
+    fn test_parse_provider_id() {
+        assert_eq!(ProviderId::parse(" Spotify"), Ok(ProviderId::SPOTIFY));
+        assert_eq!(ProviderId::new("deezer".to_string()), ProviderId::DEEZER);
+        assert!(ProviderId::parse("tidal").unwrap().is_known());
+        assert!(!ProviderId::parse("apple-music").unwrap().is_known());
+        assert_eq!(
+            ProviderId::parse("spotify:track"),
+            Err(ProviderIdError::InvalidProviderId(
+                "spotify:track".to_string()
+            ))
+        );
+        assert!(ProviderId::parse("").is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use std::{fmt::Display, ops::Deref, str::FromStr};
+
+use url::Url;
+
+use super::provider_id::ProviderIdError;
+
+/// Kind of resource a Spotify id points to
+#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub enum SpotifyResource {
+    Track,
+    Album,
+    Artist,
+    Playlist,
+}
+
+impl SpotifyResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpotifyResource::Track => "track",
+            SpotifyResource::Album => "album",
+            SpotifyResource::Artist => "artist",
+            SpotifyResource::Playlist => "playlist",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "track" => Some(SpotifyResource::Track),
+            "album" => Some(SpotifyResource::Album),
+            "artist" => Some(SpotifyResource::Artist),
+            "playlist" => Some(SpotifyResource::Playlist),
+            _ => None,
+        }
+    }
+}
+
+/// Base62 id of a Spotify track, album, artist or playlist, e.g. `6rqhFgbbKwnb9MLmUQDhG6`
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct SpotifyId {
+    id: String,
+}
+
+impl SpotifyId {
+    pub fn parse(value: &str) -> Result<Self, ProviderIdError> {
+        let valid = value.len() == 22 && value.chars().all(|char| char.is_ascii_alphanumeric());
+
+        match valid {
+            true => Ok(Self {
+                id: value.to_string(),
+            }),
+            false => Err(ProviderIdError::InvalidSpotifyId(value.to_string())),
+        }
+    }
+
+    pub fn to_uri(&self, resource: SpotifyResource) -> SpotifyUri {
+        SpotifyUri {
+            resource,
+            id: self.clone(),
+        }
+    }
+}
+
+impl FromStr for SpotifyId {
+    type Err = ProviderIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for SpotifyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl Deref for SpotifyId {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.id
+    }
+}
+
+/// Resource of Spotify, written as `spotify:track:{id}`
+///
+/// Also parsed from share URLs: `https://open.spotify.com/intl-fr/track/{id}?si=...`
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct SpotifyUri {
+    resource: SpotifyResource,
+    id: SpotifyId,
+}
+
+impl SpotifyUri {
+    pub fn new(resource: SpotifyResource, id: SpotifyId) -> Self {
+        Self { resource, id }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, ProviderIdError> {
+        let uri = match value.strip_prefix("spotify:") {
+            Some(uri) => uri
+                .split_once(':')
+                .and_then(|(resource, id)| Self::from_parts(resource, id)),
+            None => value.parse::<Url>().ok().and_then(|url| {
+                if !matches!(
+                    url.host_str(),
+                    Some("open.spotify.com" | "play.spotify.com")
+                ) {
+                    return None;
+                }
+
+                let segments = url
+                    .path_segments()?
+                    .filter(|segment| {
+                        !segment.is_empty() && *segment != "embed" && !segment.starts_with("intl-")
+                    })
+                    .collect::<Vec<_>>();
+
+                match segments[..] {
+                    [resource, id] => Self::from_parts(resource, id),
+                    _ => None,
+                }
+            }),
+        };
+
+        uri.ok_or(ProviderIdError::InvalidSpotifyUri(value.to_string()))
+    }
+
+    fn from_parts(resource: &str, id: &str) -> Option<Self> {
+        Some(Self {
+            resource: SpotifyResource::parse(resource)?,
+            id: SpotifyId::parse(id).ok()?,
+        })
+    }
+
+    pub fn resource(&self) -> SpotifyResource {
+        self.resource
+    }
+
+    pub fn id(&self) -> &SpotifyId {
+        &self.id
+    }
+
+    /// Share URL of the resource on the Spotify web player
+    pub fn url(&self) -> Url {
+        Url::parse(&format!(
+            "https://open.spotify.com/{}/{}",
+            self.resource.as_str(),
+            self.id
+        ))
+        .expect("valid Spotify URL")
+    }
+}
+
+impl FromStr for SpotifyUri {
+    type Err = ProviderIdError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl Display for SpotifyUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "spotify:{}:{}", self.resource.as_str(), self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_objects::provider::provider_id::ProviderIdError;
+
+    use super::{SpotifyId, SpotifyResource, SpotifyUri};
+
+    #[test]
+    fn test_parse_spotify_id() {
+        let id = SpotifyId::parse("6rqhFgbbKwnb9MLmUQDhG6").expect("valid id");
+
+        assert_eq!(
+            id.to_uri(SpotifyResource::Track).to_string(),
+            "spotify:track:6rqhFgbbKwnb9MLmUQDhG6"
+        );
+        assert_eq!(
+            SpotifyId::parse("6rqhFgbbKwnb9MLmUQDhG"),
+            Err(ProviderIdError::InvalidSpotifyId(
+                "6rqhFgbbKwnb9MLmUQDhG".to_string()
+            ))
+        );
+        assert!(SpotifyId::parse("6rqhFgbbKwnb9MLmUQDh/6").is_err());
+    }
+
+    #[test]
+    fn test_parse_spotify_uri() {
+        let uri = SpotifyUri::parse("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").expect("valid uri");
+
+        assert_eq!(uri.resource(), SpotifyResource::Track);
+        assert_eq!(uri.id().as_str(), "6rqhFgbbKwnb9MLmUQDhG6");
+        assert_eq!(
+            uri.url().as_str(),
+            "https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6"
+        );
+
+        for url in [
+            "https://open.spotify.com/track/6rqhFgbbKwnb9MLmUQDhG6",
+            "https://open.spotify.com/intl-fr/track/6rqhFgbbKwnb9MLmUQDhG6?si=2f4c1b0e6d8a4f2b",
+            "https://open.spotify.com/embed/track/6rqhFgbbKwnb9MLmUQDhG6",
+        ] {
+            assert_eq!(SpotifyUri::parse(url), Ok(uri.clone()));
+        }
+
+        assert!(SpotifyUri::parse("spotify:show:6rqhFgbbKwnb9MLmUQDhG6").is_err());
+        assert!(SpotifyUri::parse("spotify:track:12345").is_err());
+        assert!(SpotifyUri::parse("https://www.deezer.com/track/6rqhFgbbKwnb9MLmUQDhG6").is_err());
+        assert!(SpotifyUri::parse("https://open.spotify.com/track").is_err());
+    }
+}
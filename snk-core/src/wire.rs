@@ -0,0 +1,275 @@
+//! Versioned wire format of the entities & value objects, behind the `serde` feature.
+//!
+//! Entities are serialized as camelCase objects, unknown optional metadata being `null`.
+//! Documents stored or sent outside of the process (caches, exports...) are wrapped in a
+//! [`Versioned`] envelope, so that a breaking change of the representations can be detected.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Version of the representations, bumped on each breaking change
+pub const WIRE_VERSION: u32 = 1;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum WireError {
+    #[error("Unsupported wire version: {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// Document tagged with the version of its representation:
+/// `{"version":1,"data":...}`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(try_from = "VersionedRepr<T>")]
+pub struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct VersionedRepr<T> {
+    version: u32,
+    data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wrap the data in the current version
+    pub fn new(data: T) -> Self {
+        Self {
+            version: WIRE_VERSION,
+            data,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<T> TryFrom<VersionedRepr<T>> for Versioned<T> {
+    type Error = WireError;
+
+    fn try_from(repr: VersionedRepr<T>) -> Result<Self, Self::Error> {
+        match repr.version {
+            WIRE_VERSION => Ok(Self {
+                version: repr.version,
+                data: repr.data,
+            }),
+            version => Err(WireError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use serde_json::json;
+    use url::Url;
+
+    use crate::{
+        entities::{playlist::Playlist, track::TrackWithAlbumAndArtists},
+        value_objects::{
+            barcode::{Ean, Upc},
+            image_cover::ImageCover,
+            isrc::Isrc,
+            playlist_id::PlaylistId,
+            product_id::ProductId,
+            provider::provider_id::ProviderId,
+        },
+    };
+
+    use super::{Versioned, WireError, WIRE_VERSION};
+
+    fn spotify() -> ProviderId {
+        ProviderId::SPOTIFY
+    }
+
+    fn track_json() -> serde_json::Value {
+        json!({
+            "ids": [
+                { "type": "isrc", "value": "USAT21900366" },
+                { "type": "provider", "provider": "spotify", "value": "3QUOtaV4eHkPxRfE1cP3oh" }
+            ],
+            "name": "Nights Like This",
+            "durationMs": 201000,
+            "providerUrls": { "spotify": "https://open.spotify.com/track/3QUOtaV4eHkPxRfE1cP3oh" },
+            "explicit": true,
+            "discNumber": 1,
+            "trackNumber": 2,
+            "previewUrl": null,
+            "availableMarkets": ["FR", "SE"],
+            "playable": null,
+            "album": {
+                "ids": [{ "type": "upc", "value": "075679816290" }],
+                "name": "Nights Like This",
+                "releaseDate": "2019-02-08T00:00:00Z",
+                "covers": [{ "size": "lg", "url": "https://i.scdn.co/image/ab67616d0000b273" }],
+                "providerUrls": {}
+            },
+            "artists": [{
+                "ids": { "spotify": "2xvtxDNInKDV4AvGmjw6d1" },
+                "name": "Kehlani",
+                "providerUrls": {}
+            }]
+        })
+    }
+
+    #[test]
+    fn test_product_id() {
+        let isrc = ProductId::ISRC(Isrc::parse("USAT21900366").unwrap());
+        let provider = ProductId::Provider((spotify(), "3QUOtaV4eHkPxRfE1cP3oh".to_string()));
+
+        assert_eq!(
+            serde_json::to_value(&isrc).unwrap(),
+            json!({ "type": "isrc", "value": "USAT21900366" })
+        );
+        assert_eq!(
+            serde_json::to_value(&provider).unwrap(),
+            json!({ "type": "provider", "provider": "spotify", "value": "3QUOtaV4eHkPxRfE1cP3oh" })
+        );
+
+        for product_id in [
+            isrc,
+            provider,
+            ProductId::EAN(Ean::parse("5099749534728").unwrap()),
+        ] {
+            let value = serde_json::to_value(&product_id).unwrap();
+
+            assert!(serde_json::from_value::<ProductId>(value).unwrap() == product_id);
+        }
+
+        assert!(
+            serde_json::from_value::<ProductId>(json!({ "type": "gtin", "value": "1" })).is_err()
+        );
+        assert!(
+            serde_json::from_value::<ProductId>(
+                json!({ "type": "isrc", "value": "us-a2p-24-14843" })
+            )
+            .unwrap()
+                == ProductId::ISRC(Isrc::parse("USA2P2414843").unwrap())
+        );
+        assert!(
+            serde_json::from_value::<ProductId>(json!({ "type": "upc", "value": "1" })).is_err()
+        );
+    }
+
+    #[test]
+    fn test_value_objects() {
+        let cover = ImageCover::Md(Url::parse("https://example.com/md.jpg").unwrap());
+
+        assert_eq!(serde_json::to_value(spotify()).unwrap(), json!("spotify"));
+        assert_eq!(
+            serde_json::to_value(&cover).unwrap(),
+            json!({ "size": "md", "url": "https://example.com/md.jpg" })
+        );
+        assert_eq!(
+            serde_json::from_value::<ImageCover>(serde_json::to_value(&cover).unwrap()).unwrap(),
+            cover
+        );
+        assert_eq!(
+            serde_json::to_value(PlaylistId::LikedSongs).unwrap(),
+            json!({ "type": "likedSongs" })
+        );
+        assert!(matches!(
+            serde_json::from_value::<PlaylistId>(json!({ "type": "owned", "value": "road-trip" })).unwrap(),
+            PlaylistId::Owned(id) if id == "road-trip"
+        ));
+    }
+
+    #[test]
+    fn test_playlist_round_trip() {
+        let mut playlist = Playlist::new(
+            PlaylistId::Owned("road-trip".to_string()),
+            "Road trip".to_string(),
+            HashSet::from_iter([ImageCover::Lg(
+                Url::parse("https://example.com/lg.jpg").unwrap(),
+            )]),
+            "me".to_string(),
+            12,
+            Url::parse("https://example.com/road-trip").unwrap(),
+        );
+        playlist.set_description(Some("Songs for the road".to_string()));
+        playlist.set_public(Some(true));
+
+        let value = serde_json::to_value(&playlist).unwrap();
+
+        assert_eq!(
+            value["id"],
+            json!({ "type": "owned", "value": "road-trip" })
+        );
+        assert_eq!(value["totalSongs"], json!(12));
+        assert_eq!(value["collaborative"], json!(null));
+
+        let deserialized = serde_json::from_value::<Playlist>(value.clone()).unwrap();
+
+        assert_eq!(deserialized.name(), "Road trip");
+        assert_eq!(deserialized.description(), playlist.description());
+        assert_eq!(deserialized.public(), Some(true));
+        assert_eq!(deserialized.covers(), playlist.covers());
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), value);
+    }
+
+    #[test]
+    fn test_track_round_trip() {
+        let track = serde_json::from_value::<TrackWithAlbumAndArtists>(track_json()).unwrap();
+
+        assert_eq!(track.name(), "Nights Like This");
+        assert_eq!(track.duration_ms(), 201000);
+        assert!(track
+            .ids()
+            .contains(&ProductId::ISRC(Isrc::parse("USAT21900366").unwrap())));
+        assert_eq!(track.explicit(), Some(true));
+        assert_eq!(track.is_available_in("se"), Some(true));
+        assert_eq!(
+            track.album().release_date().to_rfc3339(),
+            "2019-02-08T00:00:00+00:00"
+        );
+        assert!(track
+            .album()
+            .ids()
+            .contains(&ProductId::UPC(Upc::parse("075679816290").unwrap())));
+        assert_eq!(
+            track.artists()[0].ids(),
+            &HashMap::from_iter([(spotify(), "2xvtxDNInKDV4AvGmjw6d1".to_string())])
+        );
+
+        let deserialized = serde_json::from_value::<TrackWithAlbumAndArtists>(
+            serde_json::to_value(&track).unwrap(),
+        )
+        .unwrap();
+
+        assert!(deserialized.ids() == track.ids());
+        assert_eq!(deserialized.provider_urls(), track.provider_urls());
+        assert_eq!(deserialized.available_markets(), track.available_markets());
+        assert_eq!(deserialized.track_number(), Some(2));
+        assert_eq!(deserialized.album().covers(), track.album().covers());
+        assert!(deserialized.artists() == track.artists());
+    }
+
+    #[test]
+    fn test_versioned() {
+        let value = json!({ "version": WIRE_VERSION, "data": track_json() });
+        let document =
+            serde_json::from_value::<Versioned<TrackWithAlbumAndArtists>>(value).unwrap();
+
+        assert_eq!(document.version(), WIRE_VERSION);
+
+        let serialized = serde_json::to_value(Versioned::new(document.into_data())).unwrap();
+
+        assert_eq!(serialized["version"], json!(1));
+        assert_eq!(serialized["data"]["name"], json!("Nights Like This"));
+
+        let result = serde_json::from_value::<Versioned<TrackWithAlbumAndArtists>>(
+            json!({ "version": 2, "data": track_json() }),
+        );
+
+        assert!(matches!(
+            result,
+            Err(error) if error.to_string() == WireError::UnsupportedVersion(2).to_string()
+        ));
+    }
+}